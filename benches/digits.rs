@@ -0,0 +1,113 @@
+//! Compares the lookup-table and SIMD digit extraction with the previous implementation,
+//! which divided by 10 once per digit.
+//!
+//! Run with `cargo bench --bench digits --features std`.
+
+use dd_maths_traits::num_sys::base_10::int::{
+    fast_digits::{padded_digits_le_u32s, padded_digits_le_u64s},
+    new_vec_u8_of_digits_base_10::{NewVecU8OfDigitsBase10Le, U8DigitsBase10LeInto},
+};
+use std::{hint::black_box, time::Instant};
+
+const LEN: usize = 1 << 16;
+const ROUNDS: u32 = 20;
+
+fn one_digit_per_division_le_into(n: u64, buf: &mut [u8]) -> usize {
+    let mut len = 0;
+    let mut q = n;
+    loop {
+        let (new_q, r) = (q / 10, q % 10);
+        q = new_q;
+        buf[len] = r as u8;
+        len += 1;
+        if q == 0 {
+            break len;
+        };
+    }
+}
+
+fn one_digit_per_division_le(n: u64) -> Vec<u8> {
+    let mut vec = Vec::<u8>::with_capacity(20);
+    let mut q = n;
+    loop {
+        let (new_q, r) = (q / 10, q % 10);
+        q = new_q;
+        vec.push(r as u8);
+        if q == 0 {
+            break;
+        };
+    }
+    vec
+}
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    f();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let ns_per_value = start.elapsed().as_secs_f64() * 1e9 / f64::from(ROUNDS) / LEN as f64;
+    println!("{name:<40} {ns_per_value:>8.2} ns per value");
+}
+
+fn main() {
+    let u64s: Vec<u64> = (0..LEN as u64)
+        .map(|i| i.wrapping_mul(11_400_714_819_323_198_485) >> (i % 64))
+        .collect();
+    let u32s: Vec<u32> = u64s.iter().map(|&v| v as u32).collect();
+
+    bench("u64: one digit per division (Vec)", || {
+        for &v in &u64s {
+            black_box(one_digit_per_division_le(black_box(v)));
+        }
+    });
+    bench("u64: new_vec_u8_of_digits_le (Vec)", || {
+        for &v in &u64s {
+            black_box(black_box(v).new_vec_u8_of_digits_le());
+        }
+    });
+
+    let mut buf = [0u8; 20];
+    bench("u64: one digit per division (buffer)", || {
+        for &v in &u64s {
+            black_box(one_digit_per_division_le_into(black_box(v), &mut buf));
+        }
+    });
+    bench("u64: u8_digits_le_into (buffer)", || {
+        for &v in &u64s {
+            black_box(black_box(v).u8_digits_le_into(&mut buf));
+        }
+    });
+
+    let mut out_u32 = vec![[0u8; 10]; LEN];
+    bench("u32: one digit per division (padded)", || {
+        for (&v, digits) in u32s.iter().zip(out_u32.iter_mut()) {
+            let mut q = black_box(v);
+            for d in digits.iter_mut() {
+                *d = (q % 10) as u8;
+                q /= 10;
+            }
+        }
+        black_box(&out_u32);
+    });
+    bench("u32: padded_digits_le_u32s (padded)", || {
+        padded_digits_le_u32s(black_box(&u32s), &mut out_u32);
+        black_box(&out_u32);
+    });
+
+    let mut out_u64 = vec![[0u8; 20]; LEN];
+    bench("u64: one digit per division (padded)", || {
+        for (&v, digits) in u64s.iter().zip(out_u64.iter_mut()) {
+            let mut q = black_box(v);
+            for d in digits.iter_mut() {
+                *d = (q % 10) as u8;
+                q /= 10;
+            }
+        }
+        black_box(&out_u64);
+    });
+    bench("u64: padded_digits_le_u64s (padded)", || {
+        padded_digits_le_u64s(black_box(&u64s), &mut out_u64);
+        black_box(&out_u64);
+    });
+}
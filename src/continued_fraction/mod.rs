@@ -0,0 +1,480 @@
+//! [Continued fractions](https://en.wikipedia.org/wiki/Continued_fraction) `[a0; a1, a2, ...]`, their convergents
+//! and semiconvergents, and the best rational approximations.
+//!
+//! The terms after the first one are positive, while the first one is the floor of the value and can be
+//! of any sign. The rationals have finite continued fractions, the [quadratic irrationals][quadratic]
+//! have periodic ones, and the [Stern–Brocot tree][stern_brocot] follows the continued fractions too.
+//!
+//! ```rust
+//! use dd_maths_traits::continued_fraction::{best_approximation, Convergents};
+//! use dd_maths_traits::rational::Ratio;
+//!
+//! let x = Ratio::new(415i32, 93).unwrap();
+//! let terms: Vec<i32> = x.continued_fraction_terms().collect();
+//! assert_eq!(terms, [4, 2, 6, 7]);
+//!
+//! let convergents: Vec<Ratio<i32>> = Convergents::new(terms).collect();
+//! assert_eq!(convergents.last(), Some(&x));
+//! assert_eq!(convergents[1], Ratio::new(9, 2).unwrap());
+//!
+//! // 58/13 is the semiconvergent [4; 2, 6] with the last term reduced to 3
+//! assert_eq!(best_approximation(&x, &13), Ratio::new(58, 13));
+//! ```
+
+pub mod quadratic;
+pub mod stern_brocot;
+
+use crate::rational::{div_floor, Ratio, RatioInt};
+use num_traits::{float::FloatCore, FromPrimitive, ToPrimitive};
+
+/// The iterator over the terms of the continued fraction of a rational, computed with the Euclidean algorithm.
+#[derive(Clone, Debug)]
+pub struct RationalTerms<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: RatioInt> Iterator for RationalTerms<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.denom.is_zero() {
+            return None;
+        }
+        let (q, r) = div_floor(self.numer.clone(), &self.denom);
+        self.numer = core::mem::replace(&mut self.denom, r);
+        Some(q)
+    }
+}
+
+impl<T: RatioInt> Ratio<T> {
+    /// Returns the terms of the continued fraction, whose last term is greater than 1 unless
+    /// it is the only one.
+    #[inline]
+    pub fn continued_fraction_terms(&self) -> RationalTerms<T> {
+        RationalTerms {
+            numer: self.numer().clone(),
+            denom: self.denom().clone(),
+        }
+    }
+
+    /// Returns the value of the finite continued fraction or [None] if it is empty, a term after the first one
+    /// is not positive or a convergent is not representable.
+    pub fn from_continued_fraction_terms<I: IntoIterator<Item = T>>(terms: I) -> Option<Self> {
+        let mut convergents = Convergents::new(terms);
+        let mut last = None;
+        for convergent in convergents.by_ref() {
+            last = Some(convergent);
+        }
+        if convergents.is_interrupted {
+            return None;
+        }
+        last
+    }
+}
+
+// The convergents h(n)/k(n) with h(n) = a(n) * h(n - 1) + h(n - 2), k(n) = a(n) * k(n - 1) + k(n - 2),
+// starting from h(-1)/k(-1) = 1/0 and h(-2)/k(-2) = 0/1
+#[derive(Clone, Debug)]
+struct Recurrence<T> {
+    h1: T,
+    k1: T,
+    h2: T,
+    k2: T,
+}
+
+impl<T: RatioInt> Recurrence<T> {
+    fn new() -> Self {
+        Self {
+            h1: T::one(),
+            k1: T::zero(),
+            h2: T::zero(),
+            k2: T::one(),
+        }
+    }
+
+    #[inline]
+    fn is_before_first_term(&self) -> bool {
+        self.k1.is_zero()
+    }
+
+    // Returns (j * h(n - 1) + h(n - 2)) / (j * k(n - 1) + k(n - 2)) or None on overflow
+    fn intermediate(&self, j: &T) -> Option<(T, T)> {
+        let h = j.checked_mul(&self.h1)?.checked_add(&self.h2)?;
+        let k = j.checked_mul(&self.k1)?.checked_add(&self.k2)?;
+        Some((h, k))
+    }
+
+    fn push(&mut self, (h, k): (T, T)) {
+        self.h2 = core::mem::replace(&mut self.h1, h);
+        self.k2 = core::mem::replace(&mut self.k1, k);
+    }
+
+    fn last(&self) -> Ratio<T> {
+        // the consecutive convergents satisfy h(n) * k(n - 1) - h(n - 1) * k(n) = ±1
+        Ratio::from_canonical_parts(self.h1.clone(), self.k1.clone())
+    }
+}
+
+/// The iterator over the convergents of a continued fraction given by its terms. It stops at the first term
+/// after the first one that is not positive or at the first convergent that is not representable.
+#[derive(Clone, Debug)]
+pub struct Convergents<I, T> {
+    terms: I,
+    recurrence: Recurrence<T>,
+    is_interrupted: bool,
+}
+
+impl<I: Iterator<Item = T>, T: RatioInt> Convergents<I, T> {
+    pub fn new<J: IntoIterator<IntoIter = I>>(terms: J) -> Self {
+        Self {
+            terms: terms.into_iter(),
+            recurrence: Recurrence::new(),
+            is_interrupted: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = T>, T: RatioInt> Iterator for Convergents<I, T> {
+    type Item = Ratio<T>;
+
+    fn next(&mut self) -> Option<Ratio<T>> {
+        if self.is_interrupted {
+            return None;
+        }
+        let a = self.terms.next()?;
+        let next = Some(a)
+            .filter(|a| self.recurrence.is_before_first_term() || *a >= T::one())
+            .and_then(|a| self.recurrence.intermediate(&a));
+        let Some(next) = next else {
+            self.is_interrupted = true;
+            return None;
+        };
+        self.recurrence.push(next);
+        Some(self.recurrence.last())
+    }
+}
+
+/// The iterator over the semiconvergents of a continued fraction given by its terms, i.e.
+/// `(j * h(n - 1) + h(n - 2)) / (j * k(n - 1) + k(n - 2))` for `j` in `1..=a(n)` for every term `a(n)`
+/// after the first one, preceded by the first convergent `a(0)/1`. The convergents are the semiconvergents
+/// with `j = a(n)`.
+///
+/// The iteration stops like that of [Convergents].
+#[derive(Clone, Debug)]
+pub struct Semiconvergents<I, T> {
+    terms: I,
+    recurrence: Recurrence<T>,
+    // the current term and the next j
+    current: Option<(T, T)>,
+    is_interrupted: bool,
+}
+
+impl<I: Iterator<Item = T>, T: RatioInt> Semiconvergents<I, T> {
+    pub fn new<J: IntoIterator<IntoIter = I>>(terms: J) -> Self {
+        Self {
+            terms: terms.into_iter(),
+            recurrence: Recurrence::new(),
+            current: None,
+            is_interrupted: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = T>, T: RatioInt> Iterator for Semiconvergents<I, T> {
+    type Item = Ratio<T>;
+
+    fn next(&mut self) -> Option<Ratio<T>> {
+        if self.is_interrupted {
+            return None;
+        }
+        let (a, j) = match self.current.take() {
+            Some(current) => current,
+            None => {
+                let a = self.terms.next()?;
+                if self.recurrence.is_before_first_term() {
+                    (a.clone(), a)
+                } else if a >= T::one() {
+                    (a, T::one())
+                } else {
+                    self.is_interrupted = true;
+                    return None;
+                }
+            }
+        };
+        let Some((h, k)) = self.recurrence.intermediate(&j) else {
+            self.is_interrupted = true;
+            return None;
+        };
+        // the semiconvergents are in lowest terms as well
+        let semiconvergent = Ratio::from_canonical_parts(h.clone(), k.clone());
+        if j == a {
+            self.recurrence.push((h, k));
+        } else {
+            self.current = Some((a, j + T::one()));
+        }
+        Some(semiconvergent)
+    }
+}
+
+/// The iterator over the terms of the continued fraction of a float, computed in the floating point arithmetic,
+/// which makes the terms inexact once the convergents are about as precise as the float.
+/// It stops at the first term that is not representable by `T` and after 64 terms.
+#[derive(Clone, Debug)]
+pub struct FloatTerms<T> {
+    x: f64,
+    remaining: u32,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T: FromPrimitive> FloatTerms<T> {
+    /// Returns [None] if `x` is not finite.
+    pub fn new<F: FloatCore>(x: F) -> Option<Self> {
+        let x = x.to_f64().filter(|x| x.is_finite())?;
+        Some(Self {
+            x,
+            remaining: 64,
+            _marker: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T: FromPrimitive> Iterator for FloatTerms<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let a = FloatCore::floor(self.x);
+        let Some(term) = T::from_f64(a) else {
+            self.remaining = 0;
+            return None;
+        };
+        let fract = self.x - a;
+        if fract == 0.0 {
+            self.remaining = 0;
+        } else {
+            self.remaining -= 1;
+            self.x = 1.0 / fract;
+        }
+        Some(term)
+    }
+}
+
+/// Returns the closest fraction to `x` with the denominator at most `max_denominator` or [None] if `max_denominator`
+/// is not positive or no such fraction is representable. Of two equally close fractions,
+/// the one with the smaller denominator is returned.
+///
+/// The best approximations are the convergents and the semiconvergents `(j * h(n - 1) + h(n - 2)) / (j * k(n - 1) + k(n - 2))`
+/// with `j` at least a half of `a(n)`.
+pub fn best_approximation<T: RatioInt>(x: &Ratio<T>, max_denominator: &T) -> Option<Ratio<T>> {
+    best_approximation_from_terms(x.continued_fraction_terms(), max_denominator, |s, c| {
+        // |x - y| without the negative differences, which the unsigned integers cannot represent
+        let distance = |y: &Ratio<T>| {
+            if x >= y {
+                x.checked_sub(y)
+            } else {
+                y.checked_sub(x)
+            }
+        };
+        matches!((distance(s), distance(c)), (Some(ds), Some(dc)) if ds < dc)
+    })
+}
+
+/// Returns the closest fraction to the float `x` with the denominator at most `max_denominator` or [None] if
+/// `x` is not finite, `max_denominator` is not positive or no such fraction is representable.
+///
+/// The approximation is found like with [best_approximation] from the [FloatTerms] of `x`.
+pub fn best_approximation_float<F: FloatCore, T>(x: F, max_denominator: &T) -> Option<Ratio<T>>
+where
+    T: RatioInt + FromPrimitive + ToPrimitive,
+{
+    let terms = FloatTerms::new(x)?;
+    let x = x.to_f64()?;
+    best_approximation_from_terms(terms, max_denominator, |s, c| {
+        let distance = |y: &Ratio<T>| {
+            Some(FloatCore::abs(
+                x - y.numer().to_f64()? / y.denom().to_f64()?,
+            ))
+        };
+        matches!((distance(s), distance(c)), (Some(ds), Some(dc)) if ds < dc)
+    })
+}
+
+// `is_closer(s, c)` decides whether the semiconvergent `s` is closer than the convergent `c`
+// when it is admissible with j equal to the half of the term
+fn best_approximation_from_terms<T, I, F>(
+    terms: I,
+    max_denominator: &T,
+    is_closer: F,
+) -> Option<Ratio<T>>
+where
+    T: RatioInt,
+    I: Iterator<Item = T>,
+    F: Fn(&Ratio<T>, &Ratio<T>) -> bool,
+{
+    if *max_denominator < T::one() {
+        return None;
+    }
+    let mut recurrence = Recurrence::new();
+    for a in terms {
+        let next = recurrence
+            .intermediate(&a)
+            .filter(|(_, k)| k <= max_denominator);
+        if let Some(next) = next {
+            recurrence.push(next);
+            continue;
+        }
+        if recurrence.is_before_first_term() {
+            return None;
+        }
+        let convergent = recurrence.last();
+        let j = (max_denominator.clone() - recurrence.k2.clone()) / recurrence.k1.clone();
+        let rest = a - j.clone();
+        if j < rest {
+            return Some(convergent);
+        }
+        // j <= a, so the semiconvergent is representable
+        let (h, k) = recurrence.intermediate(&j)?;
+        let semiconvergent = Ratio::from_canonical_parts(h, k);
+        if j > rest || is_closer(&semiconvergent, &convergent) {
+            return Some(semiconvergent);
+        }
+        return Some(convergent);
+    }
+    if recurrence.is_before_first_term() {
+        return None;
+    }
+    Some(recurrence.last())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::continued_fraction::*;
+
+    #[test]
+    fn continued_fractions_of_rationals_round_trip() {
+        for n in -100i32..=100 {
+            for d in 1..=30 {
+                let x = Ratio::new(n, d).unwrap();
+                let terms: Vec<i32> = x.continued_fraction_terms().collect();
+                assert!(terms[1..].iter().all(|&a| a >= 1));
+                assert!(terms.len() == 1 || *terms.last().unwrap() > 1);
+                assert_eq!(Ratio::from_continued_fraction_terms(terms), Some(x));
+            }
+        }
+        let x = Ratio::new(-7i8, 3).unwrap();
+        assert_eq!(x.continued_fraction_terms().collect::<Vec<_>>(), [-3, 1, 2]);
+        assert_eq!(Ratio::<i8>::from_continued_fraction_terms([1, 0]), None);
+        assert_eq!(Ratio::<i8>::from_continued_fraction_terms([]), None);
+        assert_eq!(
+            Ratio::<u8>::from_continued_fraction_terms([255, 1, 255]),
+            None
+        );
+
+        use num_bigint::BigUint;
+
+        // the ratio of consecutive Fibonacci numbers has the terms 1, 1, ..., 1, 2
+        let (mut a, mut b) = (BigUint::from(1u32), BigUint::from(1u32));
+        for _ in 0..200 {
+            (a, b) = (&a + &b, a);
+        }
+        let x = Ratio::new(a, b).unwrap();
+        let terms: Vec<BigUint> = x.continued_fraction_terms().collect();
+        assert_eq!(terms.len(), 200);
+        assert!(terms[..199].iter().all(|a| *a == BigUint::from(1u32)));
+    }
+
+    #[test]
+    fn semiconvergents_include_convergents() {
+        let terms = [1u32, 2, 3];
+        let semiconvergents: Vec<Ratio<u32>> = Semiconvergents::new(terms).collect();
+        let expected = [(1, 1), (2, 1), (3, 2), (4, 3), (7, 5), (10, 7)];
+        let expected: Vec<Ratio<u32>> = expected
+            .iter()
+            .map(|&(n, d)| Ratio::new(n, d).unwrap())
+            .collect();
+        assert_eq!(semiconvergents, expected);
+        let convergents: Vec<Ratio<u32>> = Convergents::new(terms).collect();
+        assert!(convergents.iter().all(|c| semiconvergents.contains(c)));
+    }
+
+    #[test]
+    fn best_approximations_are_the_closest_fractions() {
+        // the brute force search over all the denominators
+        let closest = |x: Ratio<i64>, max_denominator: i64| {
+            (1..=max_denominator)
+                .flat_map(|d| {
+                    let n = (x * Ratio::from_integer(d)).floor();
+                    [n, n + 1].map(|n| Ratio::new(n, d).unwrap())
+                })
+                .min_by_key(|y| {
+                    let distance = x - *y;
+                    (distance.max(-distance), *y.denom())
+                })
+                .unwrap()
+        };
+        for (n, d) in [
+            (314_159, 100_000),
+            (-271_828, 100_000),
+            (5, 8),
+            (1, 2),
+            (7, 1),
+        ] {
+            let x = Ratio::new(n, d).unwrap();
+            for max_denominator in 1..=60 {
+                assert_eq!(
+                    best_approximation(&x, &max_denominator),
+                    Some(closest(x, max_denominator)),
+                );
+            }
+        }
+        // the unsigned fractions agree with the signed ones
+        for n in 0..=60u32 {
+            for d in 1..=60u32 {
+                let x = Ratio::new(n, d).unwrap();
+                let signed = Ratio::new(i64::from(n), i64::from(d)).unwrap();
+                for max_denominator in 1..=40u32 {
+                    let expected =
+                        best_approximation(&signed, &i64::from(max_denominator)).unwrap();
+                    let y = best_approximation(&x, &max_denominator).unwrap();
+                    assert_eq!(
+                        (i64::from(*y.numer()), i64::from(*y.denom())),
+                        expected.into_parts()
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            best_approximation(&Ratio::new(2u32, 3).unwrap(), &2),
+            Ratio::new(1, 2)
+        );
+        assert_eq!(
+            best_approximation(&Ratio::new(2u32, 5).unwrap(), &3),
+            Ratio::new(1, 3)
+        );
+        assert_eq!(best_approximation(&Ratio::new(1u8, 3).unwrap(), &0), None);
+    }
+
+    #[test]
+    fn floats_are_approximated() {
+        let sqrt_2 = core::f64::consts::SQRT_2;
+        let terms: Vec<u64> = FloatTerms::new(sqrt_2).unwrap().take(10).collect();
+        assert_eq!(terms, [1, 2, 2, 2, 2, 2, 2, 2, 2, 2]);
+        assert_eq!(
+            best_approximation_float(sqrt_2, &100u32),
+            Ratio::new(140, 99)
+        );
+        assert_eq!(best_approximation_float(0.5f32, &1u32), Ratio::new(0, 1));
+        assert_eq!(best_approximation_float(f64::INFINITY, &10u32), None);
+
+        use num_bigint::BigInt;
+
+        let x = best_approximation_float(core::f64::consts::E, &BigInt::from(1_000_000)).unwrap();
+        assert_eq!(
+            x,
+            Ratio::new(BigInt::from(1_084_483), BigInt::from(398_959)).unwrap()
+        );
+    }
+}
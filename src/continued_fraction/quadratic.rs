@@ -0,0 +1,232 @@
+//! The periodic continued fractions of the [quadratic irrationals](https://en.wikipedia.org/wiki/Quadratic_irrational_number)
+//! `(p + √d) / q`.
+//!
+//! The complete quotients `(P + √D) / Q` of such a number keep `Q` dividing `D - P²`, so the terms are computed
+//! with integers only. The continued fraction becomes periodic once a complete quotient is reduced, i.e. greater
+//! than 1 with the conjugate in `(-1, 0)`.
+//!
+//! ```rust
+//! use dd_maths_traits::continued_fraction::quadratic::QuadraticIrrational;
+//!
+//! let sqrt_7 = QuadraticIrrational::sqrt(7i32).unwrap();
+//! let (pre_period, period) = sqrt_7.periodic_continued_fraction().unwrap();
+//! assert_eq!((pre_period, period), (vec![2], vec![1, 1, 1, 4]));
+//!
+//! // the golden ratio (1 + √5) / 2 = [1; 1, 1, ...]
+//! let phi = QuadraticIrrational::new(1i32, 5, 2).unwrap();
+//! assert!(phi.terms().take(10).all(|a| a == 1));
+//! ```
+
+use crate::rational::{div_floor, RatioInt};
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::vec::Vec;
+use num_traits::Signed;
+
+/// The quadratic irrational `(p + √d) / q` with the integers `p`, `d`, `q`, where `d` is not a perfect square.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QuadraticIrrational<T> {
+    p: T,
+    d: T,
+    q: T,
+}
+
+impl<T: RatioInt + Signed> QuadraticIrrational<T> {
+    /// Returns `(p + √d) / q` or [None] if `q` is 0, `d` is negative or a perfect square, or the representation
+    /// with `q` dividing `d - p²`, which scales all of `p`, `d`, `q`, is not representable.
+    pub fn new(p: T, d: T, q: T) -> Option<Self> {
+        if q.is_zero() || d.is_negative() {
+            return None;
+        }
+        let s = isqrt(&d);
+        if s.clone() * s == d {
+            return None;
+        }
+        let r = d.checked_sub(&p.checked_mul(&p)?)?;
+        if (r % q.clone()).is_zero() {
+            return Some(Self { p, d, q });
+        }
+        // (p + √d) / q = (p|q| + √(dq²)) / (q|q|) and q|q| divides (d - p²)q²
+        let abs_q = q.abs();
+        Some(Self {
+            p: p.checked_mul(&abs_q)?,
+            d: d.checked_mul(&q)?.checked_mul(&q)?,
+            q: q.checked_mul(&abs_q)?,
+        })
+    }
+
+    /// Returns `√d` or [None] if `d` is negative or a perfect square.
+    #[inline]
+    pub fn sqrt(d: T) -> Option<Self> {
+        Self::new(T::zero(), d, T::one())
+    }
+
+    /// Returns `(p, d, q)` such that the number is `(p + √d) / q` and `q` divides `d - p²`.
+    #[inline(always)]
+    pub fn parts(&self) -> (&T, &T, &T) {
+        (&self.p, &self.d, &self.q)
+    }
+
+    /// Returns the infinite iterator over the terms of the continued fraction,
+    /// which stops early only if a complete quotient is not representable.
+    #[inline]
+    pub fn terms(&self) -> QuadraticTerms<T> {
+        QuadraticTerms {
+            p: self.p.clone(),
+            q: self.q.clone(),
+            s: isqrt(&self.d),
+            d: self.d.clone(),
+            is_interrupted: false,
+        }
+    }
+
+    /// Returns the terms of the continued fraction before the period and the terms of the period
+    /// or [None] if a complete quotient is not representable.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    pub fn periodic_continued_fraction(&self) -> Option<(Vec<T>, Vec<T>)> {
+        let mut terms = self.terms();
+        let mut pre_period = Vec::new();
+        while !terms.is_reduced() {
+            pre_period.push(terms.next()?);
+        }
+        let first = (terms.p.clone(), terms.q.clone());
+        let mut period = Vec::new();
+        loop {
+            period.push(terms.next()?);
+            if (&terms.p, &terms.q) == (&first.0, &first.1) {
+                return Some((pre_period, period));
+            }
+        }
+    }
+}
+
+/// The iterator over the terms of the continued fraction of a [QuadraticIrrational].
+#[derive(Clone, Debug)]
+pub struct QuadraticTerms<T> {
+    // the complete quotient (p + √d) / q with s = ⌊√d⌋
+    p: T,
+    q: T,
+    d: T,
+    s: T,
+    is_interrupted: bool,
+}
+
+impl<T: RatioInt + Signed> QuadraticTerms<T> {
+    // Whether the complete quotient is greater than 1 and its conjugate (p - √d) / q is in (-1, 0)
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn is_reduced(&self) -> bool {
+        let (p, q, s) = (&self.p, &self.q, &self.s);
+        q.is_positive() && p <= s && p.clone() + s.clone() >= *q && s.clone() - p.clone() < *q
+    }
+
+    fn step(&mut self) -> Option<T> {
+        let (p, q, s) = (&self.p, &self.q, &self.s);
+        // ⌊(p + √d) / q⌋ = ⌊(p + s) / q⌋ for the positive q and ⌊(p + s + 1) / q⌋ for the negative one,
+        // since √d is not an integer
+        let a = if q.is_positive() {
+            div_floor(p.checked_add(s)?, q).0
+        } else {
+            let n = p.checked_add(s)?.checked_add(&T::one())?;
+            div_floor(T::zero().checked_sub(&n)?, &T::zero().checked_sub(q)?).0
+        };
+        let next_p = a.checked_mul(q)?.checked_sub(p)?;
+        let next_q = self.d.checked_sub(&next_p.checked_mul(&next_p)?)? / q.clone();
+        self.p = next_p;
+        self.q = next_q;
+        Some(a)
+    }
+}
+
+impl<T: RatioInt + Signed> Iterator for QuadraticTerms<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.is_interrupted {
+            return None;
+        }
+        let a = self.step();
+        self.is_interrupted = a.is_none();
+        a
+    }
+}
+
+// ⌊√n⌋ for the non-negative n with Newton's method, whose iterates decrease to it from above
+fn isqrt<T: RatioInt>(n: &T) -> T {
+    if n.is_zero() {
+        return T::zero();
+    }
+    let two = T::one() + T::one();
+    let mut x = n.clone();
+    loop {
+        // ⌊(x + n / x) / 2⌋ without the overflow of the sum
+        let y = n.clone() / x.clone();
+        let y = x.clone() / two.clone()
+            + y.clone() / two.clone()
+            + (x.clone() % two.clone() + y % two.clone()) / two.clone();
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::continued_fraction::quadratic::{isqrt, QuadraticIrrational};
+
+    #[test]
+    fn square_roots_have_periodic_continued_fractions() {
+        for n in 0i64..10_000 {
+            let s = isqrt(&n);
+            assert!(s * s <= n && (s + 1) * (s + 1) > n);
+        }
+        assert_eq!(isqrt(&i32::MAX), 46_340);
+        for d in 2i32..1000 {
+            let Some(x) = QuadraticIrrational::sqrt(d) else {
+                assert_eq!(isqrt(&d) * isqrt(&d), d);
+                continue;
+            };
+            let (pre_period, period) = x.periodic_continued_fraction().unwrap();
+            // √d = [a0; a1, ..., a1, 2a0] with the palindromic a1, ..., a1
+            assert_eq!(pre_period, [isqrt(&d)]);
+            let (last, rest) = period.split_last().unwrap();
+            assert_eq!(*last, 2 * pre_period[0]);
+            assert!(rest.iter().eq(rest.iter().rev()));
+        }
+        let (_, period) = QuadraticIrrational::sqrt(94i32)
+            .unwrap()
+            .periodic_continued_fraction()
+            .unwrap();
+        assert_eq!(period, [1, 2, 3, 1, 1, 5, 1, 8, 1, 5, 1, 1, 3, 2, 1, 18]);
+    }
+
+    #[test]
+    fn quadratic_irrationals_are_normalized() {
+        assert_eq!(QuadraticIrrational::new(1i32, 9, 2), None);
+        assert_eq!(QuadraticIrrational::new(1i32, -2, 2), None);
+        assert_eq!(QuadraticIrrational::new(1i32, 2, 0), None);
+        // (1 + √2) / 3 = (3 + √18) / 9
+        let x = QuadraticIrrational::new(1i32, 2, 3).unwrap();
+        assert_eq!(x.parts(), (&3, &18, &9));
+        let (pre_period, period) = x.periodic_continued_fraction().unwrap();
+        assert_eq!((pre_period, period), (vec![0, 1], vec![4, 8]));
+        // (-1 + √3) / -2 = (1 - √3) / 2 = [-1; 1, 1, 1, 2, 1, 2, ...], with the negative q
+        let x = QuadraticIrrational::new(-1i32, 3, -2).unwrap();
+        assert!(x.terms().take(7).eq([-1, 1, 1, 1, 2, 1, 2]));
+        let (pre_period, period) = QuadraticIrrational::new(-5i32, 7, -3)
+            .unwrap()
+            .periodic_continued_fraction()
+            .unwrap();
+        // (-5 + √7) / -3 = (5 - √7) / 3
+        assert_eq!((pre_period, period), (vec![0, 1, 3], vec![1, 1, 1, 4]));
+
+        use num_bigint::BigInt;
+
+        let d = BigInt::from(10).pow(40) + 1;
+        let (_, period) = QuadraticIrrational::sqrt(d)
+            .unwrap()
+            .periodic_continued_fraction()
+            .unwrap();
+        assert_eq!(period, [BigInt::from(2) * BigInt::from(10).pow(20)]);
+    }
+}
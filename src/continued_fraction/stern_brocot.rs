@@ -0,0 +1,260 @@
+//! The [Stern–Brocot tree](https://en.wikipedia.org/wiki/Stern%E2%80%93Brocot_tree) of the positive rationals and
+//! the [Farey sequences](https://en.wikipedia.org/wiki/Farey_sequence).
+//!
+//! The path from the root 1/1 to `[a0; a1, ..., an]` consists of the runs `R^a0 L^a1 R^a2 ...` with the last run
+//! shortened by one, and every node is the mediant of its parents, i.e. of the bounds of its subtree.
+//!
+//! ```rust
+//! use dd_maths_traits::continued_fraction::stern_brocot::{
+//!     farey_neighbors, from_stern_brocot_path, stern_brocot_path, SternBrocotDirection::*,
+//! };
+//! use dd_maths_traits::rational::Ratio;
+//!
+//! let x = Ratio::new(3u32, 8).unwrap();
+//! let path: Vec<_> = stern_brocot_path(&x).unwrap().collect();
+//! assert_eq!(path, [(Left, 2), (Right, 1), (Left, 1)]);
+//! assert_eq!(from_stern_brocot_path(path), Some(x));
+//!
+//! assert_eq!(farey_neighbors(&x, &8), Some((Ratio::new(1, 3), Ratio::new(2, 5))));
+//! ```
+
+use crate::{
+    continued_fraction::RationalTerms,
+    rational::{Ratio, RatioInt},
+};
+use core::iter::Peekable;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SternBrocotDirection {
+    /// Towards the smaller fractions.
+    Left,
+    /// Towards the greater fractions.
+    Right,
+}
+
+impl SternBrocotDirection {
+    #[inline]
+    const fn reversed(self) -> Self {
+        match self {
+            SternBrocotDirection::Left => SternBrocotDirection::Right,
+            SternBrocotDirection::Right => SternBrocotDirection::Left,
+        }
+    }
+}
+
+/// The iterator over the runs of the path from the root of the Stern–Brocot tree, i.e. the directions
+/// with the positive numbers of the steps in them. The runs alternate in direction.
+#[derive(Clone, Debug)]
+pub struct SternBrocotPath<T: RatioInt> {
+    terms: Peekable<RationalTerms<T>>,
+    direction: SternBrocotDirection,
+}
+
+impl<T: RatioInt> Iterator for SternBrocotPath<T> {
+    type Item = (SternBrocotDirection, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut count = self.terms.next()?;
+            let direction = self.direction;
+            self.direction = direction.reversed();
+            if self.terms.peek().is_none() {
+                // the last term is positive
+                count = count - T::one();
+            }
+            if !count.is_zero() {
+                return Some((direction, count));
+            }
+        }
+    }
+}
+
+/// Returns the path from the root 1/1 to `x` or [None] if `x` is not positive.
+pub fn stern_brocot_path<T: RatioInt>(x: &Ratio<T>) -> Option<SternBrocotPath<T>> {
+    if *x.numer() <= T::zero() {
+        return None;
+    }
+    Some(SternBrocotPath {
+        terms: x.continued_fraction_terms().peekable(),
+        direction: SternBrocotDirection::Right,
+    })
+}
+
+/// Returns the node at the end of the path from the root 1/1 given by the runs, which can be empty
+/// and need not alternate, or [None] if the node is not representable.
+pub fn from_stern_brocot_path<T, I>(path: I) -> Option<Ratio<T>>
+where
+    T: RatioInt,
+    I: IntoIterator<Item = (SternBrocotDirection, T)>,
+{
+    let (left, right) = bounds(path)?;
+    let (numer, denom) = mediant(&left, &right)?;
+    // the bounds a/b < c/d satisfy bc - ad = 1, so the mediant is in lowest terms
+    Some(Ratio::from_canonical_parts(numer, denom))
+}
+
+/// Returns the parents of the positive `x` in the Stern–Brocot tree, i.e. the closest fractions with smaller
+/// denominators on both sides, whose mediant `x` is, or [None] if `x` is not positive. The right parent is [None]
+/// for the integers, since it is `1/0` then.
+pub fn stern_brocot_parents<T: RatioInt>(x: &Ratio<T>) -> Option<(Ratio<T>, Option<Ratio<T>>)> {
+    // the bounds do not exceed x in the numerators or the denominators
+    let ((a, b), (c, d)) = bounds(stern_brocot_path(x)?).unwrap();
+    let right = (!d.is_zero()).then(|| Ratio::from_canonical_parts(c, d));
+    Some((Ratio::from_canonical_parts(a, b), right))
+}
+
+// The bounds a/b and c/d of the subtree after the path, starting from 0/1 and 1/0
+fn bounds<T, I>(path: I) -> Option<((T, T), (T, T))>
+where
+    T: RatioInt,
+    I: IntoIterator<Item = (SternBrocotDirection, T)>,
+{
+    let (mut left, mut right) = ((T::zero(), T::one()), (T::one(), T::zero()));
+    for (direction, count) in path {
+        let (bound, other) = match direction {
+            SternBrocotDirection::Left => (&mut right, &left),
+            SternBrocotDirection::Right => (&mut left, &right),
+        };
+        bound.0 = count.checked_mul(&other.0)?.checked_add(&bound.0)?;
+        bound.1 = count.checked_mul(&other.1)?.checked_add(&bound.1)?;
+    }
+    Some((left, right))
+}
+
+fn mediant<T: RatioInt>((a, b): &(T, T), (c, d): &(T, T)) -> Option<(T, T)> {
+    Some((a.checked_add(c)?, b.checked_add(d)?))
+}
+
+/// The neighbors of a fraction in a Farey sequence, the previous one and the next one.
+pub type FareyNeighbors<T> = (Option<Ratio<T>>, Option<Ratio<T>>);
+
+/// Returns the neighbors of `x` in the Farey sequence of the order, i.e. in the increasing sequence of
+/// the fractions in `[0, 1]` with the denominators at most `order`, or [None] if `x` is not in it.
+/// The neighbors are [None] at the ends of the sequence.
+pub fn farey_neighbors<T: RatioInt>(x: &Ratio<T>, order: &T) -> Option<FareyNeighbors<T>> {
+    let (n, d) = (x.numer(), x.denom());
+    if *n < T::zero() || n > d || d > order {
+        return None;
+    }
+    if n.is_zero() {
+        return Some((
+            None,
+            Some(Ratio::from_canonical_parts(T::one(), order.clone())),
+        ));
+    }
+    // the neighbor on each side is the farthest of the fractions (p + kn) / (q + kd) that goes from
+    // the parent p/q towards x and fits the order, and it does not exceed the order
+    let neighbor = |parent: Ratio<T>| {
+        let (p, q) = parent.into_parts();
+        let k = (order.clone() - q.clone()) / d.clone();
+        Ratio::from_canonical_parts(p + k.clone() * n.clone(), q + k * d.clone())
+    };
+    let (left, right) = stern_brocot_parents(x).unwrap();
+    Some((Some(neighbor(left)), right.map(neighbor)))
+}
+
+/// The iterator over the Farey sequence of the order, from 0/1 to 1/1. It stops early only if
+/// the order is so close to the maximum of `T` that the next term is not computable.
+#[derive(Clone, Debug)]
+pub struct FareySequence<T> {
+    order: T,
+    // the next two terms a/b and c/d
+    current: Option<(T, T)>,
+    next: Option<(T, T)>,
+}
+
+impl<T: RatioInt> FareySequence<T> {
+    /// Returns [None] if the order is less than 1.
+    pub fn new(order: T) -> Option<Self> {
+        if order < T::one() {
+            return None;
+        }
+        Some(Self {
+            next: Some((T::one(), order.clone())),
+            order,
+            current: Some((T::zero(), T::one())),
+        })
+    }
+}
+
+impl<T: RatioInt> Iterator for FareySequence<T> {
+    type Item = Ratio<T>;
+
+    fn next(&mut self) -> Option<Ratio<T>> {
+        let (a, b) = self.current.take()?;
+        let following = match &self.next {
+            Some((c, d)) if c != d => {
+                // the term after a/b, c/d is (kc - a) / (kd - b) with k = ⌊(n + b) / d⌋
+                self.order.checked_add(&b).and_then(|m| {
+                    let k = m / d.clone();
+                    Some((
+                        k.checked_mul(c)?.checked_sub(&a)?,
+                        k.checked_mul(d)?.checked_sub(&b)?,
+                    ))
+                })
+            }
+            _ => None,
+        };
+        self.current = core::mem::replace(&mut self.next, following);
+        Some(Ratio::from_canonical_parts(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::continued_fraction::stern_brocot::*;
+
+    #[test]
+    fn stern_brocot_paths_round_trip() {
+        for n in 1u32..=60 {
+            for d in 1..=60 {
+                let x = Ratio::new(n, d).unwrap();
+                let path: Vec<_> = stern_brocot_path(&x).unwrap().collect();
+                assert!(path.windows(2).all(|w| w[0].0 != w[1].0));
+                let depth: u32 = path.iter().map(|(_, count)| count).sum();
+                let terms: u32 = x.continued_fraction_terms().sum();
+                assert_eq!(depth, terms - 1);
+                assert_eq!(from_stern_brocot_path(path), Some(x));
+
+                let (left, right) = stern_brocot_parents(&x).unwrap();
+                assert!(left < x && right.map_or(x.is_integer(), |right| x < right));
+            }
+        }
+        assert!(stern_brocot_path(&Ratio::from_integer(0i8)).is_none());
+        assert_eq!(stern_brocot_path(&Ratio::<u8>::one()).unwrap().count(), 0);
+        use SternBrocotDirection::{Left, Right};
+        assert_eq!(
+            from_stern_brocot_path([(Left, 1), (Left, 1)]),
+            Ratio::new(1u8, 3)
+        );
+        assert_eq!(from_stern_brocot_path([(Right, 255u8)]), None);
+    }
+
+    #[test]
+    fn farey_sequences_are_increasing_neighbors() {
+        for order in 1u32..=30 {
+            let sequence: Vec<Ratio<u32>> = FareySequence::new(order).unwrap().collect();
+            // the sequence is strictly increasing and contains every fraction of the order once
+            assert!(sequence.windows(2).all(|w| w[0] < w[1]));
+            assert!(sequence.iter().all(|x| x.denom() <= &order));
+            let count = (1..=order)
+                .map(|d| {
+                    (0..=d)
+                        .filter(|&n| Ratio::new(n, d).unwrap().denom() == &d)
+                        .count()
+                })
+                .sum::<usize>();
+            assert_eq!(sequence.len(), count);
+            for (i, x) in sequence.iter().enumerate() {
+                let neighbors = farey_neighbors(x, &order).unwrap();
+                assert_eq!(neighbors.0.as_ref(), i.checked_sub(1).map(|i| &sequence[i]));
+                assert_eq!(neighbors.1.as_ref(), sequence.get(i + 1));
+            }
+        }
+        // the terms after 1/255, 1/254 are not computable in u8
+        let sequence: Vec<Ratio<u8>> = FareySequence::new(255).unwrap().collect();
+        assert_eq!(sequence, [Ratio::zero(), Ratio::new(1, 255).unwrap()]);
+        assert_eq!(FareySequence::new(0i32).map(|s| s.count()), None);
+        assert_eq!(farey_neighbors(&Ratio::new(1u32, 3).unwrap(), &2), None);
+    }
+}
@@ -92,3 +92,11 @@ pub trait ArbitrarySizeIntSubset {}
 #[cfg_attr(docsrs, doc(cfg(feature = "negative_impls")))]
 #[cfg(any(doc, test, doctest, feature = "negative_impls"))]
 impl<T: ArbitrarySizeIntSubset> !FixedSizeIntSubset for T {}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint};
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+compat::impl_arbitrary_size_int_subset!(BigUint);
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+compat::impl_arbitrary_size_int_subset!(BigInt);
@@ -15,6 +15,12 @@
 #![cfg_attr(any(doc, test, doctest, feature = "decl_macro"), feature(decl_macro))]
 #![cfg_attr(any(doc, test, doctest, feature = "const_ops"), feature(const_ops))]
 
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+extern crate alloc;
+
+pub mod continued_fraction;
 pub mod int;
 pub mod num_sys;
+pub mod number_theory;
 pub mod ranges;
+pub mod rational;
@@ -0,0 +1,159 @@
+//! [Balanced ternary](https://en.wikipedia.org/wiki/Balanced_ternary), the positional numeral system
+//! base 3 with the digits -1, 0 and 1, written as `T`, `0` and `1`.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::balanced_ternary::BalancedTernary;
+//!
+//! // 5 = 9 - 3 - 1
+//! assert_eq!(5u8.to_balanced_ternary_string(), "1TT");
+//! assert_eq!(i8::from_balanced_ternary_str("T11"), Some(-5));
+//! ```
+
+use crate::num_sys::sign_magnitude::{Magnitude, SignMagnitude, SignedAcc};
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::{string::String, vec::Vec};
+
+/// The iterator over the balanced ternary digits from the least significant one.
+/// Zero has the single digit 0.
+#[derive(Clone, Debug)]
+pub struct BalancedTernaryDigitsLe<M> {
+    is_negative: bool,
+    magnitude: M,
+    is_done: bool,
+}
+
+impl<M: Magnitude> Iterator for BalancedTernaryDigitsLe<M> {
+    type Item = i8;
+
+    fn next(&mut self) -> Option<i8> {
+        if self.is_done {
+            return None;
+        }
+        // the digits of the absolute value, negated for negative values
+        let digit = match self.magnitude.div_rem_assign_u32(3) {
+            2 => {
+                self.magnitude = self.magnitude.checked_mul_add_u32(1, 1)?;
+                -1
+            }
+            rem => rem as i8,
+        };
+        self.is_done = self.magnitude.is_zero();
+        Some(if self.is_negative { -digit } else { digit })
+    }
+}
+
+pub trait BalancedTernary: Sized {
+    type Magnitude: Magnitude;
+
+    fn balanced_ternary_digits_le(&self) -> BalancedTernaryDigitsLe<Self::Magnitude>;
+
+    /// Returns [None] if a digit is not in `-1..=1`, `digits` is empty
+    /// or the value is not representable by `Self`.
+    fn from_balanced_ternary_digits_le(digits: &[i8]) -> Option<Self>;
+
+    /// Parses the digits written from the most significant one with `T`, `0` and `1`.
+    fn from_balanced_ternary_str(s: &str) -> Option<Self>;
+
+    /// Writes the digits from the most significant one with `T`, `0` and `1`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_balanced_ternary_string(&self) -> String {
+        let digits: Vec<i8> = self.balanced_ternary_digits_le().collect();
+        digits
+            .into_iter()
+            .rev()
+            .map(|d| match d {
+                -1 => 'T',
+                0 => '0',
+                _ => '1',
+            })
+            .collect()
+    }
+}
+
+impl<T: SignMagnitude> BalancedTernary for T {
+    type Magnitude = T::Magnitude;
+
+    fn balanced_ternary_digits_le(&self) -> BalancedTernaryDigitsLe<T::Magnitude> {
+        let (is_negative, magnitude) = self.to_sign_magnitude();
+        BalancedTernaryDigitsLe {
+            is_negative,
+            magnitude,
+            is_done: false,
+        }
+    }
+
+    fn from_balanced_ternary_digits_le(digits: &[i8]) -> Option<Self> {
+        from_balanced_ternary_digits_be(digits.iter().rev().copied())
+    }
+
+    fn from_balanced_ternary_str(s: &str) -> Option<Self> {
+        let digits = s.chars().map(|c| match c {
+            'T' => -1,
+            '0' => 0,
+            '1' => 1,
+            _ => i8::MAX,
+        });
+        from_balanced_ternary_digits_be(digits)
+    }
+}
+
+fn from_balanced_ternary_digits_be<T: SignMagnitude, I: Iterator<Item = i8>>(
+    digits: I,
+) -> Option<T> {
+    let mut acc = SignedAcc::zero();
+    let mut is_empty = true;
+    for d in digits {
+        if !(-1..=1).contains(&d) {
+            return None;
+        }
+        acc = acc.checked_mul_add((false, 3), (d < 0, d.unsigned_abs().into()))?;
+        is_empty = false;
+    }
+    if is_empty {
+        return None;
+    }
+    acc.into_int()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::balanced_ternary::BalancedTernary;
+
+    #[test]
+    fn balanced_ternary_representations_round_trip() {
+        for n in -2000i16..=2000 {
+            let digits: Vec<i8> = n.balanced_ternary_digits_le().collect();
+            assert_eq!(i16::from_balanced_ternary_digits_le(&digits), Some(n));
+            let s = n.to_balanced_ternary_string();
+            assert_eq!(i16::from_balanced_ternary_str(&s), Some(n));
+            // negation swaps T and 1
+            let negated: String = s
+                .chars()
+                .map(|c| match c {
+                    'T' => '1',
+                    '1' => 'T',
+                    c => c,
+                })
+                .collect();
+            assert_eq!(i16::from_balanced_ternary_str(&negated), Some(-n));
+        }
+        for n in [i128::MIN, i128::MAX] {
+            let s = n.to_balanced_ternary_string();
+            assert_eq!(i128::from_balanced_ternary_str(&s), Some(n));
+        }
+        assert_eq!(0u8.to_balanced_ternary_string(), "0");
+        assert_eq!(u8::from_balanced_ternary_str("T"), None);
+        assert_eq!(u8::from_balanced_ternary_str("1-"), None);
+        assert_eq!(i8::from_balanced_ternary_digits_le(&[2]), None);
+    }
+
+    #[test]
+    fn balanced_ternary_representations_round_trip_for_big_integers() {
+        use num_bigint::BigInt;
+
+        let n = -BigInt::from(7u32).pow(150) - 5u32;
+        let s = n.to_balanced_ternary_string();
+        assert_eq!(BigInt::from_balanced_ternary_str(&s), Some(n));
+    }
+}
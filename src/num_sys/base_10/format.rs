@@ -0,0 +1,318 @@
+//! Configurable formatting of integers base 10 with digit grouping, forced sign, zero padding
+//! and scientific or engineering notation.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::base_10::format::{FormatBase10, FormatOptions, Grouping, Notation};
+//!
+//! let indian = FormatOptions {
+//!     grouping: Grouping::Indian,
+//!     ..FormatOptions::default()
+//! };
+//! assert_eq!(123_456_789u32.to_formatted_string(&indian), "12,34,56,789");
+//! let engineering = FormatOptions {
+//!     notation: Notation::Engineering { significant_digits: 3 },
+//!     force_sign: true,
+//!     ..FormatOptions::default()
+//! };
+//! assert_eq!(123_456_789u32.to_formatted_string(&engineering), "+123e6");
+//! ```
+
+use crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::U8DigitsBase10BeInto;
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::string::String;
+use core::fmt::{self, Write};
+use max_len_base_10_as_usize::MaxLenBase10AsUsize;
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// The grouping of the digits of the positional notation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Grouping {
+    /// "1234567"
+    #[default]
+    None,
+    /// "1,234,567"
+    Thousands,
+    /// The [Indian numbering system](https://en.wikipedia.org/wiki/Indian_numbering_system)
+    /// with lakhs and crores, "12,34,567".
+    Indian,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// All digits, grouped with [Grouping] and padded with zeros to [FormatOptions::min_digits].
+    #[default]
+    Positional,
+    /// "1.23e4" with the given number of significant digits, rounded half away from zero.
+    Scientific { significant_digits: usize },
+    /// Same as [Scientific][Notation::Scientific] but the exponent is a multiple of 3, "12.3e3".
+    Engineering { significant_digits: usize },
+}
+
+/// The options of [FormatBase10]. [Grouping] and [min_digits][FormatOptions::min_digits]
+/// affect only the [positional notation][Notation::Positional].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    pub grouping: Grouping,
+    pub separator: char,
+    pub decimal_point: char,
+    /// Whether to write '+' for non-negative values.
+    pub force_sign: bool,
+    /// The minimal number of digits, reached by padding with leading zeros.
+    pub min_digits: usize,
+    pub notation: Notation,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            grouping: Grouping::None,
+            separator: ',',
+            decimal_point: '.',
+            force_sign: false,
+            min_digits: 1,
+            notation: Notation::Positional,
+        }
+    }
+}
+
+pub trait FormatBase10 {
+    fn write_formatted<W: Write>(&self, w: &mut W, options: &FormatOptions) -> fmt::Result;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_formatted_string(&self, options: &FormatOptions) -> String {
+        let mut s = String::new();
+        // writing into String never fails
+        let _ = self.write_formatted(&mut s, options);
+        s
+    }
+}
+
+// Whether a separator follows the digit with `remaining` digits after it
+fn is_group_end(grouping: Grouping, remaining: usize) -> bool {
+    match grouping {
+        Grouping::None => false,
+        Grouping::Thousands => remaining != 0 && remaining.is_multiple_of(3),
+        Grouping::Indian => remaining == 3 || (remaining > 3 && remaining % 2 == 1),
+    }
+}
+
+fn write_digit<W: Write>(w: &mut W, d: u8) -> fmt::Result {
+    w.write_char(char::from(b'0' + d))
+}
+
+fn write_positional<W: Write>(w: &mut W, digits_be: &[u8], options: &FormatOptions) -> fmt::Result {
+    let padding = options.min_digits.saturating_sub(digits_be.len());
+    let total = padding + digits_be.len();
+    let padded = core::iter::repeat_n(0, padding).chain(digits_be.iter().copied());
+    for (i, d) in padded.enumerate() {
+        write_digit(w, d)?;
+        if is_group_end(options.grouping, total - i - 1) {
+            w.write_char(options.separator)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_exponential<W: Write>(
+    w: &mut W,
+    digits_be: &[u8],
+    significant_digits: usize,
+    is_engineering: bool,
+    options: &FormatOptions,
+) -> fmt::Result {
+    let significant_digits = significant_digits.max(1);
+    let is_zero = digits_be == [0];
+    let mut exponent = digits_be.len() - 1;
+    // Rounding half away from zero turns the trailing nines of the kept digits into zeros
+    // and increments the digit before them, if any
+    let round_up = digits_be.get(significant_digits).is_some_and(|&d| d >= 5);
+    let incremented = if round_up {
+        digits_be[..significant_digits]
+            .iter()
+            .rposition(|&d| d != 9)
+    } else {
+        None
+    };
+    if round_up && incremented.is_none() {
+        exponent += 1;
+    }
+    let mantissa_digit = |i: usize| -> u8 {
+        match (round_up, incremented) {
+            (false, _) => digits_be.get(i).copied().unwrap_or(0),
+            (true, Some(j)) if i < j => digits_be[i],
+            (true, Some(j)) if i == j => digits_be[i] + 1,
+            (true, Some(_)) => 0,
+            (true, None) => u8::from(i == 0),
+        }
+    };
+    let int_len = if is_engineering && !is_zero {
+        exponent % 3 + 1
+    } else {
+        1
+    };
+    for i in 0..int_len {
+        // the engineering notation may need more integer digits than the significant ones
+        let d = if i < significant_digits {
+            mantissa_digit(i)
+        } else {
+            0
+        };
+        write_digit(w, d)?;
+    }
+    if significant_digits > int_len {
+        w.write_char(options.decimal_point)?;
+        for i in int_len..significant_digits {
+            write_digit(w, mantissa_digit(i))?;
+        }
+    }
+    write!(w, "e{}", if is_zero { 0 } else { exponent + 1 - int_len })
+}
+
+fn write_formatted<W: Write>(
+    w: &mut W,
+    is_negative: bool,
+    digits_be: &[u8],
+    options: &FormatOptions,
+) -> fmt::Result {
+    let leading_zeros = digits_be.iter().take_while(|&&d| d == 0).count();
+    let digits_be = match &digits_be[leading_zeros..] {
+        [] => &[0][..],
+        digits_be => digits_be,
+    };
+    if is_negative {
+        w.write_char('-')?;
+    } else if options.force_sign {
+        w.write_char('+')?;
+    }
+    match options.notation {
+        Notation::Positional => write_positional(w, digits_be, options),
+        Notation::Scientific { significant_digits } => {
+            write_exponential(w, digits_be, significant_digits, false, options)
+        }
+        Notation::Engineering { significant_digits } => {
+            write_exponential(w, digits_be, significant_digits, true, options)
+        }
+    }
+}
+
+macro_rules! impl_format_base_10 {
+    ($($t:ty),+) => {
+        $(
+            impl FormatBase10 for $t {
+                fn write_formatted<W: Write>(&self, w: &mut W, options: &FormatOptions) -> fmt::Result {
+                    let mut buf = [0u8; <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE];
+                    // the buffer fits all digits of the type
+                    let len = self.u8_digits_be_into(&mut buf).unwrap();
+                    #[allow(unused_comparisons)]
+                    let is_negative = *self < 0;
+                    write_formatted(w, is_negative, &buf[..len], options)
+                }
+            }
+        )+
+    };
+}
+
+impl_format_base_10!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl FormatBase10 for BigUint {
+    fn write_formatted<W: Write>(&self, w: &mut W, options: &FormatOptions) -> fmt::Result {
+        write_formatted(w, false, &self.to_radix_be(10), options)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl FormatBase10 for BigInt {
+    fn write_formatted<W: Write>(&self, w: &mut W, options: &FormatOptions) -> fmt::Result {
+        let is_negative = self.sign() == Sign::Minus;
+        write_formatted(w, is_negative, &self.magnitude().to_radix_be(10), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::base_10::format::*;
+
+    #[test]
+    fn positional_formatting_groups_and_pads_digits() {
+        let thousands = FormatOptions {
+            grouping: Grouping::Thousands,
+            ..FormatOptions::default()
+        };
+        assert_eq!(0u8.to_formatted_string(&thousands), "0");
+        assert_eq!(999u16.to_formatted_string(&thousands), "999");
+        assert_eq!(
+            (-1_234_567i32).to_formatted_string(&thousands),
+            "-1,234,567"
+        );
+        let indian = FormatOptions {
+            grouping: Grouping::Indian,
+            separator: '_',
+            ..FormatOptions::default()
+        };
+        assert_eq!(1234u16.to_formatted_string(&indian), "1_234");
+        assert_eq!(12_345u16.to_formatted_string(&indian), "12_345");
+        assert_eq!(1_234_567u32.to_formatted_string(&indian), "12_34_567");
+        let padded = FormatOptions {
+            min_digits: 7,
+            force_sign: true,
+            ..thousands
+        };
+        assert_eq!(1234u16.to_formatted_string(&padded), "+0,001,234");
+        assert_eq!(0i8.to_formatted_string(&padded), "+0,000,000");
+    }
+
+    #[test]
+    fn exponential_formatting_rounds_to_significant_digits() {
+        let scientific = |significant_digits| FormatOptions {
+            notation: Notation::Scientific { significant_digits },
+            ..FormatOptions::default()
+        };
+        let engineering = |significant_digits| FormatOptions {
+            notation: Notation::Engineering { significant_digits },
+            ..FormatOptions::default()
+        };
+        assert_eq!(12_345u32.to_formatted_string(&scientific(3)), "1.23e4");
+        assert_eq!(12_355u32.to_formatted_string(&scientific(3)), "1.24e4");
+        assert_eq!((-99_960i32).to_formatted_string(&scientific(3)), "-1.00e5");
+        assert_eq!(7u8.to_formatted_string(&scientific(3)), "7.00e0");
+        assert_eq!(0u8.to_formatted_string(&scientific(2)), "0.0e0");
+        assert_eq!(12_345u32.to_formatted_string(&engineering(3)), "12.3e3");
+        assert_eq!(12_345u32.to_formatted_string(&engineering(1)), "10e3");
+        assert_eq!(999_999u32.to_formatted_string(&engineering(2)), "1.0e6");
+        assert_eq!(1_000u32.to_formatted_string(&engineering(4)), "1.000e3");
+        let french = FormatOptions {
+            decimal_point: ',',
+            ..scientific(2)
+        };
+        assert_eq!(150u8.to_formatted_string(&french), "1,5e2");
+    }
+
+    #[test]
+    fn formatting_works_for_big_integers() {
+        use num_bigint::{BigInt, BigUint};
+
+        let n = BigUint::from(10u32).pow(30) - 1u32;
+        let thousands = FormatOptions {
+            grouping: Grouping::Thousands,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            n.to_formatted_string(&thousands),
+            "999,999,999,999,999,999,999,999,999,999"
+        );
+        let scientific = FormatOptions {
+            notation: Notation::Scientific {
+                significant_digits: 2,
+            },
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            (-BigInt::from(n)).to_formatted_string(&scientific),
+            "-1.0e30"
+        );
+    }
+}
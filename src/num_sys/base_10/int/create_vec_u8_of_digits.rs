@@ -0,0 +1,73 @@
+use crate::num_sys::{
+    base_10::int::new_vec_u8_of_digits_base_10::NewVecU8OfDigitsBase10Le, CreateVecU8OfDigits,
+};
+use alloc::vec::Vec;
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint};
+
+// The canonical "digits" of integers are the digits base 10 of the absolute value
+// in little endian order, i.e. exactly the output of NewVecU8OfDigitsBase10Le.
+macro_rules! impl_create_vec_u8_of_digits {
+    () => {
+        #[inline(always)]
+        fn create_vec_u8_of_digits(&self) -> Vec<u8> {
+            self.new_vec_u8_of_digits_le()
+        }
+    };
+}
+
+macro_rules! impl_trait {
+    ($trait:path, @PRIM_INTS, $fn_macro_name:ident) => {
+        impl_trait!($trait, [u8,u16,u32,u64,u128,usize,i8,i16,i32,i64,i128,isize], $fn_macro_name);
+    };
+    ($trait:path, [$($t:ty),+], $fn_macro_name:ident) => {
+        $(
+            impl $trait for $t {
+                $fn_macro_name!();
+            }
+        )+
+    };
+}
+
+impl_trait!(CreateVecU8OfDigits, @PRIM_INTS, impl_create_vec_u8_of_digits);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl_trait!(
+    CreateVecU8OfDigits,
+    [BigUint, BigInt],
+    impl_create_vec_u8_of_digits
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::{
+        base_10::int::new_vec_u8_of_digits_base_10::NewVecU8OfDigitsBase10Le, CreateVecU8OfDigits,
+    };
+
+    #[test]
+    fn create_vec_u8_of_digits_is_little_endian() {
+        assert_eq!(1230u16.create_vec_u8_of_digits(), [0, 3, 2, 1]);
+        assert_eq!(0u8.create_vec_u8_of_digits(), [0]);
+    }
+
+    #[test]
+    fn create_vec_u8_of_digits_matches_new_vec_u8_of_digits_le_for_negative_primitive_signed_integers(
+    ) {
+        assert_eq!(
+            i64::MIN.create_vec_u8_of_digits(),
+            i64::MIN.new_vec_u8_of_digits_le()
+        );
+    }
+
+    #[test]
+    fn create_vec_u8_of_digits_matches_new_vec_u8_of_digits_le_for_big_int() {
+        use num_bigint::BigInt;
+
+        let big_int = BigInt::from(-170_141_183_460_469_231_731_687_303_715_884_105_722i128);
+        assert_eq!(
+            big_int.create_vec_u8_of_digits(),
+            big_int.new_vec_u8_of_digits_le()
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! High-throughput extraction of digits base 10 from primitive integers.
+//!
+//! Instead of dividing by 10 once per digit, the digits are extracted four at a time
+//! (one division by 10000 followed by a cheap split into two pairs) and each pair of digits
+//! is looked up in a table of 100 entries.
+//!
+//! For batch conversion of `&[u32]` and `&[u64]` slices into fixed-width (zero-padded) digits,
+//! [padded_digits_le_u32s] and [padded_digits_le_u64s] split the values into chunks of four digits
+//! and, on `x86_64`, extract the digits of eight chunks at once with SSE2 instructions.
+
+use core::mem::MaybeUninit;
+
+// DIGIT_PAIRS_LE[n] = [n % 10, n / 10]
+const DIGIT_PAIRS_LE: [[u8; 2]; 100] = {
+    let mut table = [[0u8; 2]; 100];
+    let mut n = 0;
+    while n < 100 {
+        table[n] = [(n % 10) as u8, (n / 10) as u8];
+        n += 1;
+    }
+    table
+};
+
+#[inline(always)]
+fn write_2_digits_le(pair: u8, out: &mut [MaybeUninit<u8>], len: usize) {
+    let [lo, hi] = DIGIT_PAIRS_LE[pair as usize];
+    out[len].write(lo);
+    out[len + 1].write(hi);
+}
+
+#[inline(always)]
+fn write_4_digits_le(chunk: u16, out: &mut [MaybeUninit<u8>], len: usize) {
+    write_2_digits_le((chunk % 100) as u8, out, len);
+    write_2_digits_le((chunk / 100) as u8, out, len + 2);
+}
+
+// Writes the digits of n < 10000 without leading zeros and returns their count
+#[inline(always)]
+fn write_up_to_4_digits_le(n: u16, out: &mut [MaybeUninit<u8>], mut len: usize) -> usize {
+    let mut n = n;
+    if n >= 100 {
+        write_2_digits_le((n % 100) as u8, out, len);
+        n /= 100;
+        len += 2;
+    }
+    if n >= 10 {
+        write_2_digits_le(n as u8, out, len);
+        len + 2
+    } else {
+        out[len].write(n as u8);
+        len + 1
+    }
+}
+
+/// Views the initialized bytes as possibly uninitialized ones.
+///
+/// # Safety
+///
+/// The caller must not write [MaybeUninit::uninit] or any other uninitialized value through the returned slice,
+/// as the bytes are read as initialized afterwards.
+#[inline(always)]
+pub(crate) unsafe fn as_uninit_mut(buf: &mut [u8]) -> &mut [MaybeUninit<u8>] {
+    // SAFETY: MaybeUninit<u8> has the same layout as u8 and the caller writes only initialized values
+    unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) }
+}
+
+/// Writing digits base 10 of the absolute value in little endian order into
+/// possibly uninitialized memory.
+pub(crate) trait WriteDigitsLe: Copy {
+    /// Initializes the first `len` elements of `out` with the digits and returns `len`.
+    ///
+    /// Panics if `out` is shorter than the count of digits.
+    fn write_digits_le(self, out: &mut [MaybeUninit<u8>]) -> usize;
+}
+
+macro_rules! impl_write_digits_le_for_unsigned {
+    ($($t:ty),+) => {
+        $(
+            impl WriteDigitsLe for $t {
+                fn write_digits_le(self, out: &mut [MaybeUninit<u8>]) -> usize {
+                    let mut n = self;
+                    let mut len = 0;
+                    while n >= 10_000 {
+                        write_4_digits_le((n % 10_000) as u16, out, len);
+                        n /= 10_000;
+                        len += 4;
+                    }
+                    write_up_to_4_digits_le(n as u16, out, len)
+                }
+            }
+        )+
+    };
+}
+
+impl_write_digits_le_for_unsigned!(u16, u32, u64, usize);
+
+impl WriteDigitsLe for u8 {
+    #[inline]
+    fn write_digits_le(self, out: &mut [MaybeUninit<u8>]) -> usize {
+        write_up_to_4_digits_le(u16::from(self), out, 0)
+    }
+}
+
+impl WriteDigitsLe for u128 {
+    fn write_digits_le(self, out: &mut [MaybeUninit<u8>]) -> usize {
+        // Divisions of u128 are much slower than the ones of u64,
+        // so the value is split into chunks of 16 digits first.
+        const TEN_POW_16: u128 = 10_000_000_000_000_000;
+        let mut n = self;
+        let mut len = 0;
+        while n > u128::from(u64::MAX) {
+            let mut chunk = (n % TEN_POW_16) as u64;
+            n /= TEN_POW_16;
+            for _ in 0..4 {
+                write_4_digits_le((chunk % 10_000) as u16, out, len);
+                chunk /= 10_000;
+                len += 4;
+            }
+        }
+        len + (n as u64).write_digits_le(&mut out[len..])
+    }
+}
+
+macro_rules! impl_write_digits_le_for_signed {
+    ($($t:ty),+) => {
+        $(
+            impl WriteDigitsLe for $t {
+                #[inline(always)]
+                fn write_digits_le(self, out: &mut [MaybeUninit<u8>]) -> usize {
+                    self.unsigned_abs().write_digits_le(out)
+                }
+            }
+        )+
+    };
+}
+
+impl_write_digits_le_for_signed!(i8, i16, i32, i64, i128, isize);
+
+// Writes the 4 digits base 10 of each chunk < 10000, including leading zeros, in little endian order.
+fn padded_digits_le_u16x8(chunks: &[u16; 8], out: &mut [[u8; 4]; 8]) {
+    #[cfg(target_arch = "x86_64")]
+    x86_64::padded_digits_le_u16x8_sse2(chunks, out);
+
+    #[cfg(not(target_arch = "x86_64"))]
+    for (&chunk, digits) in chunks.iter().zip(out.iter_mut()) {
+        let [d0, d1] = DIGIT_PAIRS_LE[(chunk % 100) as usize];
+        let [d2, d3] = DIGIT_PAIRS_LE[(chunk / 100) as usize];
+        *digits = [d0, d1, d2, d3];
+    }
+}
+
+// Converts the values in blocks of 8, splitting each value into `CHUNKS` chunks of 4 digits
+// and writing the first `N` digits.
+fn padded_digits_le<T: Copy + Default, const CHUNKS: usize, const N: usize>(
+    values: &[T],
+    out: &mut [[u8; N]],
+    split: impl Fn(T) -> [u16; CHUNKS],
+) {
+    assert!(out.len() >= values.len(), "`out` is shorter than `values`");
+    let mut chunks = [[0u16; 8]; CHUNKS];
+    let mut chunks_digits = [[[0u8; 4]; 8]; CHUNKS];
+    for (values, out) in values.chunks(8).zip(out.chunks_mut(8)) {
+        for (j, &v) in values.iter().enumerate() {
+            for (k, chunk) in split(v).into_iter().enumerate() {
+                chunks[k][j] = chunk;
+            }
+        }
+        for (chunks, chunks_digits) in chunks.iter().zip(chunks_digits.iter_mut()) {
+            padded_digits_le_u16x8(chunks, chunks_digits);
+        }
+        for (j, digits) in out.iter_mut().enumerate().take(values.len()) {
+            for (k, chunk_digits) in digits.chunks_mut(4).enumerate() {
+                chunk_digits.copy_from_slice(&chunks_digits[k][j][..chunk_digits.len()]);
+            }
+        }
+    }
+}
+
+/// Writes the 10 digits base 10 of each value, including leading zeros, in little endian order.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `values`.
+pub fn padded_digits_le_u32s(values: &[u32], out: &mut [[u8; 10]]) {
+    padded_digits_le(values, out, |v: u32| {
+        let (hi, lo) = (v / 100_000_000, v % 100_000_000);
+        [(lo % 10_000) as u16, (lo / 10_000) as u16, hi as u16]
+    });
+}
+
+/// Writes the 20 digits base 10 of each value, including leading zeros, in little endian order.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `values`.
+pub fn padded_digits_le_u64s(values: &[u64], out: &mut [[u8; 20]]) {
+    padded_digits_le(values, out, |v: u64| {
+        let (hi, lo) = (v / 100_000_000, (v % 100_000_000) as u32);
+        let (hi, mid) = ((hi / 100_000_000) as u16, (hi % 100_000_000) as u32);
+        [
+            (lo % 10_000) as u16,
+            (lo / 10_000) as u16,
+            (mid % 10_000) as u16,
+            (mid / 10_000) as u16,
+            hi,
+        ]
+    });
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::*;
+
+    // For any x < 10000, x / 10 == (x * 6554) >> 16.
+    const DIV_10_MAGIC: i16 = 6554;
+
+    pub(super) fn padded_digits_le_u16x8_sse2(chunks: &[u16; 8], out: &mut [[u8; 4]; 8]) {
+        // SAFETY: SSE2 is available on all x86_64 processors, the unaligned load reads exactly the 16 bytes
+        // of the chunks and the two unaligned stores write exactly the 32 bytes of the output
+        unsafe {
+            let magic = _mm_set1_epi16(DIV_10_MAGIC);
+            let ten = _mm_set1_epi16(10);
+            let x = _mm_loadu_si128(chunks.as_ptr() as *const __m128i);
+            let q1 = _mm_mulhi_epu16(x, magic);
+            let q2 = _mm_mulhi_epu16(q1, magic);
+            let d3 = _mm_mulhi_epu16(q2, magic);
+            let d0 = _mm_sub_epi16(x, _mm_mullo_epi16(q1, ten));
+            let d1 = _mm_sub_epi16(q1, _mm_mullo_epi16(q2, ten));
+            let d2 = _mm_sub_epi16(q2, _mm_mullo_epi16(d3, ten));
+            // each 16-bit lane holds two digits, least significant first
+            let d01 = _mm_or_si128(d0, _mm_slli_epi16(d1, 8));
+            let d23 = _mm_or_si128(d2, _mm_slli_epi16(d3, 8));
+            // each 32-bit lane holds the four digits of a chunk
+            let lo = _mm_unpacklo_epi16(d01, d23);
+            let hi = _mm_unpackhi_epi16(d01, d23);
+            let out = out.as_mut_ptr() as *mut __m128i;
+            _mm_storeu_si128(out, lo);
+            _mm_storeu_si128(out.add(1), hi);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::base_10::int::fast_digits::{
+        padded_digits_le_u32s, padded_digits_le_u64s, WriteDigitsLe,
+    };
+    use core::mem::MaybeUninit;
+
+    fn digits_le<T: WriteDigitsLe>(n: T) -> Vec<u8> {
+        let mut out = [MaybeUninit::<u8>::uninit(); 39];
+        let len = n.write_digits_le(&mut out);
+        out[..len]
+            .iter()
+            .map(|d| unsafe { d.assume_init() })
+            .collect()
+    }
+
+    fn naive_digits_le(n: u128) -> Vec<u8> {
+        let mut digits = Vec::new();
+        let mut q = n;
+        loop {
+            digits.push((q % 10) as u8);
+            q /= 10;
+            if q == 0 {
+                break digits;
+            }
+        }
+    }
+
+    #[test]
+    fn write_digits_le_matches_naive_digits_le() {
+        for n in [
+            0u128,
+            7,
+            10,
+            99,
+            100,
+            9_999,
+            10_000,
+            12_345,
+            1 << 64,
+            u128::MAX,
+        ] {
+            assert_eq!(digits_le(n), naive_digits_le(n));
+        }
+        for n in [0u8, 9, 10, 100, 255] {
+            assert_eq!(digits_le(n), naive_digits_le(n.into()));
+        }
+        assert_eq!(digits_le(u64::MAX), naive_digits_le(u64::MAX.into()));
+        assert_eq!(
+            digits_le(i64::MIN),
+            naive_digits_le(i64::MIN.unsigned_abs().into())
+        );
+    }
+
+    #[test]
+    fn padded_digits_le_u32s_matches_naive_digits_le() {
+        let values: Vec<u32> = (0..37u32)
+            .map(|i| i.wrapping_mul(2_654_435_761))
+            .chain([0, 9, u32::MAX])
+            .collect();
+        let mut out = vec![[0u8; 10]; values.len()];
+        padded_digits_le_u32s(&values, &mut out);
+        for (&v, digits) in values.iter().zip(&out) {
+            let naive = naive_digits_le(v.into());
+            assert_eq!(digits[..naive.len()], naive);
+            assert!(digits[naive.len()..].iter().all(|&d| d == 0));
+        }
+    }
+
+    #[test]
+    fn padded_digits_le_u64s_matches_naive_digits_le() {
+        let values: Vec<u64> = (0..21u64)
+            .map(|i| i.wrapping_mul(11_400_714_819_323_198_485))
+            .chain([0, 100_000_000, u64::MAX])
+            .collect();
+        let mut out = vec![[0u8; 20]; values.len()];
+        padded_digits_le_u64s(&values, &mut out);
+        for (&v, digits) in values.iter().zip(&out) {
+            let naive = naive_digits_le(v.into());
+            assert_eq!(digits[..naive.len()], naive);
+            assert!(digits[naive.len()..].iter().all(|&d| d == 0));
+        }
+    }
+}
@@ -115,4 +115,14 @@ mod tests {
         let big_int = BigInt::from(-170_141_183_460_469_231_731_687_303_715_884_105_722i128);
         assert_eq!(big_int.get_last_digit_as_u8(), 2);
     }
+
+    #[test]
+    fn get_last_digit_as_u8_works_for_multi_limb_big_uint() {
+        use num_bigint::BigUint;
+
+        let big_uint = BigUint::from(3u32).pow(1000) * 7u32;
+        let expected = (&big_uint % 10u32).iter_u32_digits().next().unwrap_or(0) as u8;
+        assert_eq!(big_uint.get_last_digit_as_u8(), expected);
+        assert_eq!(BigUint::from(0u32).get_last_digit_as_u8(), 0);
+    }
 }
@@ -73,32 +73,21 @@ impl_trait!(
     impl_get_last_digit_base_10_as_u8_for_prim_signed_int
 );
 
-// TODO: decompose the function and maybe share the results with the library authors
+// TODO: maybe share the results with the library authors
 // TODO: track https://github.com/rust-num/num-bigint/issues/233
 #[cfg(any(doc, test, doctest, feature = "num-bigint"))]
 impl crate::num_sys::base_10::GetLastDigitBase10AsU8 for num_bigint::BigUint {
     fn get_last_digit_base_10_as_u8(&self) -> u8 {
-        const DEFAULT_RADIX: u8 = 10;
-        // Unfortunately, the library doesn't offer a way to return the remainder
-        // of the divisor's type even for primitive integers.
-        let rem: Self = self % DEFAULT_RADIX;
-        let rem: u32 = {
-            let least_significant_digit: u32 = {
-                // TODO: contact the library authors to make endianness explicit. Read more about endianness:
-                // https://en.wikipedia.org/wiki/Endianness
-                let mut le_iter_u32 = rem.iter_u32_digits();
-                le_iter_u32
-                    .next()
-                    // integers always have at least one digit
-                    .unwrap()
-            };
-            least_significant_digit
-        };
-        let rem: u8 = rem
-            .try_into()
-            // that number is the remainder of divison by 10 and therefore < 10 < 256
-            .unwrap();
-        rem
+        // self = d_0 + d_1 * 2^64 + d_2 * 2^128 + ... where d_i are little endian u64 digits.
+        // Since 2^64 ≡ 6 (mod 10) and 6^k ≡ 6 (mod 10) for k >= 1,
+        // self ≡ d_0 + 6 * (d_1 + d_2 + ...) (mod 10), which requires no allocation
+        // unlike computing the remainder BigUint.
+        let mut le_iter_u64 = self.iter_u64_digits();
+        let least_significant_digit: u64 = le_iter_u64.next().unwrap_or(0);
+        let rest_sum_mod_10: u64 = le_iter_u64.fold(0, |acc, d| (acc + d % 10) % 10);
+        let rem: u64 = (least_significant_digit % 10 + 6 * rest_sum_mod_10) % 10;
+        // that number is the remainder of divison by 10 and therefore < 10 < 256
+        rem as u8
     }
 }
 
@@ -0,0 +1,85 @@
+use crate::num_sys::GetMaxLenBase10AsUsize;
+use max_len_base_10_as_usize::MaxLenBase10AsUsize;
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint, Sign};
+
+macro_rules! impl_get_max_len_base_10_as_usize {
+    () => {
+        #[inline(always)]
+        fn get_max_len_base_10_as_usize(&self) -> usize {
+            Self::MAX_LEN_BASE_10_AS_USIZE
+        }
+    };
+}
+
+macro_rules! impl_trait {
+    ($trait:path, @PRIM_INTS, $fn_macro_name:ident) => {
+        impl_trait!($trait, [u8,u16,u32,u64,u128,usize,i8,i16,i32,i64,i128,isize], $fn_macro_name);
+    };
+    ($trait:path, [$($t:ty),+], $fn_macro_name:ident) => {
+        $(
+            impl $trait for $t {
+                $fn_macro_name!();
+            }
+        )+
+    };
+}
+
+impl_trait!(GetMaxLenBase10AsUsize, @PRIM_INTS, impl_get_max_len_base_10_as_usize);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl GetMaxLenBase10AsUsize for BigUint {
+    fn get_max_len_base_10_as_usize(&self) -> usize {
+        // self < 2^bits, so the count of digits is at most floor(bits * log10(2)) + 1
+        // and 30103 / 100000 is slightly greater than log10(2) = 0.30102999566...
+        let bits: u64 = self.bits();
+        (bits * 30_103 / 100_000 + 1) as usize
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl GetMaxLenBase10AsUsize for BigInt {
+    fn get_max_len_base_10_as_usize(&self) -> usize {
+        let sign_len = if self.sign() == Sign::Minus { 1 } else { 0 };
+        self.magnitude().get_max_len_base_10_as_usize() + sign_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::{
+        base_10::int::new_vec_u8_of_digits_base_10::NewVecU8OfDigitsBase10Le,
+        GetMaxLenBase10AsUsize,
+    };
+
+    #[test]
+    fn get_max_len_base_10_as_usize_accounts_for_minus_sign() {
+        assert_eq!(0u8.get_max_len_base_10_as_usize(), 3);
+        assert_eq!(0i8.get_max_len_base_10_as_usize(), 4);
+        assert_eq!(
+            i128::MIN.get_max_len_base_10_as_usize(),
+            i128::MIN.new_vec_u8_of_digits_le().len() + 1
+        );
+    }
+
+    #[test]
+    fn get_max_len_base_10_as_usize_bounds_new_vec_u8_of_digits_le_for_big_uint() {
+        use num_bigint::BigUint;
+
+        for n in [0u128, 9, 10, 99, 1 << 64, u128::MAX] {
+            let big_uint = BigUint::from(n);
+            let len = big_uint.new_vec_u8_of_digits_le().len();
+            let max_len = big_uint.get_max_len_base_10_as_usize();
+            assert!(len <= max_len && max_len <= len + 1);
+        }
+    }
+
+    #[test]
+    fn get_max_len_base_10_as_usize_accounts_for_minus_sign_for_big_int() {
+        use num_bigint::BigInt;
+
+        assert_eq!(BigInt::from(-7).get_max_len_base_10_as_usize(), 2);
+        assert_eq!(BigInt::from(7).get_max_len_base_10_as_usize(), 1);
+    }
+}
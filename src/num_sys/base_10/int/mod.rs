@@ -1,6 +1,8 @@
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+pub mod create_vec_u8_of_digits;
+pub mod fast_digits;
 pub mod get_last_digit_as_u8;
 pub mod get_last_digit_base_10_as_u8;
-pub mod sum_digits;
-
-#[cfg(any(doc, test, doctest, feature = "std"))]
+pub mod get_max_len_base_10_as_usize;
 pub mod new_vec_u8_of_digits_base_10;
+pub mod sum_digits;
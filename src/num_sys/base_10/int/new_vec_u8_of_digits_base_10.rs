@@ -1,41 +1,110 @@
-#[cfg(any(doc, test, doctest, feature = "std"))]
-use {is_signed_trait::IsSigned, max_len_base_10_as_usize::MaxLenBase10AsUsize};
+use crate::num_sys::base_10::int::fast_digits::{as_uninit_mut, WriteDigitsLe};
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use {alloc::vec::Vec, is_signed_trait::IsSigned};
+use {core::mem::MaybeUninit, max_len_base_10_as_usize::MaxLenBase10AsUsize};
 
 #[cfg(any(doc, test, doctest, feature = "bigint"))]
 use num_bigint::{BigInt, BigUint};
 
-#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-#[cfg(any(doc, test, doctest, feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
 pub trait NewVecU8OfDigitsBase10Le {
     fn new_vec_u8_of_digits_le(&self) -> Vec<u8>;
 }
 
-#[cfg(any(doc, test, doctest, feature = "std"))]
+/// Allocation-free counterpart of [NewVecU8OfDigitsBase10Le] that writes the digits base 10
+/// in little endian order into caller-provided storage.
+pub trait U8DigitsBase10LeInto {
+    /// Writes the digits into the beginning of `buf` and returns their count or [None]
+    /// if `buf` is too short, in which case the contents of `buf` are unspecified.
+    fn u8_digits_le_into(&self, buf: &mut [u8]) -> Option<usize>;
+    fn extend_with_u8_digits_le<E: Extend<u8>>(&self, sink: &mut E);
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+pub trait NewVecU8OfDigitsBase10Be {
+    fn new_vec_u8_of_digits_be(&self) -> Vec<u8>;
+}
+
+/// Allocation-free counterpart of [NewVecU8OfDigitsBase10Be] that writes the digits base 10
+/// in big endian order into caller-provided storage.
+pub trait U8DigitsBase10BeInto {
+    /// Writes the digits into the beginning of `buf` and returns their count or [None]
+    /// if `buf` is too short, in which case the contents of `buf` are unspecified.
+    fn u8_digits_be_into(&self, buf: &mut [u8]) -> Option<usize>;
+    fn extend_with_u8_digits_be<E: Extend<u8>>(&self, sink: &mut E);
+}
+
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
 macro_rules! impl_new_vec_u8_of_digits_le {
     ($fn_name:ident) => {
-        // TODO: consider implementing via sparse_capacity_mut + set_len
         fn $fn_name(&self) -> Vec<u8> {
             let mut vec = Vec::<u8>::with_capacity(
                 Self::MAX_LEN_BASE_10_AS_USIZE - if Self::IS_SIGNED { 1 } else { 0 },
             );
-            let mut q = *self;
-            loop {
-                let (new_q, r) = (q / 10, q % 10);
-                q = new_q;
-                vec.push(if Self::IS_SIGNED {
-                    (r as i8).abs() as u8
-                } else {
-                    r as u8
-                });
-                if q == 0 {
-                    break;
-                };
+            let len = self.write_digits_le(vec.spare_capacity_mut());
+            // SAFETY: the first len elements have been initialized by write_digits_le
+            unsafe { vec.set_len(len) };
+            vec
+        }
+    };
+}
+
+macro_rules! impl_u8_digits_le_into {
+    ($t:ty) => {
+        fn u8_digits_le_into(&self, buf: &mut [u8]) -> Option<usize> {
+            const MAX_LEN: usize = <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE;
+            if buf.len() >= MAX_LEN {
+                // SAFETY: write_digits_le writes only the initialized digits
+                return Some(self.write_digits_le(unsafe { as_uninit_mut(buf) }));
             }
+            let mut digits = [MaybeUninit::<u8>::uninit(); MAX_LEN];
+            let len = self.write_digits_le(&mut digits);
+            // SAFETY: the first len elements have been initialized by write_digits_le
+            // and MaybeUninit<u8> has the same layout as u8
+            let digits = unsafe { &*(&digits[..len] as *const [MaybeUninit<u8>] as *const [u8]) };
+            buf.get_mut(..len)?.copy_from_slice(digits);
+            Some(len)
+        }
+
+        fn extend_with_u8_digits_le<E: Extend<u8>>(&self, sink: &mut E) {
+            let mut buf = [0u8; <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE];
+            // the buffer can hold the digits of any value of the type
+            let len = self.u8_digits_le_into(&mut buf).unwrap();
+            sink.extend(buf[..len].iter().copied());
+        }
+    };
+}
+
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+macro_rules! impl_new_vec_u8_of_digits_be {
+    ($fn_name:ident) => {
+        fn $fn_name(&self) -> Vec<u8> {
+            let mut vec = self.new_vec_u8_of_digits_le();
+            vec.reverse();
             vec
         }
     };
 }
 
+macro_rules! impl_u8_digits_be_into {
+    ($t:ty) => {
+        fn u8_digits_be_into(&self, buf: &mut [u8]) -> Option<usize> {
+            let len = self.u8_digits_le_into(buf)?;
+            buf[..len].reverse();
+            Some(len)
+        }
+
+        fn extend_with_u8_digits_be<E: Extend<u8>>(&self, sink: &mut E) {
+            let mut buf = [0u8; <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE];
+            // the buffer can hold the digits of any value of the type
+            let len = self.u8_digits_le_into(&mut buf).unwrap();
+            sink.extend(buf[..len].iter().rev().copied());
+        }
+    };
+}
+
 macro_rules! impl_trait {
     ($trait_name:ident::$fn_name:ident for @PRIM_INTS as $macro_name:ident) => {
         impl_trait!(
@@ -44,18 +113,35 @@ macro_rules! impl_trait {
     };
     ($trait_name:ident::$fn_name:ident for [$($t:ty),+] as $macro_name:ident) => {
         $(
-            #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-            #[cfg(any(doc, test, doctest, feature = "std"))]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            #[cfg(any(doc, test, doctest, feature = "alloc"))]
             impl $trait_name for $t {
                 $macro_name!($fn_name);
             }
         )+
-    }
+    };
+    ($trait_name:ident for @PRIM_INTS as $macro_name:ident) => {
+        impl_trait!(
+            $trait_name for [u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize] as $macro_name
+        );
+    };
+    ($trait_name:ident for [$($t:ty),+] as $macro_name:ident) => {
+        $(
+            impl $trait_name for $t {
+                $macro_name!($t);
+            }
+        )+
+    };
 }
 
 impl_trait!(NewVecU8OfDigitsBase10Le::new_vec_u8_of_digits_le for @PRIM_INTS as impl_new_vec_u8_of_digits_le);
+impl_trait!(U8DigitsBase10LeInto for @PRIM_INTS as impl_u8_digits_le_into);
+impl_trait!(NewVecU8OfDigitsBase10Be::new_vec_u8_of_digits_be for @PRIM_INTS as impl_new_vec_u8_of_digits_be);
+impl_trait!(U8DigitsBase10BeInto for @PRIM_INTS as impl_u8_digits_be_into);
 
-#[cfg(any(doc, test, doctest, all(feature = "bigint", feature = "std")))]
+// Since 0.4.7, num-bigint converts to radix via divide-and-conquer by precomputed powers of the radix
+// and divides large numbers via the Burnikel-Ziegler algorithm, which takes subquadratic time.
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
 impl NewVecU8OfDigitsBase10Le for BigUint {
     #[inline(always)]
     fn new_vec_u8_of_digits_le(&self) -> Vec<u8> {
@@ -63,7 +149,7 @@ impl NewVecU8OfDigitsBase10Le for BigUint {
     }
 }
 
-#[cfg(any(doc, test, doctest, all(feature = "bigint", feature = "std")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
 impl NewVecU8OfDigitsBase10Le for BigInt {
     #[inline(always)]
     fn new_vec_u8_of_digits_le(&self) -> Vec<u8> {
@@ -71,3 +157,150 @@ impl NewVecU8OfDigitsBase10Le for BigInt {
         vec_of_digits
     }
 }
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl U8DigitsBase10LeInto for BigUint {
+    fn u8_digits_le_into(&self, buf: &mut [u8]) -> Option<usize> {
+        let digits = self.to_radix_le(10);
+        buf.get_mut(..digits.len())?.copy_from_slice(&digits);
+        Some(digits.len())
+    }
+
+    #[inline(always)]
+    fn extend_with_u8_digits_le<E: Extend<u8>>(&self, sink: &mut E) {
+        sink.extend(self.to_radix_le(10));
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl U8DigitsBase10LeInto for BigInt {
+    #[inline(always)]
+    fn u8_digits_le_into(&self, buf: &mut [u8]) -> Option<usize> {
+        self.magnitude().u8_digits_le_into(buf)
+    }
+
+    #[inline(always)]
+    fn extend_with_u8_digits_le<E: Extend<u8>>(&self, sink: &mut E) {
+        self.magnitude().extend_with_u8_digits_le(sink)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl NewVecU8OfDigitsBase10Be for BigUint {
+    #[inline(always)]
+    fn new_vec_u8_of_digits_be(&self) -> Vec<u8> {
+        self.to_radix_be(10)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl NewVecU8OfDigitsBase10Be for BigInt {
+    #[inline(always)]
+    fn new_vec_u8_of_digits_be(&self) -> Vec<u8> {
+        let (_, vec_of_digits) = self.to_radix_be(10);
+        vec_of_digits
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl U8DigitsBase10BeInto for BigUint {
+    fn u8_digits_be_into(&self, buf: &mut [u8]) -> Option<usize> {
+        let digits = self.to_radix_be(10);
+        buf.get_mut(..digits.len())?.copy_from_slice(&digits);
+        Some(digits.len())
+    }
+
+    #[inline(always)]
+    fn extend_with_u8_digits_be<E: Extend<u8>>(&self, sink: &mut E) {
+        sink.extend(self.to_radix_be(10));
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl U8DigitsBase10BeInto for BigInt {
+    #[inline(always)]
+    fn u8_digits_be_into(&self, buf: &mut [u8]) -> Option<usize> {
+        self.magnitude().u8_digits_be_into(buf)
+    }
+
+    #[inline(always)]
+    fn extend_with_u8_digits_be<E: Extend<u8>>(&self, sink: &mut E) {
+        self.magnitude().extend_with_u8_digits_be(sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::{
+        NewVecU8OfDigitsBase10Be, NewVecU8OfDigitsBase10Le, U8DigitsBase10BeInto,
+        U8DigitsBase10LeInto,
+    };
+
+    #[test]
+    fn u8_digits_le_into_works_for_negative_primitive_signed_integers() {
+        let mut buf = [0u8; 3];
+        assert_eq!((-128i8).u8_digits_le_into(&mut buf), Some(3));
+        assert_eq!(buf, [8, 2, 1]);
+        assert_eq!((-1000i16).u8_digits_le_into(&mut buf), None);
+    }
+
+    #[test]
+    fn extend_with_u8_digits_le_matches_new_vec_u8_of_digits_le() {
+        let mut vec = vec![9];
+        u128::MAX.extend_with_u8_digits_le(&mut vec);
+        assert_eq!(vec[1..], u128::MAX.new_vec_u8_of_digits_le());
+    }
+
+    #[test]
+    fn u8_digits_le_into_works_for_big_int() {
+        use num_bigint::BigInt;
+
+        let big_int = BigInt::from(-170_141_183_460_469_231_731_687_303_715_884_105_722i128);
+        let mut buf = [0u8; 39];
+        assert_eq!(big_int.u8_digits_le_into(&mut buf), Some(39));
+        assert_eq!(buf[..], big_int.new_vec_u8_of_digits_le());
+        assert_eq!(big_int.u8_digits_le_into(&mut buf[..38]), None);
+    }
+
+    #[test]
+    fn new_vec_u8_of_digits_be_is_reversed_new_vec_u8_of_digits_le() {
+        let mut le = i32::MIN.new_vec_u8_of_digits_le();
+        le.reverse();
+        assert_eq!(i32::MIN.new_vec_u8_of_digits_be(), le);
+        assert_eq!(1230u16.new_vec_u8_of_digits_be(), [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn u8_digits_be_into_works_for_primitive_integers() {
+        let mut buf = [0u8; 4];
+        assert_eq!((-128i8).u8_digits_be_into(&mut buf), Some(3));
+        assert_eq!(buf[..3], [1, 2, 8]);
+
+        let mut vec = Vec::new();
+        1230u16.extend_with_u8_digits_be(&mut vec);
+        assert_eq!(vec, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn u8_digits_be_into_works_for_big_int() {
+        use num_bigint::BigInt;
+
+        let big_int = BigInt::from(-170_141_183_460_469_231_731_687_303_715_884_105_722i128);
+        let mut buf = [0u8; 39];
+        assert_eq!(big_int.u8_digits_be_into(&mut buf), Some(39));
+        assert_eq!(buf[..], big_int.new_vec_u8_of_digits_be());
+        assert_eq!(buf[38], 2);
+    }
+
+    #[test]
+    fn new_vec_u8_of_digits_le_works_for_big_uint_with_many_digits() {
+        use num_bigint::BigUint;
+
+        // 10^20000 + 1
+        let big_uint = BigUint::from(10u32).pow(20_000) + 1u32;
+        let digits = big_uint.new_vec_u8_of_digits_le();
+        assert_eq!(digits.len(), 20_001);
+        assert_eq!((digits[0], digits[20_000]), (1, 1));
+        assert!(digits[1..20_000].iter().all(|&d| d == 0));
+    }
+}
@@ -1,19 +1,21 @@
+pub mod format;
 pub mod int;
 
 mod ten;
 pub use ten::Ten;
 
-#[cfg(any(doc, test, doctest, feature = "std"))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Write};
 use num_traits::PrimInt;
-#[cfg(any(doc, test, doctest, feature = "std"))]
-use std::string::ToString;
 
-#[cfg(any(doc, test, doctest, feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
 pub trait ToCanonicalRepresentationBase10AsString {
     fn to_canonical_representation_base_10_as_string(&self) -> String;
 }
 
-#[cfg(any(doc, test, doctest, feature = "std"))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
 impl<T> ToCanonicalRepresentationBase10AsString for T
 where
     T: PrimInt + ToString,
@@ -24,6 +26,59 @@ where
     }
 }
 
+/// Allocation-free counterpart of [ToCanonicalRepresentationBase10AsString] that writes
+/// the ASCII bytes of the canonical representation into caller-provided storage.
+pub trait ToCanonicalRepresentationBase10Into {
+    /// Writes the representation into the beginning of `buf` and returns its length or [None]
+    /// if `buf` is too short, in which case the contents of `buf` are unspecified.
+    fn to_canonical_representation_base_10_into(&self, buf: &mut [u8]) -> Option<usize>;
+    fn extend_with_canonical_representation_base_10<E: Extend<u8>>(&self, sink: &mut E);
+}
+
+impl<T> ToCanonicalRepresentationBase10Into for T
+where
+    T: PrimInt + Display,
+{
+    fn to_canonical_representation_base_10_into(&self, buf: &mut [u8]) -> Option<usize> {
+        let mut writer = SliceWriter { buf, len: 0 };
+        write!(writer, "{}", self).ok()?;
+        Some(writer.len)
+    }
+
+    #[inline]
+    fn extend_with_canonical_representation_base_10<E: Extend<u8>>(&self, sink: &mut E) {
+        // writing into ExtendWriter never fails
+        let _ = write!(ExtendWriter(sink), "{}", self);
+    }
+}
+
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+struct ExtendWriter<'a, E: Extend<u8>>(&'a mut E);
+
+impl<E: Extend<u8>> Write for ExtendWriter<'_, E> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0.extend(s.bytes());
+        Ok(())
+    }
+}
+
 pub trait GetLastDigitBase10AsU8 {
     fn get_last_digit_base_10_as_u8(&self) -> u8;
 }
@@ -37,4 +92,24 @@ pub mod tests {
     ) {
         assert_eq!((-17).to_canonical_representation_base_10_as_string(), "-17");
     }
+
+    #[test]
+    pub fn to_canonical_representation_base_10_into_works_for_negative_primitive_signed_integers() {
+        use crate::num_sys::base_10::ToCanonicalRepresentationBase10Into;
+
+        let mut buf = [0u8; 4];
+        assert_eq!(
+            (-128i8).to_canonical_representation_base_10_into(&mut buf),
+            Some(4)
+        );
+        assert_eq!(&buf, b"-128");
+        assert_eq!(
+            (-1000i16).to_canonical_representation_base_10_into(&mut buf),
+            None
+        );
+
+        let mut vec = Vec::new();
+        (-17).extend_with_canonical_representation_base_10(&mut vec);
+        assert_eq!(vec, b"-17");
+    }
 }
@@ -0,0 +1,193 @@
+//! Digit functions base 10 over slices of primitive integers.
+//!
+//! [last_digits_into] and [digit_sums_into] are element-wise loops without early exits, which
+//! the compiler can vectorize. With the `rayon` feature, `par_*` counterparts split the work between threads.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::batch::{digit_histogram, last_digits_into};
+//!
+//! let values = [17i32, -250, 9];
+//! let mut last_digits = [0u8; 3];
+//! last_digits_into(&values, &mut last_digits);
+//! assert_eq!(last_digits, [7, 0, 9]);
+//! assert_eq!(digit_histogram(&values), [1, 1, 1, 0, 0, 1, 0, 1, 0, 1]);
+//! ```
+//!
+//! For signed integers, the digits are the digits of the absolute value.
+
+use crate::num_sys::consts::*;
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::vec::Vec;
+
+/// Primitive integers supported by the functions of [this module][crate::num_sys::batch].
+pub trait BatchDigitsBase10: Copy + Send + Sync {
+    fn last_digit(self) -> u8;
+    fn digit_sum(self) -> u32;
+    fn add_digits_to_histogram(self, histogram: &mut [u64; 10]);
+}
+
+macro_rules! impl_batch_digits_base_10 {
+    ($($t:ty => $last_digit:ident, $digit_sum:ident;)+) => {
+        $(
+            impl BatchDigitsBase10 for $t {
+                #[inline(always)]
+                fn last_digit(self) -> u8 {
+                    $last_digit(self)
+                }
+
+                #[inline(always)]
+                fn digit_sum(self) -> u32 {
+                    $digit_sum(self)
+                }
+
+                #[inline]
+                fn add_digits_to_histogram(self, histogram: &mut [u64; 10]) {
+                    // division truncates towards zero, so the remainders
+                    // are the digits of the absolute value up to the sign
+                    let mut q = self;
+                    loop {
+                        histogram[usize::from($last_digit(q))] += 1;
+                        q /= 10;
+                        if q == 0 {
+                            break;
+                        }
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_batch_digits_base_10!(
+    u8 => last_digit_u8, digit_sum_u8;
+    u16 => last_digit_u16, digit_sum_u16;
+    u32 => last_digit_u32, digit_sum_u32;
+    u64 => last_digit_u64, digit_sum_u64;
+    u128 => last_digit_u128, digit_sum_u128;
+    usize => last_digit_usize, digit_sum_usize;
+    i8 => last_digit_i8, digit_sum_i8;
+    i16 => last_digit_i16, digit_sum_i16;
+    i32 => last_digit_i32, digit_sum_i32;
+    i64 => last_digit_i64, digit_sum_i64;
+    i128 => last_digit_i128, digit_sum_i128;
+    isize => last_digit_isize, digit_sum_isize;
+);
+
+/// Writes the last digit of each value into the corresponding element of `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `values`.
+pub fn last_digits_into<T: BatchDigitsBase10>(values: &[T], out: &mut [u8]) {
+    assert!(out.len() >= values.len(), "`out` is shorter than `values`");
+    for (d, &v) in out.iter_mut().zip(values) {
+        *d = v.last_digit();
+    }
+}
+
+/// Writes the sum of digits of each value into the corresponding element of `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `values`.
+pub fn digit_sums_into<T: BatchDigitsBase10>(values: &[T], out: &mut [u32]) {
+    assert!(out.len() >= values.len(), "`out` is shorter than `values`");
+    for (s, &v) in out.iter_mut().zip(values) {
+        *s = v.digit_sum();
+    }
+}
+
+/// Returns the sums of digits of the values.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+pub fn digit_sums<T: BatchDigitsBase10>(values: &[T]) -> Vec<u32> {
+    values.iter().map(|&v| v.digit_sum()).collect()
+}
+
+/// Returns how many times each digit occurs in the values (without leading zeros).
+pub fn digit_histogram<T: BatchDigitsBase10>(values: &[T]) -> [u64; 10] {
+    let mut histogram = [0u64; 10];
+    for &v in values {
+        v.add_digits_to_histogram(&mut histogram);
+    }
+    histogram
+}
+
+#[cfg(any(doc, test, doctest, feature = "rayon"))]
+const PAR_CHUNK_LEN: usize = 1 << 14;
+
+/// Parallel counterpart of [last_digits_into].
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+#[cfg(any(doc, test, doctest, feature = "rayon"))]
+pub fn par_last_digits_into<T: BatchDigitsBase10>(values: &[T], out: &mut [u8]) {
+    use rayon::prelude::*;
+
+    assert!(out.len() >= values.len(), "`out` is shorter than `values`");
+    values
+        .par_chunks(PAR_CHUNK_LEN)
+        .zip(out.par_chunks_mut(PAR_CHUNK_LEN))
+        .for_each(|(values, out)| last_digits_into(values, out));
+}
+
+/// Parallel counterpart of [digit_sums].
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+#[cfg(any(doc, test, doctest, feature = "rayon"))]
+pub fn par_digit_sums<T: BatchDigitsBase10>(values: &[T]) -> Vec<u32> {
+    use rayon::prelude::*;
+
+    let mut sums = alloc::vec![0u32; values.len()];
+    values
+        .par_chunks(PAR_CHUNK_LEN)
+        .zip(sums.par_chunks_mut(PAR_CHUNK_LEN))
+        .for_each(|(values, sums)| digit_sums_into(values, sums));
+    sums
+}
+
+/// Parallel counterpart of [digit_histogram].
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+#[cfg(any(doc, test, doctest, feature = "rayon"))]
+pub fn par_digit_histogram<T: BatchDigitsBase10>(values: &[T]) -> [u64; 10] {
+    use rayon::prelude::*;
+
+    values
+        .par_chunks(PAR_CHUNK_LEN)
+        .map(digit_histogram)
+        .reduce(
+            || [0u64; 10],
+            |mut acc, histogram| {
+                for (a, h) in acc.iter_mut().zip(histogram) {
+                    *a += h;
+                }
+                acc
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::batch::*;
+
+    #[test]
+    fn batch_fns_work_for_negative_primitive_signed_integers() {
+        let values = [i8::MIN, -17, 0, 99];
+        let mut last_digits = [0u8; 4];
+        last_digits_into(&values, &mut last_digits);
+        assert_eq!(last_digits, [8, 7, 0, 9]);
+        assert_eq!(digit_sums(&values), [11, 8, 0, 18]);
+        assert_eq!(digit_histogram(&values), [1, 2, 1, 0, 0, 0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn par_batch_fns_match_sequential_ones() {
+        let values: Vec<u64> = (0..100_000u64)
+            .map(|i| i.wrapping_mul(11_400_714_819_323_198_485) >> (i % 64))
+            .collect();
+        let mut last_digits = vec![0u8; values.len()];
+        let mut par_last_digits = vec![0u8; values.len()];
+        last_digits_into(&values, &mut last_digits);
+        par_last_digits_into(&values, &mut par_last_digits);
+        assert_eq!(last_digits, par_last_digits);
+        assert_eq!(digit_sums(&values), par_digit_sums(&values));
+        assert_eq!(digit_histogram(&values), par_digit_histogram(&values));
+    }
+}
@@ -0,0 +1,249 @@
+//! [Binary-coded decimal](https://en.wikipedia.org/wiki/Binary-coded_decimal) representations of unsigned integers,
+//! with the digits base 10 from the most significant one encoded as nibbles.
+//!
+//! The unpacked BCD stores one digit in the low nibble of every byte. The packed BCD stores two digits
+//! per byte, the more significant one in the high nibble, and the odd counts of digits are padded with
+//! a leading zero digit.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::bcd::{Bcd, BcdEncoding, BcdError};
+//!
+//! let mut buf = [0u8; 3];
+//! let len = 1234u16.packed_bcd_into(BcdEncoding::Weighted8421, &mut buf).unwrap();
+//! assert_eq!(&buf[..len], [0x12, 0x34]);
+//! assert_eq!(u16::from_packed_bcd(&[0x12, 0x34], BcdEncoding::Weighted8421), Ok(1234));
+//! assert_eq!(
+//!     u16::from_packed_bcd(&[0x1A], BcdEncoding::Weighted8421),
+//!     Err(BcdError::InvalidNibble(0xA))
+//! );
+//!
+//! let len = 905u16.unpacked_bcd_into(BcdEncoding::Excess3, &mut buf).unwrap();
+//! assert_eq!(&buf[..len], [0xC, 0x3, 0x8]);
+//! ```
+
+use crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::U8DigitsBase10BeInto;
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use max_len_base_10_as_usize::MaxLenBase10AsUsize;
+
+/// The encoding of the digits base 10 as nibbles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BcdEncoding {
+    /// The natural BCD, where every digit is encoded with its 4-bit binary representation.
+    #[default]
+    Weighted8421,
+    /// The [excess-3](https://en.wikipedia.org/wiki/Excess-3) code, where every digit `d` is encoded as `d + 3`.
+    Excess3,
+}
+
+impl BcdEncoding {
+    #[inline]
+    pub const fn encode_digit(self, digit: u8) -> u8 {
+        match self {
+            BcdEncoding::Weighted8421 => digit,
+            BcdEncoding::Excess3 => digit + 3,
+        }
+    }
+
+    /// Returns the digit or [None] if the nibble is not a code of a digit,
+    /// i.e. it is not less than 10 for [BcdEncoding::Weighted8421] or not in `3..=12` for [BcdEncoding::Excess3].
+    #[inline]
+    pub const fn decode_nibble(self, nibble: u8) -> Option<u8> {
+        match self {
+            BcdEncoding::Weighted8421 if nibble < 10 => Some(nibble),
+            BcdEncoding::Excess3 if nibble >= 3 && nibble <= 12 => Some(nibble - 3),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BcdError {
+    /// The nibble is not a code of a digit in the encoding.
+    InvalidNibble(u8),
+    /// The byte of the unpacked BCD has a non-zero high nibble.
+    InvalidUnpackedByte(u8),
+    /// The value is not representable by the target type.
+    Overflow,
+}
+
+impl Display for BcdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BcdError::InvalidNibble(nibble) => write!(f, "invalid BCD nibble {:#x}", nibble),
+            BcdError::InvalidUnpackedByte(byte) => {
+                write!(f, "invalid unpacked BCD byte {:#04x}", byte)
+            }
+            BcdError::Overflow => f.write_str("the BCD value is out of range of the target type"),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(any(doc, test, doctest, feature = "std"))]
+impl std::error::Error for BcdError {}
+
+pub trait Bcd: Sized {
+    /// Writes the unpacked BCD into the beginning of `buf` and returns its length in bytes or [None]
+    /// if `buf` is too short, in which case the contents of `buf` are unspecified.
+    fn unpacked_bcd_into(&self, encoding: BcdEncoding, buf: &mut [u8]) -> Option<usize>;
+
+    /// Writes the packed BCD into the beginning of `buf` and returns its length in bytes or [None]
+    /// if `buf` is too short, in which case the contents of `buf` are unspecified.
+    fn packed_bcd_into(&self, encoding: BcdEncoding, buf: &mut [u8]) -> Option<usize>;
+
+    /// Decodes the unpacked BCD, whose high nibbles must be 0, or [BcdError::InvalidUnpackedByte] is returned.
+    /// The empty input is decoded as 0.
+    fn from_unpacked_bcd(bytes: &[u8], encoding: BcdEncoding) -> Result<Self, BcdError>;
+
+    /// Decodes the packed BCD. The empty input is decoded as 0.
+    fn from_packed_bcd(bytes: &[u8], encoding: BcdEncoding) -> Result<Self, BcdError>;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_unpacked_bcd(&self, encoding: BcdEncoding) -> Vec<u8>;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_packed_bcd(&self, encoding: BcdEncoding) -> Vec<u8>;
+}
+
+macro_rules! push_digit {
+    ($t:ty, $acc:expr, $encoding:expr, $nibble:expr) => {{
+        let digit = $encoding
+            .decode_nibble($nibble)
+            .ok_or(BcdError::InvalidNibble($nibble))?;
+        $acc.checked_mul(10)
+            .and_then(|acc| acc.checked_add(digit as $t))
+            .ok_or(BcdError::Overflow)
+    }};
+}
+
+macro_rules! impl_bcd {
+    ($($t:ty),+) => {
+        $(
+            impl Bcd for $t {
+                fn unpacked_bcd_into(&self, encoding: BcdEncoding, buf: &mut [u8]) -> Option<usize> {
+                    let len = self.u8_digits_be_into(buf)?;
+                    for b in &mut buf[..len] {
+                        *b = encoding.encode_digit(*b);
+                    }
+                    Some(len)
+                }
+
+                fn packed_bcd_into(&self, encoding: BcdEncoding, buf: &mut [u8]) -> Option<usize> {
+                    // the leading zero digit pads the odd counts of digits
+                    let mut digits = [0u8; <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE + 1];
+                    // the buffer can hold the digits of any value of the type
+                    let len = self.u8_digits_be_into(&mut digits[1..]).unwrap();
+                    let digits = &digits[(len + 1) % 2..=len];
+                    let buf = buf.get_mut(..digits.len() / 2)?;
+                    for (b, pair) in buf.iter_mut().zip(digits.chunks_exact(2)) {
+                        *b = encoding.encode_digit(pair[0]) << 4 | encoding.encode_digit(pair[1]);
+                    }
+                    Some(buf.len())
+                }
+
+                fn from_unpacked_bcd(bytes: &[u8], encoding: BcdEncoding) -> Result<Self, BcdError> {
+                    bytes.iter().try_fold(0, |acc: $t, &b| {
+                        if b > 0xF {
+                            return Err(BcdError::InvalidUnpackedByte(b));
+                        }
+                        push_digit!($t, acc, encoding, b)
+                    })
+                }
+
+                fn from_packed_bcd(bytes: &[u8], encoding: BcdEncoding) -> Result<Self, BcdError> {
+                    bytes
+                        .iter()
+                        .flat_map(|&b| [b >> 4, b & 0xF])
+                        .try_fold(0, |acc: $t, nibble| push_digit!($t, acc, encoding, nibble))
+                }
+
+                #[cfg(any(doc, test, doctest, feature = "alloc"))]
+                fn to_unpacked_bcd(&self, encoding: BcdEncoding) -> Vec<u8> {
+                    let mut buf = [0u8; <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE];
+                    let len = self.unpacked_bcd_into(encoding, &mut buf).unwrap();
+                    buf[..len].to_vec()
+                }
+
+                #[cfg(any(doc, test, doctest, feature = "alloc"))]
+                fn to_packed_bcd(&self, encoding: BcdEncoding) -> Vec<u8> {
+                    let mut buf = [0u8; <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE.div_ceil(2)];
+                    let len = self.packed_bcd_into(encoding, &mut buf).unwrap();
+                    buf[..len].to_vec()
+                }
+            }
+        )+
+    };
+}
+
+impl_bcd!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::bcd::{Bcd, BcdEncoding, BcdError};
+
+    #[test]
+    fn bcd_representations_round_trip() {
+        for encoding in [BcdEncoding::Weighted8421, BcdEncoding::Excess3] {
+            for n in (0u32..=1_000_000).step_by(7).chain([u32::MAX]) {
+                let unpacked = n.to_unpacked_bcd(encoding);
+                assert_eq!(unpacked.len(), n.to_string().len());
+                assert_eq!(u32::from_unpacked_bcd(&unpacked, encoding), Ok(n));
+                let packed = n.to_packed_bcd(encoding);
+                assert_eq!(packed.len(), n.to_string().len().div_ceil(2));
+                assert_eq!(u32::from_packed_bcd(&packed, encoding), Ok(n));
+            }
+            for n in [0u128, 9, 10, u128::MAX] {
+                let packed = n.to_packed_bcd(encoding);
+                assert_eq!(u128::from_packed_bcd(&packed, encoding), Ok(n));
+            }
+        }
+        assert_eq!(
+            u64::MAX.to_packed_bcd(BcdEncoding::Weighted8421),
+            [0x18, 0x44, 0x67, 0x44, 0x07, 0x37, 0x09, 0x55, 0x16, 0x15]
+        );
+        assert_eq!(7u8.to_packed_bcd(BcdEncoding::Excess3), [0x3A]);
+        assert_eq!(0u8.to_unpacked_bcd(BcdEncoding::Weighted8421), [0]);
+    }
+
+    #[test]
+    fn invalid_bcd_is_rejected() {
+        use BcdEncoding::{Excess3, Weighted8421};
+
+        assert_eq!(
+            u8::from_packed_bcd(&[0x0F], Weighted8421),
+            Err(BcdError::InvalidNibble(0xF))
+        );
+        assert_eq!(
+            u8::from_packed_bcd(&[0xA0], Weighted8421),
+            Err(BcdError::InvalidNibble(0xA))
+        );
+        assert_eq!(
+            u8::from_unpacked_bcd(&[0x15], Weighted8421),
+            Err(BcdError::InvalidUnpackedByte(0x15))
+        );
+        assert_eq!(
+            BcdError::InvalidUnpackedByte(0x15).to_string(),
+            "invalid unpacked BCD byte 0x15"
+        );
+        assert_eq!(
+            u8::from_unpacked_bcd(&[2], Excess3),
+            Err(BcdError::InvalidNibble(2))
+        );
+        assert_eq!(
+            u8::from_unpacked_bcd(&[13], Excess3),
+            Err(BcdError::InvalidNibble(13))
+        );
+        assert_eq!(
+            u8::from_packed_bcd(&[0x02, 0x56], Weighted8421),
+            Err(BcdError::Overflow)
+        );
+        assert_eq!(u8::from_packed_bcd(&[0x02, 0x55], Weighted8421), Ok(255));
+        assert_eq!(u8::from_packed_bcd(&[], Weighted8421), Ok(0));
+        assert_eq!(1234u16.packed_bcd_into(Weighted8421, &mut [0; 1]), None);
+        assert_eq!(1234u16.unpacked_bcd_into(Weighted8421, &mut [0; 3]), None);
+    }
+}
@@ -0,0 +1,191 @@
+//! [Bijective base-k numeration](https://en.wikipedia.org/wiki/Bijective_numeration) with the digits
+//! `1..=k`, in which every non-negative integer has exactly one representation and zero is empty.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::bijective::Bijective;
+//!
+//! // spreadsheet column names are bijective base 26 with the digits A..=Z
+//! assert_eq!(28u32.to_spreadsheet_column().unwrap(), "AB");
+//! assert_eq!(u32::from_spreadsheet_column("ZZ"), Some(702));
+//! // bijective base 10 writes 10 with the digit A
+//! assert_eq!(110u8.to_bijective_string(10).unwrap(), "AA");
+//! ```
+
+use crate::num_sys::sign_magnitude::{Magnitude, SignMagnitude, SignedAcc};
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::{string::String, vec::Vec};
+
+/// The iterator over the digits in bijective base `radix` from the least significant one.
+/// Zero has no digits.
+#[derive(Clone, Debug)]
+pub struct BijectiveDigitsLe<M> {
+    magnitude: M,
+    radix: u32,
+}
+
+impl<M: Magnitude> Iterator for BijectiveDigitsLe<M> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.magnitude.is_zero() {
+            return None;
+        }
+        // n = q * radix + d with 1 <= d <= radix
+        match self.magnitude.div_rem_assign_u32(self.radix) {
+            0 => {
+                self.magnitude = self.magnitude.sub_u32(1);
+                Some(self.radix)
+            }
+            rem => Some(rem),
+        }
+    }
+}
+
+/// Conversions to and from the representations in bijective base `radix`.
+///
+/// # Panics
+///
+/// The methods panic if `radix` is 0 or, for the string forms, greater than 35.
+pub trait Bijective: Sized {
+    type Magnitude: Magnitude;
+
+    /// Returns [None] for negative integers.
+    fn bijective_digits_le(&self, radix: u32) -> Option<BijectiveDigitsLe<Self::Magnitude>>;
+
+    /// Returns [None] if a digit is not in `1..=radix` or the value is not representable by `Self`.
+    fn from_bijective_digits_le(digits: &[u32], radix: u32) -> Option<Self>;
+
+    /// Parses the digits written from the most significant one with `1-9` and `A-Z` (case-insensitive)
+    /// for the digits from 10.
+    fn from_bijective_str(s: &str, radix: u32) -> Option<Self>;
+
+    /// Parses a spreadsheet column name, i.e. the digits in bijective base 26 written with `A-Z`
+    /// (case-insensitive).
+    fn from_spreadsheet_column(s: &str) -> Option<Self>;
+
+    /// Writes the digits from the most significant one with `1-9` and `A-Z` for the digits from 10
+    /// or returns [None] for negative integers.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_bijective_string(&self, radix: u32) -> Option<String> {
+        assert!(radix <= 35, "The radix must be within 1...35");
+        let digits: Vec<u32> = self.bijective_digits_le(radix)?.collect();
+        // the digits are in 1..=35
+        Some(
+            digits
+                .into_iter()
+                .rev()
+                .map(|d| char::from_digit(d, 36).unwrap().to_ascii_uppercase())
+                .collect(),
+        )
+    }
+
+    /// Writes the spreadsheet column name or returns [None] for negative integers.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_spreadsheet_column(&self) -> Option<String> {
+        let digits: Vec<u32> = self.bijective_digits_le(26)?.collect();
+        // the digits are in 1..=26
+        Some(
+            digits
+                .into_iter()
+                .rev()
+                .map(|d| char::from(b'A' + d as u8 - 1))
+                .collect(),
+        )
+    }
+}
+
+impl<T: SignMagnitude> Bijective for T {
+    type Magnitude = T::Magnitude;
+
+    fn bijective_digits_le(&self, radix: u32) -> Option<BijectiveDigitsLe<T::Magnitude>> {
+        assert!(radix >= 1, "The radix must be at least 1");
+        match self.to_sign_magnitude() {
+            (true, magnitude) if !magnitude.is_zero() => None,
+            (_, magnitude) => Some(BijectiveDigitsLe { magnitude, radix }),
+        }
+    }
+
+    fn from_bijective_digits_le(digits: &[u32], radix: u32) -> Option<Self> {
+        assert!(radix >= 1, "The radix must be at least 1");
+        from_bijective_digits_be(digits.iter().rev().copied(), radix)
+    }
+
+    fn from_bijective_str(s: &str, radix: u32) -> Option<Self> {
+        assert!((1..=35).contains(&radix), "The radix must be within 1...35");
+        // '0' is not a digit, which from_bijective_digits_be rejects
+        let digits = s.chars().map(|c| c.to_digit(36).unwrap_or(0));
+        from_bijective_digits_be(digits, radix)
+    }
+
+    fn from_spreadsheet_column(s: &str) -> Option<Self> {
+        let digits = s.chars().map(|c| match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => u32::from(c) - u32::from('A') + 1,
+            _ => 0,
+        });
+        from_bijective_digits_be(digits, 26)
+    }
+}
+
+fn from_bijective_digits_be<T: SignMagnitude, I: Iterator<Item = u32>>(
+    digits: I,
+    radix: u32,
+) -> Option<T> {
+    let mut acc = SignedAcc::zero();
+    for d in digits {
+        if !(1..=radix).contains(&d) {
+            return None;
+        }
+        acc = acc.checked_mul_add((false, radix), (false, d))?;
+    }
+    acc.into_int()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::bijective::Bijective;
+
+    #[test]
+    fn bijective_representations_round_trip() {
+        for radix in [1, 2, 10, 26, 35] {
+            for n in 0u16..=(if radix == 1 { 100 } else { 5000 }) {
+                let digits: Vec<u32> = n.bijective_digits_le(radix).unwrap().collect();
+                assert!(digits.iter().all(|&d| (1..=radix).contains(&d)));
+                assert_eq!(u16::from_bijective_digits_le(&digits, radix), Some(n));
+                let s = n.to_bijective_string(radix).unwrap();
+                assert_eq!(u16::from_bijective_str(&s, radix), Some(n));
+            }
+        }
+        assert_eq!(5u8.to_bijective_string(1).unwrap(), "11111");
+        assert_eq!(0u8.to_bijective_string(10).unwrap(), "");
+        assert_eq!((-1i8).bijective_digits_le(10).map(|_| ()), None);
+        assert_eq!(u8::from_bijective_str("10", 10), None);
+        assert_eq!(
+            u128::from_bijective_str(&u128::MAX.to_bijective_string(3).unwrap(), 3),
+            Some(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn spreadsheet_columns_round_trip() {
+        let cases = [
+            (1u32, "A"),
+            (26, "Z"),
+            (27, "AA"),
+            (702, "ZZ"),
+            (703, "AAA"),
+        ];
+        for (n, column) in cases {
+            assert_eq!(n.to_spreadsheet_column().unwrap(), column);
+            assert_eq!(u32::from_spreadsheet_column(column), Some(n));
+        }
+        assert_eq!(u32::from_spreadsheet_column("a1"), None);
+
+        use num_bigint::BigUint;
+
+        let n = BigUint::from(26u32).pow(40) * 3u32 + 1u32;
+        let column = n.to_spreadsheet_column().unwrap();
+        assert_eq!(BigUint::from_spreadsheet_column(&column), Some(n));
+    }
+}
@@ -0,0 +1,375 @@
+//! [Check digit](https://en.wikipedia.org/wiki/Check_digit) schemes over digits base 10.
+//!
+//! Every scheme is a zero-sized type implementing [CheckDigitScheme]. Digit slices are written
+//! from the most significant digit, as identifiers are printed, and integers are decomposed with
+//! [U8DigitsBase10BeInto] into a stack buffer, so nothing allocates for slices and primitive integers
+//! (the big integers allocate their digits with `to_radix_be`).
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::check_digits::{compute_for, validate_for, CheckDigitScheme, Isbn10, Luhn};
+//!
+//! assert_eq!(Luhn::compute(&[7, 9, 9, 2, 7, 3, 9, 8, 7, 1]), Some(3));
+//! assert!(validate_for::<Luhn, _>(&79_927_398_713u64));
+//! // the fixed-length schemes pad integers with leading zeros, and 10 stands for 'X'
+//! assert_eq!(compute_for::<Isbn10, _>(&30_640_615u32), Some(2));
+//! ```
+
+use crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::U8DigitsBase10BeInto;
+
+/// A check digit scheme over digits base 10 written from the most significant digit.
+pub trait CheckDigitScheme {
+    /// The length of the payload for fixed-length schemes.
+    const PAYLOAD_LEN: Option<usize> = None;
+    /// The number of digits base 10 that the check value takes in the full identifier.
+    const CHECK_LEN: usize = 1;
+
+    /// Returns the check value for the payload or [None] if the payload contains a value
+    /// that is not a digit base 10 or its length differs from [PAYLOAD_LEN][CheckDigitScheme::PAYLOAD_LEN].
+    fn compute(payload: &[u8]) -> Option<u8>;
+
+    /// Checks whether `digits` are a payload followed by its check value, written with
+    /// [CHECK_LEN][CheckDigitScheme::CHECK_LEN] digits.
+    fn validate(digits: &[u8]) -> bool {
+        if digits.len() < Self::CHECK_LEN {
+            return false;
+        }
+        let (payload, check) = digits.split_at(digits.len() - Self::CHECK_LEN);
+        let check = check
+            .iter()
+            .try_fold(0u8, |acc, &d| (d < 10).then(|| acc * 10 + d));
+        check.is_some() && Self::compute(payload) == check
+    }
+}
+
+// Enough for every identifier in practice, including the numeric forms of IBANs
+const MAX_DIGITS: usize = 128;
+
+// Writes the digits of `n` base 10 into the beginning of `buf`, padding them with leading zeros
+// up to `min_len`, and returns their count.
+fn digits_be_into<T: U8DigitsBase10BeInto>(
+    n: &T,
+    min_len: usize,
+    buf: &mut [u8; MAX_DIGITS],
+) -> Option<usize> {
+    let len = n.u8_digits_be_into(buf)?;
+    let padded_len = len.max(min_len);
+    if padded_len > MAX_DIGITS {
+        return None;
+    }
+    let start = padded_len - len;
+    buf.copy_within(..len, start);
+    buf[..start].fill(0);
+    Some(padded_len)
+}
+
+/// Returns the check value for the digits base 10 of `payload` or [None] if they are invalid for the scheme
+/// or there are more than 128 of them. Fixed-length schemes pad the digits with leading zeros.
+///
+/// For signed integers, the digits are the digits of the absolute value.
+pub fn compute_for<S: CheckDigitScheme, T: U8DigitsBase10BeInto>(payload: &T) -> Option<u8> {
+    let mut buf = [0u8; MAX_DIGITS];
+    let len = digits_be_into(payload, S::PAYLOAD_LEN.unwrap_or(0), &mut buf)?;
+    S::compute(&buf[..len])
+}
+
+/// Checks whether the digits base 10 of `n` are a payload followed by its check value.
+/// Fixed-length schemes pad the digits with leading zeros.
+///
+/// For signed integers, the digits are the digits of the absolute value.
+pub fn validate_for<S: CheckDigitScheme, T: U8DigitsBase10BeInto>(n: &T) -> bool {
+    let mut buf = [0u8; MAX_DIGITS];
+    let min_len = S::PAYLOAD_LEN.map_or(0, |len| len + S::CHECK_LEN);
+    match digits_be_into(n, min_len, &mut buf) {
+        Some(len) => S::validate(&buf[..len]),
+        None => false,
+    }
+}
+
+fn check_payload<S: CheckDigitScheme>(payload: &[u8]) -> Option<()> {
+    let len_matches = !matches!(S::PAYLOAD_LEN, Some(len) if len != payload.len());
+    (len_matches && payload.iter().all(|&d| d < 10)).then_some(())
+}
+
+/// The [Luhn algorithm](https://en.wikipedia.org/wiki/Luhn_algorithm) (mod 10), used for payment card numbers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Luhn;
+
+impl CheckDigitScheme for Luhn {
+    fn compute(payload: &[u8]) -> Option<u8> {
+        check_payload::<Self>(payload)?;
+        let sum: u32 = payload
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &d)| {
+                let d = u32::from(d);
+                if i % 2 == 0 {
+                    // the digit sum of the doubled digit
+                    if d < 5 {
+                        2 * d
+                    } else {
+                        2 * d - 9
+                    }
+                } else {
+                    d
+                }
+            })
+            .sum();
+        Some(((10 - sum % 10) % 10) as u8)
+    }
+}
+
+/// The [Verhoeff algorithm](https://en.wikipedia.org/wiki/Verhoeff_algorithm), which detects
+/// all single-digit errors and all transpositions of adjacent digits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Verhoeff;
+
+impl Verhoeff {
+    // the multiplication table of the dihedral group D5
+    const D: [[u8; 10]; 10] = [
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+        [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+        [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+        [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+        [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+        [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+        [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+        [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+        [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+        [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+    ];
+    // the powers of the permutation applied to the digit at the given position from the right
+    const P: [[u8; 10]; 8] = [
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+        [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+        [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+        [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+        [9, 4, 5, 3, 1, 2, 6, 8, 7, 0],
+        [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+        [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+        [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+    ];
+    const INV: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+    // `offset` is the position from the right of the last digit
+    fn fold(digits: &[u8], offset: usize) -> u8 {
+        digits.iter().rev().enumerate().fold(0, |c, (i, &d)| {
+            Self::D[usize::from(c)][usize::from(Self::P[(i + offset) % 8][usize::from(d)])]
+        })
+    }
+}
+
+impl CheckDigitScheme for Verhoeff {
+    fn compute(payload: &[u8]) -> Option<u8> {
+        check_payload::<Self>(payload)?;
+        Some(Self::INV[usize::from(Self::fold(payload, 1))])
+    }
+
+    fn validate(digits: &[u8]) -> bool {
+        !digits.is_empty() && digits.iter().all(|&d| d < 10) && Self::fold(digits, 0) == 0
+    }
+}
+
+/// The [Damm algorithm](https://en.wikipedia.org/wiki/Damm_algorithm), which detects
+/// all single-digit errors and all transpositions of adjacent digits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Damm;
+
+impl Damm {
+    // a totally anti-symmetric quasigroup of order 10 with zeros on the diagonal
+    const TABLE: [[u8; 10]; 10] = [
+        [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+        [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+        [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+        [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+        [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+        [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+        [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+        [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+        [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+        [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+    ];
+}
+
+impl CheckDigitScheme for Damm {
+    fn compute(payload: &[u8]) -> Option<u8> {
+        check_payload::<Self>(payload)?;
+        Some(
+            payload
+                .iter()
+                .fold(0, |c, &d| Self::TABLE[usize::from(c)][usize::from(d)]),
+        )
+    }
+}
+
+/// The [ISBN-10](https://en.wikipedia.org/wiki/ISBN#ISBN-10_check_digits) check digit (mod 11)
+/// of a 9-digit payload. The check value 10 is written as 'X'; digit slices
+/// passed to [validate][CheckDigitScheme::validate] may end with 10 for it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Isbn10;
+
+impl CheckDigitScheme for Isbn10 {
+    const PAYLOAD_LEN: Option<usize> = Some(9);
+
+    fn compute(payload: &[u8]) -> Option<u8> {
+        check_payload::<Self>(payload)?;
+        let sum: u32 = payload
+            .iter()
+            .zip((2..=10).rev())
+            .map(|(&d, w)| u32::from(d) * w)
+            .sum();
+        Some(((11 - sum % 11) % 11) as u8)
+    }
+
+    fn validate(digits: &[u8]) -> bool {
+        match digits.split_last() {
+            Some((&check, payload)) => Self::compute(payload) == Some(check),
+            None => false,
+        }
+    }
+}
+
+// The check digit of GTINs (EAN-8, UPC-A, EAN-13, ...): the weights from the right
+// of the payload are 3, 1, 3, 1, ...
+fn gtin_check_digit(payload: &[u8]) -> u8 {
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| u32::from(d) * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// The [EAN-13](https://en.wikipedia.org/wiki/International_Article_Number) check digit
+/// of a 12-digit payload.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Ean13;
+
+/// The [ISBN-13](https://en.wikipedia.org/wiki/ISBN#ISBN-13_check_digit_calculation) check digit,
+/// which is the [EAN-13][Ean13] one.
+pub type Isbn13 = Ean13;
+
+impl CheckDigitScheme for Ean13 {
+    const PAYLOAD_LEN: Option<usize> = Some(12);
+
+    fn compute(payload: &[u8]) -> Option<u8> {
+        check_payload::<Self>(payload)?;
+        Some(gtin_check_digit(payload))
+    }
+}
+
+/// The [UPC-A](https://en.wikipedia.org/wiki/Universal_Product_Code#Check_digit_calculation) check digit
+/// of an 11-digit payload.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UpcA;
+
+impl CheckDigitScheme for UpcA {
+    const PAYLOAD_LEN: Option<usize> = Some(11);
+
+    fn compute(payload: &[u8]) -> Option<u8> {
+        check_payload::<Self>(payload)?;
+        Some(gtin_check_digit(payload))
+    }
+}
+
+/// The ISO 7064 MOD 97-10 check value in `2..=98`, written with two digits, as used by
+/// [IBANs](https://en.wikipedia.org/wiki/International_Bank_Account_Number#Validating_the_IBAN).
+///
+/// The payload of an IBAN is its rearranged numeric form without the check digits,
+/// where the letters are replaced with `10..=35`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Iso7064Mod97_10;
+
+impl Iso7064Mod97_10 {
+    fn rem_97(digits: &[u8]) -> u32 {
+        digits.iter().fold(0, |r, &d| (r * 10 + u32::from(d)) % 97)
+    }
+}
+
+impl CheckDigitScheme for Iso7064Mod97_10 {
+    const CHECK_LEN: usize = 2;
+
+    fn compute(payload: &[u8]) -> Option<u8> {
+        check_payload::<Self>(payload)?;
+        Some((98 - Self::rem_97(payload) * 100 % 97) as u8)
+    }
+
+    fn validate(digits: &[u8]) -> bool {
+        digits.len() > Self::CHECK_LEN
+            && digits.iter().all(|&d| d < 10)
+            && Self::rem_97(digits) == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::check_digits::*;
+
+    #[test]
+    fn check_digit_schemes_match_reference_examples() {
+        assert_eq!(Luhn::compute(&[7, 9, 9, 2, 7, 3, 9, 8, 7, 1]), Some(3));
+        assert_eq!(Verhoeff::compute(&[2, 3, 6]), Some(3));
+        assert!(Verhoeff::validate(&[2, 3, 6, 3]));
+        assert_eq!(Damm::compute(&[5, 7, 2]), Some(4));
+        assert!(Damm::validate(&[5, 7, 2, 4]));
+        assert_eq!(Isbn10::compute(&[0, 3, 0, 6, 4, 0, 6, 1, 5]), Some(2));
+        assert!(Isbn10::validate(&[0, 8, 0, 4, 4, 2, 9, 5, 7, 10]));
+        assert_eq!(
+            Isbn13::compute(&[9, 7, 8, 0, 3, 0, 6, 4, 0, 6, 1, 5]),
+            Some(7)
+        );
+        assert_eq!(UpcA::compute(&[0, 3, 6, 0, 0, 0, 2, 9, 1, 4, 5]), Some(2));
+        // GB82 WEST 1234 5698 7654 32
+        assert_eq!(
+            compute_for::<Iso7064Mod97_10, _>(&32_142_829_123_456_987_654_321_611u128),
+            Some(82)
+        );
+        assert!(validate_for::<Iso7064Mod97_10, _>(
+            &3_214_282_912_345_698_765_432_161_182u128
+        ));
+    }
+
+    #[test]
+    fn check_digit_schemes_reject_invalid_input() {
+        assert_eq!(Luhn::compute(&[1, 10]), None);
+        assert_eq!(Ean13::compute(&[1; 11]), None);
+        assert!(!Luhn::validate(&[]));
+        assert!(!Damm::validate(&[5, 7, 2, 10]));
+        assert!(!Iso7064Mod97_10::validate(&[1]));
+        assert!(!validate_for::<UpcA, _>(&36_000_291_453u64));
+        assert!(validate_for::<UpcA, _>(&36_000_291_452u64));
+    }
+
+    #[test]
+    fn verhoeff_and_damm_detect_single_digit_errors_and_adjacent_transpositions() {
+        let payload = [8u8, 4, 7, 3, 1, 9, 0, 2];
+        for check in [Verhoeff::compute(&payload), Damm::compute(&payload)] {
+            assert!(check.is_some());
+        }
+        let mut digits = [0u8; 9];
+        digits[..8].copy_from_slice(&payload);
+        for (compute, validate) in [
+            (
+                Verhoeff::compute as fn(&[u8]) -> Option<u8>,
+                Verhoeff::validate as fn(&[u8]) -> bool,
+            ),
+            (Damm::compute, Damm::validate),
+        ] {
+            digits[8] = compute(&payload).unwrap();
+            assert!(validate(&digits));
+            for i in 0..digits.len() {
+                for d in 0..10 {
+                    let mut corrupted = digits;
+                    corrupted[i] = d;
+                    assert_eq!(validate(&corrupted), d == digits[i]);
+                }
+                if i + 1 < digits.len() && digits[i] != digits[i + 1] {
+                    let mut corrupted = digits;
+                    corrupted.swap(i, i + 1);
+                    assert!(!validate(&corrupted));
+                }
+            }
+        }
+    }
+}
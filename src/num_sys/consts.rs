@@ -0,0 +1,192 @@
+//! `const fn` counterparts of the base 10 digit traits that are usable on stable Rust.
+//!
+//! The trait implementations in [num_sys::base_10::int][crate::num_sys::base_10::int] are `const`
+//! only with `const_trait_impl` and `const_ops` enabled. The free functions below can be used
+//! in constant contexts (e.g. for lookup tables or static assertions) without nightly Rust.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::consts::{digit_count_u64, last_digit_i32};
+//!
+//! const LEN: u32 = digit_count_u64(u64::MAX);
+//! const LAST: u8 = last_digit_i32(-17);
+//! assert_eq!((LEN, LAST), (20, 7));
+//! ```
+//!
+//! For signed integers, the digits are the digits of the absolute value, exactly as in
+//! [GetLastDigitBase10AsU8][crate::num_sys::base_10::GetLastDigitBase10AsU8].
+
+macro_rules! impl_unsigned_consts {
+    ($($t:ty => $last_digit:ident, $digit_count:ident, $digit_sum:ident, $reverse_digits:ident;)+) => {
+        $(
+            /// Returns the last digit base 10.
+            #[inline]
+            pub const fn $last_digit(n: $t) -> u8 {
+                // cast doesn't wrap around because the remainder is < 10
+                (n % 10) as u8
+            }
+
+            /// Returns the number of digits base 10. Zero has one digit.
+            #[inline]
+            pub const fn $digit_count(n: $t) -> u32 {
+                let mut count: u32 = 1;
+                let mut q = n / 10;
+                while q != 0 {
+                    q /= 10;
+                    count += 1;
+                }
+                count
+            }
+
+            /// Returns the sum of digits base 10.
+            #[inline]
+            pub const fn $digit_sum(n: $t) -> u32 {
+                let mut sum: u32 = 0;
+                let mut q = n;
+                loop {
+                    sum += (q % 10) as u32;
+                    q /= 10;
+                    if q == 0 {
+                        break;
+                    }
+                }
+                sum
+            }
+
+            /// Returns the number with the digits base 10 in reverse order or [None] if it
+            /// doesn't fit into the type. Trailing zeros of the argument are dropped.
+            #[inline]
+            pub const fn $reverse_digits(n: $t) -> Option<$t> {
+                let mut reversed: $t = 0;
+                let mut q = n;
+                while q != 0 {
+                    reversed = match reversed.checked_mul(10) {
+                        Some(r) => r,
+                        None => return None,
+                    };
+                    reversed = match reversed.checked_add(q % 10) {
+                        Some(r) => r,
+                        None => return None,
+                    };
+                    q /= 10;
+                }
+                Some(reversed)
+            }
+        )+
+    };
+}
+
+macro_rules! impl_signed_consts {
+    ($($t:ty as $ut:ty => $last_digit:ident, $digit_count:ident, $digit_sum:ident, $reverse_digits:ident
+        via $u_last_digit:ident, $u_digit_count:ident, $u_digit_sum:ident, $u_reverse_digits:ident;)+) => {
+        $(
+            /// Returns the last digit base 10 of the absolute value.
+            #[inline]
+            pub const fn $last_digit(n: $t) -> u8 {
+                $u_last_digit(n.unsigned_abs())
+            }
+
+            /// Returns the number of digits base 10 of the absolute value. Zero has one digit.
+            #[inline]
+            pub const fn $digit_count(n: $t) -> u32 {
+                $u_digit_count(n.unsigned_abs())
+            }
+
+            /// Returns the sum of digits base 10 of the absolute value.
+            #[inline]
+            pub const fn $digit_sum(n: $t) -> u32 {
+                $u_digit_sum(n.unsigned_abs())
+            }
+
+            /// Returns the number with the digits base 10 of the absolute value in reverse order
+            /// and the sign of the argument or [None] if it doesn't fit into the type.
+            /// Trailing zeros of the argument are dropped.
+            #[inline]
+            pub const fn $reverse_digits(n: $t) -> Option<$t> {
+                let reversed: $ut = match $u_reverse_digits(n.unsigned_abs()) {
+                    Some(r) => r,
+                    None => return None,
+                };
+                if n < 0 {
+                    // -(<$t>::MIN) is not representable but <$t>::MIN is
+                    if reversed == <$t>::MIN.unsigned_abs() {
+                        Some(<$t>::MIN)
+                    } else if reversed > <$t>::MAX as $ut {
+                        None
+                    } else {
+                        Some(-(reversed as $t))
+                    }
+                } else if reversed > <$t>::MAX as $ut {
+                    None
+                } else {
+                    Some(reversed as $t)
+                }
+            }
+        )+
+    };
+}
+
+impl_unsigned_consts!(
+    u8 => last_digit_u8, digit_count_u8, digit_sum_u8, reverse_digits_u8;
+    u16 => last_digit_u16, digit_count_u16, digit_sum_u16, reverse_digits_u16;
+    u32 => last_digit_u32, digit_count_u32, digit_sum_u32, reverse_digits_u32;
+    u64 => last_digit_u64, digit_count_u64, digit_sum_u64, reverse_digits_u64;
+    u128 => last_digit_u128, digit_count_u128, digit_sum_u128, reverse_digits_u128;
+    usize => last_digit_usize, digit_count_usize, digit_sum_usize, reverse_digits_usize;
+);
+
+impl_signed_consts!(
+    i8 as u8 => last_digit_i8, digit_count_i8, digit_sum_i8, reverse_digits_i8
+        via last_digit_u8, digit_count_u8, digit_sum_u8, reverse_digits_u8;
+    i16 as u16 => last_digit_i16, digit_count_i16, digit_sum_i16, reverse_digits_i16
+        via last_digit_u16, digit_count_u16, digit_sum_u16, reverse_digits_u16;
+    i32 as u32 => last_digit_i32, digit_count_i32, digit_sum_i32, reverse_digits_i32
+        via last_digit_u32, digit_count_u32, digit_sum_u32, reverse_digits_u32;
+    i64 as u64 => last_digit_i64, digit_count_i64, digit_sum_i64, reverse_digits_i64
+        via last_digit_u64, digit_count_u64, digit_sum_u64, reverse_digits_u64;
+    i128 as u128 => last_digit_i128, digit_count_i128, digit_sum_i128, reverse_digits_i128
+        via last_digit_u128, digit_count_u128, digit_sum_u128, reverse_digits_u128;
+    isize as usize => last_digit_isize, digit_count_isize, digit_sum_isize, reverse_digits_isize
+        via last_digit_usize, digit_count_usize, digit_sum_usize, reverse_digits_usize;
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn const_digit_fns_are_usable_in_const_contexts() {
+        const LAST: u8 = last_digit_u32(1_234_567);
+        const COUNT: u32 = digit_count_u64(u64::MAX);
+        const SUM: u32 = digit_sum_u128(u128::MAX);
+        const REVERSED: Option<u64> = reverse_digits_u64(1_234_500);
+        assert_eq!(LAST, 7);
+        assert_eq!(COUNT, 20);
+        // 340282366920938463463374607431768211455
+        assert_eq!(SUM, 165);
+        assert_eq!(REVERSED, Some(54_321));
+    }
+
+    #[test]
+    fn const_digit_fns_work_for_zero() {
+        assert_eq!(last_digit_u8(0), 0);
+        assert_eq!(digit_count_u8(0), 1);
+        assert_eq!(digit_sum_u8(0), 0);
+        assert_eq!(reverse_digits_u8(0), Some(0));
+    }
+
+    #[test]
+    fn const_digit_fns_work_for_negative_primitive_signed_integers() {
+        assert_eq!(last_digit_i8(i8::MIN), 8);
+        assert_eq!(digit_count_i128(i128::MIN), 39);
+        assert_eq!(digit_sum_i32(-17), 8);
+        assert_eq!(reverse_digits_i16(-123), Some(-321));
+    }
+
+    #[test]
+    fn reverse_digits_detects_overflow() {
+        assert_eq!(reverse_digits_u8(199), None);
+        assert_eq!(reverse_digits_i8(-128), None);
+        assert_eq!(reverse_digits_i8(-82), Some(-28));
+        assert_eq!(reverse_digits_u64(u64::MAX), None);
+    }
+}
@@ -0,0 +1,310 @@
+//! Digit frequency histograms and their conformity to [Benford's law](https://en.wikipedia.org/wiki/Benford%27s_law).
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::digit_histogram::DigitHistogram;
+//!
+//! // powers of 2 are known to follow Benford's law
+//! let histogram = DigitHistogram::from_iter(10, (0..128).map(|k| 1u128 << k));
+//! let conformity = histogram.benford_first_digit_conformity().unwrap();
+//! assert!(conformity.mad < 0.006);
+//! ```
+
+use alloc::vec::Vec;
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint};
+
+/// Integers whose digits of the absolute value can be enumerated in any radix in `2..=256`.
+pub trait DigitsInRadix {
+    /// Calls `f` with the digits base `radix` of the absolute value from the least significant
+    /// to the most significant one. Zero has a single digit.
+    fn for_each_digit_le<F: FnMut(u32)>(&self, radix: u32, f: F);
+}
+
+macro_rules! impl_digits_in_radix {
+    (@UNSIGNED [$($t:ty),+]) => {
+        $(
+            impl DigitsInRadix for $t {
+                #[inline]
+                fn for_each_digit_le<F: FnMut(u32)>(&self, radix: u32, f: F) {
+                    for_each_digit_le(u128::from(*self), radix, f)
+                }
+            }
+        )+
+    };
+    (@SIGNED [$($t:ty),+]) => {
+        $(
+            impl DigitsInRadix for $t {
+                #[inline]
+                fn for_each_digit_le<F: FnMut(u32)>(&self, radix: u32, f: F) {
+                    for_each_digit_le(u128::from(self.unsigned_abs()), radix, f)
+                }
+            }
+        )+
+    };
+}
+
+fn for_each_digit_le<F: FnMut(u32)>(n: u128, radix: u32, mut f: F) {
+    let radix = u128::from(radix);
+    let mut q = n;
+    loop {
+        // the remainder is less than radix <= 256
+        f((q % radix) as u32);
+        q /= radix;
+        if q == 0 {
+            break;
+        }
+    }
+}
+
+impl_digits_in_radix!(@UNSIGNED [u8, u16, u32, u64, u128]);
+impl_digits_in_radix!(@SIGNED [i8, i16, i32, i64, i128]);
+
+impl DigitsInRadix for usize {
+    #[inline]
+    fn for_each_digit_le<F: FnMut(u32)>(&self, radix: u32, f: F) {
+        // usize is at most 128 bits wide on all supported platforms
+        for_each_digit_le(*self as u128, radix, f)
+    }
+}
+
+impl DigitsInRadix for isize {
+    #[inline]
+    fn for_each_digit_le<F: FnMut(u32)>(&self, radix: u32, f: F) {
+        for_each_digit_le(self.unsigned_abs() as u128, radix, f)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl DigitsInRadix for BigUint {
+    fn for_each_digit_le<F: FnMut(u32)>(&self, radix: u32, f: F) {
+        self.to_radix_le(radix)
+            .into_iter()
+            .map(u32::from)
+            .for_each(f)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl DigitsInRadix for BigInt {
+    #[inline(always)]
+    fn for_each_digit_le<F: FnMut(u32)>(&self, radix: u32, f: F) {
+        self.magnitude().for_each_digit_le(radix, f)
+    }
+}
+
+/// Counts of digits of integers in the given radix.
+///
+/// * [all_digits][DigitHistogram::all_digits] counts digits in all positions;
+/// * [first_digits][DigitHistogram::first_digits] counts the most significant digits of non-zero integers;
+/// * [first_two_digits][DigitHistogram::first_two_digits] counts the numbers formed by the two most
+///   significant digits of integers with at least two digits, so only the indices in `radix..radix^2` are used;
+/// * [last_digits][DigitHistogram::last_digits] counts the least significant digits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DigitHistogram {
+    radix: u32,
+    all_digits: Vec<u64>,
+    first_digits: Vec<u64>,
+    first_two_digits: Vec<u64>,
+    last_digits: Vec<u64>,
+}
+
+impl DigitHistogram {
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in `2..=256`.
+    pub fn new(radix: u32) -> Self {
+        assert!(
+            (2..=256).contains(&radix),
+            "The radix must be within 2...256"
+        );
+        let radix_usize = radix as usize;
+        Self {
+            radix,
+            all_digits: alloc::vec![0; radix_usize],
+            first_digits: alloc::vec![0; radix_usize],
+            first_two_digits: alloc::vec![0; radix_usize * radix_usize],
+            last_digits: alloc::vec![0; radix_usize],
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in `2..=256`.
+    pub fn from_iter<T: DigitsInRadix, I: IntoIterator<Item = T>>(radix: u32, iter: I) -> Self {
+        let mut histogram = Self::new(radix);
+        histogram.extend(iter);
+        histogram
+    }
+
+    pub fn add<T: DigitsInRadix>(&mut self, n: &T) {
+        let (mut len, mut first, mut second) = (0usize, 0u32, 0u32);
+        n.for_each_digit_le(self.radix, |d| {
+            if len == 0 {
+                self.last_digits[d as usize] += 1;
+            }
+            self.all_digits[d as usize] += 1;
+            second = first;
+            first = d;
+            len += 1;
+        });
+        if first != 0 {
+            self.first_digits[first as usize] += 1;
+        }
+        if len >= 2 {
+            self.first_two_digits[(first * self.radix + second) as usize] += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    #[inline(always)]
+    pub fn all_digits(&self) -> &[u64] {
+        &self.all_digits
+    }
+
+    #[inline(always)]
+    pub fn first_digits(&self) -> &[u64] {
+        &self.first_digits
+    }
+
+    #[inline(always)]
+    pub fn first_two_digits(&self) -> &[u64] {
+        &self.first_two_digits
+    }
+
+    #[inline(always)]
+    pub fn last_digits(&self) -> &[u64] {
+        &self.last_digits
+    }
+
+    /// Conformity of the [first digits][DigitHistogram::first_digits] to Benford's law,
+    /// i.e. to the probabilities `log_radix(1 + 1/d)` for `d` in `1..radix`,
+    /// or [None] if no non-zero integers have been counted.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(any(doc, test, doctest, feature = "std"))]
+    pub fn benford_first_digit_conformity(&self) -> Option<BenfordConformity> {
+        BenfordConformity::new(self.radix, &self.first_digits[1..], 1)
+    }
+
+    /// Conformity of the [first two digits][DigitHistogram::first_two_digits] to Benford's law,
+    /// i.e. to the probabilities `log_radix(1 + 1/d)` for `d` in `radix..radix^2`,
+    /// or [None] if no integers with at least two digits have been counted.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(any(doc, test, doctest, feature = "std"))]
+    pub fn benford_first_two_digits_conformity(&self) -> Option<BenfordConformity> {
+        let radix = self.radix as usize;
+        BenfordConformity::new(self.radix, &self.first_two_digits[radix..], radix)
+    }
+}
+
+impl<T: DigitsInRadix> Extend<T> for DigitHistogram {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for n in iter {
+            self.add(&n);
+        }
+    }
+}
+
+/// Goodness-of-fit statistics of observed leading digits against Benford's law.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(any(doc, test, doctest, feature = "std"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenfordConformity {
+    /// [Pearson's chi-squared statistic](https://en.wikipedia.org/wiki/Pearson%27s_chi-squared_test).
+    pub chi_squared: f64,
+    /// One less than the number of bins.
+    pub degrees_of_freedom: usize,
+    /// Mean absolute deviation of the observed proportions from the expected ones.
+    ///
+    /// For the first digits base 10, Nigrini considers the values below 0.006, 0.012 and 0.015
+    /// close, acceptable and marginally acceptable conformity, respectively.
+    pub mad: f64,
+    /// The total count of observations.
+    pub count: u64,
+}
+
+#[cfg(any(doc, test, doctest, feature = "std"))]
+impl BenfordConformity {
+    // counts[i] is the count of observations of the leading digits forming first_value + i
+    fn new(radix: u32, counts: &[u64], first_value: usize) -> Option<Self> {
+        let count: u64 = counts.iter().sum();
+        if count == 0 {
+            return None;
+        }
+        let ln_radix = f64::from(radix).ln();
+        let (mut chi_squared, mut total_abs_deviation) = (0f64, 0f64);
+        for (i, &observed) in counts.iter().enumerate() {
+            let value = (first_value + i) as f64;
+            let p = (1.0 + 1.0 / value).ln() / ln_radix;
+            let expected = p * count as f64;
+            chi_squared += (observed as f64 - expected).powi(2) / expected;
+            total_abs_deviation += (observed as f64 / count as f64 - p).abs();
+        }
+        Some(Self {
+            chi_squared,
+            degrees_of_freedom: counts.len() - 1,
+            mad: total_abs_deviation / counts.len() as f64,
+            count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::digit_histogram::DigitHistogram;
+
+    #[test]
+    fn digit_histogram_counts_digits_in_all_positions() {
+        let histogram = DigitHistogram::from_iter(10, [1203i32, -45, 0, 7]);
+        assert_eq!(histogram.all_digits(), [2, 1, 1, 1, 1, 1, 0, 1, 0, 0]);
+        assert_eq!(histogram.first_digits(), [0, 1, 0, 0, 1, 0, 0, 1, 0, 0]);
+        assert_eq!(histogram.last_digits(), [1, 0, 0, 1, 0, 1, 0, 1, 0, 0]);
+        let first_two_digits: Vec<(usize, u64)> = histogram
+            .first_two_digits()
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, c)| c > 0)
+            .collect();
+        assert_eq!(first_two_digits, [(12, 1), (45, 1)]);
+    }
+
+    #[test]
+    fn digit_histogram_works_in_other_radices() {
+        use num_bigint::BigUint;
+
+        // 0b1101 and 255 = 0xff
+        let mut histogram = DigitHistogram::new(2);
+        histogram.add(&BigUint::from(13u32));
+        assert_eq!(histogram.all_digits(), [1, 3]);
+        assert_eq!(histogram.first_two_digits(), [0, 0, 0, 1]);
+        let histogram = DigitHistogram::from_iter(16, [255u8]);
+        assert_eq!(histogram.first_digits()[15], 1);
+    }
+
+    #[test]
+    fn benford_conformity_distinguishes_benford_and_uniform_samples() {
+        use num_bigint::BigUint;
+
+        let powers_of_2 = (0..2000u32).map(|k| BigUint::from(2u32).pow(k));
+        let histogram = DigitHistogram::from_iter(10, powers_of_2);
+        let conformity = histogram.benford_first_digit_conformity().unwrap();
+        assert_eq!((conformity.count, conformity.degrees_of_freedom), (2000, 8));
+        assert!(conformity.mad < 0.006);
+        assert!(conformity.chi_squared < 15.51); // 95% quantile for 8 degrees of freedom
+        assert!(histogram.benford_first_two_digits_conformity().unwrap().mad < 0.0022);
+
+        let uniform = DigitHistogram::from_iter(10, 1..10_000u32);
+        let conformity = uniform.benford_first_digit_conformity().unwrap();
+        assert!(conformity.mad > 0.015);
+        assert!(conformity.chi_squared > 15.51);
+
+        assert!(DigitHistogram::from_iter(10, [0u8])
+            .benford_first_digit_conformity()
+            .is_none());
+    }
+}
@@ -0,0 +1,207 @@
+//! Sequences of digits that carry their [order][DigitOrder] and radix in the type.
+//!
+//! Unlike plain [`Vec<u8>`][alloc::vec::Vec] returned by
+//! [NewVecU8OfDigitsBase10Le][crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::NewVecU8OfDigitsBase10Le]
+//! or [CreateVecU8OfDigits][crate::num_sys::CreateVecU8OfDigits], [Digits] of different orders
+//! can't be mixed up accidentally.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::digits::{Be, Digits, Le};
+//!
+//! let le: Digits<Le> = Digits::of_base_10_le(&1230u16);
+//! assert_eq!(le.as_slice(), &[0, 3, 2, 1]);
+//! let be: Digits<Be> = le.into();
+//! assert_eq!(be.as_slice(), &[1, 2, 3, 0]);
+//! assert_eq!(be.to_string(), "1230");
+//! ```
+
+use crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::{
+    NewVecU8OfDigitsBase10Be, NewVecU8OfDigitsBase10Le,
+};
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+/// The order of digits in [Digits], i.e. either [Le] or [Be].
+pub trait DigitOrder {
+    const IS_LITTLE_ENDIAN: bool;
+}
+
+/// [Little endian](https://en.wikipedia.org/wiki/Endianness) order,
+/// i.e. the least significant digit comes first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Le;
+
+/// [Big endian](https://en.wikipedia.org/wiki/Endianness) order,
+/// i.e. the most significant digit comes first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Be;
+
+impl DigitOrder for Le {
+    const IS_LITTLE_ENDIAN: bool = true;
+}
+
+impl DigitOrder for Be {
+    const IS_LITTLE_ENDIAN: bool = false;
+}
+
+/// A non-empty sequence of digits base `RADIX` in the given [DigitOrder].
+///
+/// `RADIX` must be in `2..=36` so that each digit can be displayed as a single
+/// alphanumeric character.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Digits<Order: DigitOrder, const RADIX: u32 = 10> {
+    digits: Vec<u8>,
+    order: PhantomData<Order>,
+}
+
+impl<Order: DigitOrder, const RADIX: u32> Digits<Order, RADIX> {
+    /// Returns [None] if `RADIX` is not in `2..=36`, `digits` is empty,
+    /// or some of the `digits` is not less than `RADIX`.
+    pub fn new(digits: Vec<u8>) -> Option<Self> {
+        let is_valid = (2..=36).contains(&RADIX)
+            && !digits.is_empty()
+            && digits.iter().all(|&d| u32::from(d) < RADIX);
+        if is_valid {
+            Some(Self {
+                digits,
+                order: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.digits
+    }
+
+    #[inline(always)]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.digits
+    }
+
+    #[inline(always)]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    /// Iterates over digits from the most significant to the least significant one
+    /// regardless of the order of storage.
+    pub fn iter_be(&self) -> impl DoubleEndedIterator<Item = u8> + '_ {
+        let len = self.digits.len();
+        (0..len).map(move |i| {
+            if Order::IS_LITTLE_ENDIAN {
+                self.digits[len - 1 - i]
+            } else {
+                self.digits[i]
+            }
+        })
+    }
+
+    fn reversed<Other: DigitOrder>(mut self) -> Digits<Other, RADIX> {
+        self.digits.reverse();
+        Digits {
+            digits: self.digits,
+            order: PhantomData,
+        }
+    }
+}
+
+impl Digits<Le, 10> {
+    /// Digits base 10 of the absolute value of `n`.
+    pub fn of_base_10_le<T: NewVecU8OfDigitsBase10Le>(n: &T) -> Self {
+        Self {
+            digits: n.new_vec_u8_of_digits_le(),
+            order: PhantomData,
+        }
+    }
+}
+
+impl Digits<Be, 10> {
+    /// Digits base 10 of the absolute value of `n`.
+    pub fn of_base_10_be<T: NewVecU8OfDigitsBase10Be>(n: &T) -> Self {
+        Self {
+            digits: n.new_vec_u8_of_digits_be(),
+            order: PhantomData,
+        }
+    }
+}
+
+impl<const RADIX: u32> Digits<Le, RADIX> {
+    #[inline(always)]
+    pub fn into_be(self) -> Digits<Be, RADIX> {
+        self.reversed()
+    }
+}
+
+impl<const RADIX: u32> Digits<Be, RADIX> {
+    #[inline(always)]
+    pub fn into_le(self) -> Digits<Le, RADIX> {
+        self.reversed()
+    }
+}
+
+impl<const RADIX: u32> From<Digits<Le, RADIX>> for Digits<Be, RADIX> {
+    #[inline(always)]
+    fn from(digits: Digits<Le, RADIX>) -> Self {
+        digits.into_be()
+    }
+}
+
+impl<const RADIX: u32> From<Digits<Be, RADIX>> for Digits<Le, RADIX> {
+    #[inline(always)]
+    fn from(digits: Digits<Be, RADIX>) -> Self {
+        digits.into_le()
+    }
+}
+
+/// Displays the digits from the most significant to the least significant one
+/// using `0-9` and `a-z`, as in [core::primitive::u32::from_str_radix].
+impl<Order: DigitOrder, const RADIX: u32> fmt::Display for Digits<Order, RADIX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_digit = |f: &mut fmt::Formatter<'_>, d: &u8| {
+            // the digit is less than RADIX <= 36
+            let c = char::from_digit(u32::from(*d), RADIX).unwrap();
+            fmt::Write::write_char(f, c)
+        };
+        if Order::IS_LITTLE_ENDIAN {
+            self.digits.iter().rev().try_for_each(|d| write_digit(f, d))
+        } else {
+            self.digits.iter().try_for_each(|d| write_digit(f, d))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::digits::{Be, Digits, Le};
+
+    #[test]
+    fn digits_new_rejects_invalid_digits() {
+        assert!(Digits::<Le, 2>::new(vec![1, 0, 1]).is_some());
+        assert!(Digits::<Le, 2>::new(vec![1, 2]).is_none());
+        assert!(Digits::<Be>::new(vec![]).is_none());
+        assert!(Digits::<Be, 37>::new(vec![1]).is_none());
+    }
+
+    #[test]
+    fn digits_conversions_between_orders_round_trip() {
+        let le = Digits::<Le>::of_base_10_le(&-1230i32);
+        let be: Digits<Be> = le.clone().into();
+        assert_eq!(be, Digits::of_base_10_be(&1230u64));
+        assert_eq!(Digits::<Le>::from(be), le);
+    }
+
+    #[test]
+    fn digits_display_is_most_significant_first() {
+        let le = Digits::<Le, 16>::new(vec![15, 0, 10]).unwrap();
+        assert_eq!(le.to_string(), "a0f");
+        assert_eq!(le.into_be().to_string(), "a0f");
+        assert!(Digits::<Le, 16>::new(vec![15, 0, 10])
+            .unwrap()
+            .iter_be()
+            .eq([10, 0, 15]));
+    }
+}
@@ -0,0 +1,516 @@
+//! [Divisibility rules](https://en.wikipedia.org/wiki/Divisibility_rule) base 10 that reduce an integer
+//! to a number with a few digits using its digits alone.
+//!
+//! A divisor `d` is split into the factor `2^a * 5^b`, which divides `10^max(a, b)`, and the factor coprime
+//! with 10. The former is decided by the last `max(a, b)` digits. For the latter, the smallest `k` such that
+//! `10^k ≡ ±1 (mod d)` is found, and the number is repeatedly replaced by the sum (for `+1`) or the absolute value
+//! of the alternating sum (for `-1`) of its groups of `k` digits until it has at most `k` digits. This gives
+//! the familiar rules for 3 and 9 (digit sum), 11 (alternating digit sum), 7 and 13 (alternating sum of triplets,
+//! as `1001 = 7 * 11 * 13`) and 37 (sum of triplets, as `999 = 27 * 37`).
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::divisibility::{DivisibilityRule, DivisibleByDigitRules};
+//!
+//! let (is_divisible, steps) = 123_456_789u32.divisibility_steps(7).unwrap();
+//! assert!(!is_divisible);
+//! // 789 - 456 + 123 = 456, which is not divisible by 7
+//! assert_eq!(steps[0].rule, DivisibilityRule::AlternatingDigitGroupSum { group_len: 3 });
+//! assert_eq!(steps[0].value, 456);
+//! ```
+//!
+//! For signed integers, the rules are applied to the absolute value.
+
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::vec::Vec;
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint};
+
+/// The longest group of digits that the rules for the factors coprime with 10 may use.
+pub const MAX_GROUP_LEN: u32 = 32;
+
+/// A digit-based rule that replaces a number with a smaller one, preserving its divisibility by a factor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DivisibilityRule {
+    /// The factor divides `10^count`, so only the last `count` digits matter.
+    LastDigits { count: u32 },
+    /// The factor divides `10^group_len - 1`, so the number can be replaced by the sum
+    /// of its groups of `group_len` digits. For `group_len == 1`, it is the sum of digits.
+    DigitGroupSum { group_len: u32 },
+    /// The factor divides `10^group_len + 1`, so the number can be replaced by the absolute value
+    /// of the alternating sum of its groups of `group_len` digits.
+    AlternatingDigitGroupSum { group_len: u32 },
+}
+
+/// The rules deciding the divisibility by a divisor, one per factor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DivisibilityRules {
+    /// The factor `2^a * 5^b` of the divisor and the [LastDigits][DivisibilityRule::LastDigits] rule for it,
+    /// unless the factor is 1.
+    pub last_digits: Option<(u64, DivisibilityRule)>,
+    /// The factor of the divisor coprime with 10 and the digit group rule for it, unless the factor is 1.
+    pub digit_groups: Option<(u64, DivisibilityRule)>,
+}
+
+impl DivisibilityRules {
+    /// Returns the rules for `divisor` or [None] if `divisor` is 0 or its factor coprime with 10
+    /// needs groups longer than [MAX_GROUP_LEN] digits.
+    pub fn new(divisor: u64) -> Option<Self> {
+        if divisor == 0 {
+            return None;
+        }
+        let (twos, fives) = (divisor.trailing_zeros(), {
+            let mut fives = 0u32;
+            let mut q = divisor >> divisor.trailing_zeros();
+            while q.is_multiple_of(5) {
+                q /= 5;
+                fives += 1;
+            }
+            fives
+        });
+        let coprime_factor = (divisor >> twos) / 5u64.pow(fives);
+        let last_digits = (coprime_factor != divisor).then(|| {
+            let count = twos.max(fives);
+            (
+                divisor / coprime_factor,
+                DivisibilityRule::LastDigits { count },
+            )
+        });
+        let digit_groups = if coprime_factor == 1 {
+            None
+        } else {
+            let d = u128::from(coprime_factor);
+            let mut r = 1u128;
+            let rule = (1..=MAX_GROUP_LEN).find_map(|group_len| {
+                r = r * 10 % d;
+                if r == 1 {
+                    Some(DivisibilityRule::DigitGroupSum { group_len })
+                } else if r == d - 1 {
+                    Some(DivisibilityRule::AlternatingDigitGroupSum { group_len })
+                } else {
+                    None
+                }
+            })?;
+            Some((coprime_factor, rule))
+        };
+        Some(Self {
+            last_digits,
+            digit_groups,
+        })
+    }
+}
+
+/// The result of applying a [DivisibilityRule] for a factor of the divisor.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DivisibilityStep<T> {
+    pub factor: u64,
+    pub rule: DivisibilityRule,
+    pub value: T,
+}
+
+/// The trait for integers whose divisibility can be decided with [digit rules][crate::num_sys::divisibility].
+pub trait DivisibleByDigitRules {
+    /// The type of the absolute value, which the rules are applied to.
+    type Magnitude;
+
+    /// Decides whether `self` is divisible by `divisor`, calling `on_step` with every step that
+    /// changes the number, or returns [None] if there are no [rules][DivisibilityRules::new] for `divisor`.
+    ///
+    /// After the last step for a factor, the number has at most as many digits as the rule
+    /// considers at once and its divisibility by the factor is looked up with `%`.
+    fn is_divisible_by_digit_rules_with<F: FnMut(&DivisibilityStep<Self::Magnitude>)>(
+        &self,
+        divisor: u64,
+        on_step: F,
+    ) -> Option<bool>;
+
+    #[inline(always)]
+    fn is_divisible_by_digit_rules(&self, divisor: u64) -> Option<bool> {
+        self.is_divisible_by_digit_rules_with(divisor, |_| {})
+    }
+
+    /// Same as [is_divisible_by_digit_rules_with][DivisibleByDigitRules::is_divisible_by_digit_rules_with]
+    /// but collects the steps.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn divisibility_steps(
+        &self,
+        divisor: u64,
+    ) -> Option<(bool, Vec<DivisibilityStep<Self::Magnitude>>)>
+    where
+        Self::Magnitude: Clone,
+    {
+        let mut steps = Vec::new();
+        let is_divisible =
+            self.is_divisible_by_digit_rules_with(divisor, |step| steps.push(step.clone()))?;
+        Some((is_divisible, steps))
+    }
+}
+
+// Arithmetic on groups of digits base 10 of the magnitudes
+trait DigitGroups: Sized + Clone {
+    fn has_at_most_digits(&self, count: u32) -> bool;
+    // Requires `!self.has_at_most_digits(count)`
+    fn last_digits(&self, count: u32) -> Self;
+    // Requires `!self.has_at_most_digits(group_len)`
+    fn digit_group_sum(&self, group_len: u32, alternating: bool) -> Self;
+    fn is_multiple_of_factor(&self, factor: u64) -> bool;
+}
+
+impl DigitGroups for u128 {
+    #[inline]
+    fn has_at_most_digits(&self, count: u32) -> bool {
+        !matches!(10u128.checked_pow(count), Some(p) if *self >= p)
+    }
+
+    #[inline]
+    fn last_digits(&self, count: u32) -> Self {
+        self % 10u128.pow(count)
+    }
+
+    fn digit_group_sum(&self, group_len: u32, alternating: bool) -> Self {
+        let p = 10u128.pow(group_len);
+        let (mut q, mut sums, mut i) = (*self, [0u128; 2], 0);
+        while q != 0 {
+            // the sums do not exceed the number
+            sums[i % 2] += q % p;
+            q /= p;
+            i += 1;
+        }
+        if alternating {
+            sums[0].abs_diff(sums[1])
+        } else {
+            sums[0] + sums[1]
+        }
+    }
+
+    #[inline]
+    fn is_multiple_of_factor(&self, factor: u64) -> bool {
+        self.is_multiple_of(u128::from(factor))
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl DigitGroups for BigUint {
+    fn has_at_most_digits(&self, count: u32) -> bool {
+        *self < BigUint::from(10u32).pow(count)
+    }
+
+    fn last_digits(&self, count: u32) -> Self {
+        self % BigUint::from(10u32).pow(count)
+    }
+
+    fn digit_group_sum(&self, group_len: u32, alternating: bool) -> Self {
+        use num_traits::Zero;
+
+        let p = BigUint::from(10u32).pow(group_len);
+        let (mut q, mut sums, mut i) = (self.clone(), [BigUint::zero(), BigUint::zero()], 0);
+        while !q.is_zero() {
+            sums[i % 2] += &q % &p;
+            q /= &p;
+            i += 1;
+        }
+        let [even, odd] = sums;
+        match (alternating, even >= odd) {
+            (false, _) => even + odd,
+            (true, true) => even - odd,
+            (true, false) => odd - even,
+        }
+    }
+
+    fn is_multiple_of_factor(&self, factor: u64) -> bool {
+        use num_traits::Zero;
+
+        (self % factor).is_zero()
+    }
+}
+
+fn is_divisible_by_digit_rules<M: DigitGroups, F: FnMut(&DivisibilityStep<M>)>(
+    n: M,
+    divisor: u64,
+    mut on_step: F,
+) -> Option<bool> {
+    let rules = DivisibilityRules::new(divisor)?;
+    for (factor, rule) in [rules.last_digits, rules.digit_groups]
+        .into_iter()
+        .flatten()
+    {
+        let mut value = n.clone();
+        match rule {
+            DivisibilityRule::LastDigits { count } => {
+                if !value.has_at_most_digits(count) {
+                    value = value.last_digits(count);
+                    on_step(&DivisibilityStep {
+                        factor,
+                        rule,
+                        value: value.clone(),
+                    });
+                }
+            }
+            DivisibilityRule::DigitGroupSum { group_len }
+            | DivisibilityRule::AlternatingDigitGroupSum { group_len } => {
+                let alternating = matches!(rule, DivisibilityRule::AlternatingDigitGroupSum { .. });
+                while !value.has_at_most_digits(group_len) {
+                    value = value.digit_group_sum(group_len, alternating);
+                    on_step(&DivisibilityStep {
+                        factor,
+                        rule,
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+        if !value.is_multiple_of_factor(factor) {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+macro_rules! impl_divisible_by_digit_rules {
+    ($($t:ty => |$n:ident| $magnitude:expr;)+) => {
+        $(
+            impl DivisibleByDigitRules for $t {
+                type Magnitude = u128;
+
+                #[inline]
+                fn is_divisible_by_digit_rules_with<F: FnMut(&DivisibilityStep<u128>)>(
+                    &self,
+                    divisor: u64,
+                    on_step: F,
+                ) -> Option<bool> {
+                    let $n = *self;
+                    is_divisible_by_digit_rules($magnitude, divisor, on_step)
+                }
+            }
+        )+
+    };
+}
+
+impl_divisible_by_digit_rules!(
+    u8 => |n| u128::from(n);
+    u16 => |n| u128::from(n);
+    u32 => |n| u128::from(n);
+    u64 => |n| u128::from(n);
+    u128 => |n| n;
+    usize => |n| n as u128;
+    i8 => |n| u128::from(n.unsigned_abs());
+    i16 => |n| u128::from(n.unsigned_abs());
+    i32 => |n| u128::from(n.unsigned_abs());
+    i64 => |n| u128::from(n.unsigned_abs());
+    i128 => |n| n.unsigned_abs();
+    isize => |n| n.unsigned_abs() as u128;
+);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl DivisibleByDigitRules for BigUint {
+    type Magnitude = BigUint;
+
+    fn is_divisible_by_digit_rules_with<F: FnMut(&DivisibilityStep<BigUint>)>(
+        &self,
+        divisor: u64,
+        on_step: F,
+    ) -> Option<bool> {
+        is_divisible_by_digit_rules(self.clone(), divisor, on_step)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl DivisibleByDigitRules for BigInt {
+    type Magnitude = BigUint;
+
+    #[inline(always)]
+    fn is_divisible_by_digit_rules_with<F: FnMut(&DivisibilityStep<BigUint>)>(
+        &self,
+        divisor: u64,
+        on_step: F,
+    ) -> Option<bool> {
+        self.magnitude()
+            .is_divisible_by_digit_rules_with(divisor, on_step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::divisibility::*;
+
+    // a deterministic stream of integers spread over all magnitudes
+    fn samples() -> impl Iterator<Item = u64> {
+        (0..2_000u64).map(|i| i.wrapping_mul(11_400_714_819_323_198_485) >> (i % 64))
+    }
+
+    #[test]
+    fn divisibility_rules_match_the_familiar_ones() {
+        use DivisibilityRule::*;
+
+        let groups = |d| DivisibilityRules::new(d).unwrap().digit_groups.unwrap().1;
+        assert_eq!(groups(3), DigitGroupSum { group_len: 1 });
+        assert_eq!(groups(9), DigitGroupSum { group_len: 1 });
+        assert_eq!(groups(11), AlternatingDigitGroupSum { group_len: 1 });
+        assert_eq!(groups(7), AlternatingDigitGroupSum { group_len: 3 });
+        assert_eq!(groups(13), AlternatingDigitGroupSum { group_len: 3 });
+        assert_eq!(groups(37), DigitGroupSum { group_len: 3 });
+        assert_eq!(
+            DivisibilityRules::new(40).unwrap(),
+            DivisibilityRules {
+                last_digits: Some((40, LastDigits { count: 3 })),
+                digit_groups: None,
+            }
+        );
+        assert_eq!(DivisibilityRules::new(0), None);
+    }
+
+    #[test]
+    fn digit_rules_agree_with_rem_for_primitive_integers() {
+        for divisor in 1..=200u64 {
+            let has_rules = DivisibilityRules::new(divisor).is_some();
+            for n in samples() {
+                let expected = n % divisor == 0;
+                assert_eq!(
+                    n.is_divisible_by_digit_rules(divisor),
+                    has_rules.then_some(expected)
+                );
+                assert_eq!(
+                    (n as i64)
+                        .wrapping_neg()
+                        .is_divisible_by_digit_rules(divisor),
+                    has_rules.then(|| (n as i64).unsigned_abs() % divisor == 0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn digit_rules_agree_with_rem_for_big_integers() {
+        use num_bigint::{BigInt, BigUint};
+        use num_traits::Zero;
+
+        for divisor in [2u64, 3, 7, 11, 13, 16, 37, 99, 101, 1001, 10001] {
+            for (i, n) in samples().enumerate().take(200) {
+                let n = BigUint::from(n).pow(1 + i as u32 % 5) * (1 + (i as u64 % 3) * divisor);
+                assert_eq!(
+                    n.is_divisible_by_digit_rules(divisor),
+                    Some((&n % divisor).is_zero())
+                );
+                assert_eq!(
+                    BigInt::from(n.clone()).is_divisible_by_digit_rules(divisor),
+                    Some((&n % divisor).is_zero())
+                );
+            }
+        }
+    }
+
+    // xorshift for the reproducible pseudorandom integers
+    fn random(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    // a pseudorandom integer of a pseudorandom bit length
+    fn random_u128(state: &mut u64) -> u128 {
+        let n = u128::from(random(state)) << 64 | u128::from(random(state));
+        n >> (random(state) % 128)
+    }
+
+    // a pseudorandom divisor with the digit rules, i.e. a factor of 10^k ± 1 times 2^a * 5^b,
+    // or an arbitrary one, which may have no rules
+    fn random_divisor(state: &mut u64) -> u64 {
+        fn gcd(mut a: u128, mut b: u128) -> u128 {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        }
+
+        loop {
+            if random(state) % 4 == 0 {
+                return random(state) >> (random(state) % 64);
+            }
+            let k = 1 + (random(state) % u64::from(MAX_GROUP_LEN)) as u32;
+            let m = if random(state) % 2 == 0 {
+                10u128.pow(k) - 1
+            } else {
+                10u128.pow(k) + 1
+            };
+            let g = gcd(random_u128(state), m);
+            let g = if random(state) % 2 == 0 { g } else { m / g };
+            let scale =
+                2u128.pow((random(state) % 12) as u32) * 5u128.pow((random(state) % 6) as u32);
+            if let Some(d) = g.checked_mul(scale).and_then(|d| u64::try_from(d).ok()) {
+                return d;
+            }
+        }
+    }
+
+    #[test]
+    fn digit_rules_agree_with_rem_for_random_integers() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..20_000 {
+            let divisor = random_divisor(&mut state);
+            let rules = DivisibilityRules::new(divisor);
+            let d = u128::from(divisor);
+            // the multiples of the divisor and their neighbors as well as the arbitrary integers
+            let n = match random(&mut state) % 3 {
+                0 => random_u128(&mut state),
+                offset => (random_u128(&mut state) / d.max(1) * d).wrapping_add(offset as u128 - 1),
+            };
+            let expected = |n: u128| rules.is_some().then(|| divisor != 0 && n % d == 0);
+            assert_eq!(n.is_divisible_by_digit_rules(divisor), expected(n));
+            assert_eq!(
+                (n as u64).is_divisible_by_digit_rules(divisor),
+                expected(u128::from(n as u64))
+            );
+            let signed = if random(&mut state) % 100 == 0 {
+                i128::MIN
+            } else {
+                n as i128
+            };
+            assert_eq!(
+                signed.is_divisible_by_digit_rules(divisor),
+                expected(signed.unsigned_abs())
+            );
+        }
+    }
+
+    #[test]
+    fn digit_rules_agree_with_rem_near_limb_boundaries() {
+        use num_bigint::{BigInt, BigUint};
+        use num_traits::{One, Zero};
+
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..1_000 {
+            let divisor = random_divisor(&mut state);
+            let has_rules = DivisibilityRules::new(divisor).is_some();
+            // 2^(64 * limbs) with a small offset, possibly rounded down to a multiple of the divisor
+            let boundary = BigUint::one() << (64 * (1 + random(&mut state) % 6));
+            let offset = BigUint::from(random(&mut state) % 3);
+            let n = match random(&mut state) % 3 {
+                0 => &boundary + offset,
+                1 => &boundary - 1u8 - offset,
+                _ => &boundary / divisor.max(1) * divisor + offset,
+            };
+            let expected = has_rules.then(|| (&n % divisor.max(1)).is_zero());
+            assert_eq!(n.is_divisible_by_digit_rules(divisor), expected);
+            assert_eq!(
+                (-BigInt::from(n.clone())).is_divisible_by_digit_rules(divisor),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn divisibility_steps_report_intermediate_values() {
+        // 9 + 9 + 9 + 9 = 36, 3 + 6 = 9
+        let (is_divisible, steps) = 9999u16.divisibility_steps(9).unwrap();
+        assert!(is_divisible);
+        let values: Vec<u128> = steps.iter().map(|step| step.value).collect();
+        assert_eq!(values, [36, 9]);
+        // 12 = 4 * 3: the last two digits 36 and then 3 + 6 = 9
+        let (is_divisible, steps) = (-1236i32).divisibility_steps(12).unwrap();
+        assert!(is_divisible);
+        let steps: Vec<(u64, u128)> = steps.iter().map(|s| (s.factor, s.value)).collect();
+        assert_eq!(steps, [(4, 36), (3, 12), (3, 3)]);
+    }
+}
@@ -0,0 +1,224 @@
+//! The [factorial number system](https://en.wikipedia.org/wiki/Factorial_number_system) and
+//! the ranking of permutations in lexicographic order with their [Lehmer codes](https://en.wikipedia.org/wiki/Lehmer_code).
+//!
+//! The digit at position `i` from the least significant one has the radix `i + 1`, so it is less than
+//! or equal to `i` and the place value is `i!`. The Lehmer code of a permutation of `0..n` is
+//! the factoradic representation of its rank from the most significant digit.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::factoradic::{rank_permutation, unrank_permutation, Factoradic};
+//!
+//! // 463 = 3 * 5! + 4 * 4! + 1 * 3! + 0 * 2! + 1 * 1! + 0 * 0!
+//! let digits: Vec<u32> = 463u32.factoradic_digits_le().unwrap().collect();
+//! assert_eq!(digits, [0, 1, 0, 1, 4, 3]);
+//!
+//! let mut permutation = [0; 4];
+//! unrank_permutation(&10u8, &mut permutation).unwrap();
+//! assert_eq!(permutation, [1, 3, 0, 2]);
+//! assert_eq!(rank_permutation::<u8>(&permutation), Some(10));
+//! ```
+
+use crate::num_sys::sign_magnitude::{Magnitude, SignMagnitude};
+
+/// The iterator over the factoradic digits from the least significant one.
+/// Zero has the single digit 0.
+#[derive(Clone, Debug)]
+pub struct FactoradicDigitsLe<M> {
+    magnitude: M,
+    radix: u32,
+    is_done: bool,
+}
+
+impl<M: Magnitude> Iterator for FactoradicDigitsLe<M> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.is_done {
+            return None;
+        }
+        let digit = self.magnitude.div_rem_assign_u32(self.radix);
+        self.radix += 1;
+        self.is_done = self.magnitude.is_zero();
+        Some(digit)
+    }
+}
+
+pub trait Factoradic: Sized {
+    type Magnitude: Magnitude;
+
+    /// Returns [None] for negative integers.
+    fn factoradic_digits_le(&self) -> Option<FactoradicDigitsLe<Self::Magnitude>>;
+
+    /// Returns [None] if the digit at position `i` exceeds `i`
+    /// or the value is not representable by `Self`.
+    fn from_factoradic_digits_le(digits: &[u32]) -> Option<Self>;
+}
+
+impl<T: SignMagnitude> Factoradic for T {
+    type Magnitude = T::Magnitude;
+
+    fn factoradic_digits_le(&self) -> Option<FactoradicDigitsLe<T::Magnitude>> {
+        match self.to_sign_magnitude() {
+            (true, magnitude) if !magnitude.is_zero() => None,
+            (_, magnitude) => Some(FactoradicDigitsLe {
+                magnitude,
+                radix: 1,
+                is_done: false,
+            }),
+        }
+    }
+
+    fn from_factoradic_digits_le(digits: &[u32]) -> Option<Self> {
+        let mut magnitude = T::Magnitude::from_u32(0);
+        for (i, &d) in digits.iter().enumerate().rev() {
+            let radix = u32::try_from(i + 1).ok()?;
+            if d >= radix {
+                return None;
+            }
+            magnitude = magnitude.checked_mul_add_u32(radix, d)?;
+        }
+        T::from_sign_magnitude(false, magnitude)
+    }
+}
+
+/// Writes the Lehmer code of `permutation` into the beginning of `code`, i.e. the number of the later elements
+/// less than the element for every element, or returns [None] if `permutation` is not a permutation of
+/// `0..permutation.len()` or `code` is too short.
+///
+/// The time complexity is quadratic, but nothing is allocated.
+pub fn lehmer_code(permutation: &[usize], code: &mut [usize]) -> Option<()> {
+    if code.len() < permutation.len() || !is_permutation(permutation) {
+        return None;
+    }
+    for (i, (c, &p)) in code.iter_mut().zip(permutation).enumerate() {
+        *c = permutation[i + 1..].iter().filter(|&&q| q < p).count();
+    }
+    Some(())
+}
+
+/// Writes the permutation of `0..code.len()` with the given Lehmer code into the beginning of `permutation`
+/// or returns [None] if the code at position `i` is not less than `code.len() - i` or `permutation` is too short.
+pub fn permutation_from_lehmer_code(code: &[usize], permutation: &mut [usize]) -> Option<()> {
+    let n = code.len();
+    let permutation = permutation.get_mut(..n)?;
+    if code.iter().enumerate().any(|(i, &c)| c >= n - i) {
+        return None;
+    }
+    permutation.copy_from_slice(code);
+    decode_lehmer_code_in_place(permutation);
+    Some(())
+}
+
+// Turns the Lehmer code into the permutation: the suffix from `i + 1` is a permutation of the values other than
+// the one at `i`, so the values not less than it are incremented
+fn decode_lehmer_code_in_place(code: &mut [usize]) {
+    for i in (0..code.len()).rev() {
+        let (head, tail) = code.split_at_mut(i + 1);
+        for q in tail {
+            if *q >= head[i] {
+                *q += 1;
+            }
+        }
+    }
+}
+
+fn is_permutation(permutation: &[usize]) -> bool {
+    let n = permutation.len();
+    permutation
+        .iter()
+        .enumerate()
+        .all(|(i, &p)| p < n && !permutation[..i].contains(&p))
+}
+
+/// Returns the rank of `permutation` among the permutations of `0..permutation.len()` in lexicographic order
+/// or [None] if it is not such a permutation or the rank is not representable by `T`.
+pub fn rank_permutation<T: SignMagnitude>(permutation: &[usize]) -> Option<T> {
+    if !is_permutation(permutation) {
+        return None;
+    }
+    let n = permutation.len();
+    let mut magnitude = T::Magnitude::from_u32(0);
+    for (i, &p) in permutation.iter().enumerate() {
+        // the Lehmer code digit at position i, whose radix is n - i
+        let digit = permutation[i + 1..].iter().filter(|&&q| q < p).count();
+        magnitude = magnitude.checked_mul_add_u32(u32::try_from(n - i).ok()?, digit as u32)?;
+    }
+    T::from_sign_magnitude(false, magnitude)
+}
+
+/// Writes the permutation of `0..permutation.len()` with the given rank in lexicographic order into `permutation`
+/// or returns [None] if `rank` is negative or not less than `permutation.len()!`.
+pub fn unrank_permutation<T: SignMagnitude>(rank: &T, permutation: &mut [usize]) -> Option<()> {
+    let (is_negative, mut magnitude) = rank.to_sign_magnitude();
+    if is_negative && !magnitude.is_zero() {
+        return None;
+    }
+    let n = permutation.len();
+    for (i, p) in permutation.iter_mut().enumerate().rev() {
+        *p = magnitude.div_rem_assign_u32(u32::try_from(n - i).ok()?) as usize;
+    }
+    if !magnitude.is_zero() {
+        return None;
+    }
+    decode_lehmer_code_in_place(permutation);
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::factoradic::*;
+
+    #[test]
+    fn factoradic_representations_round_trip() {
+        for n in 0u32..=5040 {
+            let digits: Vec<u32> = n.factoradic_digits_le().unwrap().collect();
+            assert!(digits.iter().enumerate().all(|(i, &d)| d as usize <= i));
+            assert_eq!(u32::from_factoradic_digits_le(&digits), Some(n));
+        }
+        // 7! = 5040
+        let digits: Vec<u32> = 5040u16.factoradic_digits_le().unwrap().collect();
+        assert_eq!(digits, [0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(u32::from_factoradic_digits_le(&[0, 2]), None);
+        assert!((-1i8).factoradic_digits_le().is_none());
+
+        use num_bigint::BigUint;
+
+        let n = BigUint::from(3u32).pow(300);
+        let digits: Vec<u32> = n.factoradic_digits_le().unwrap().collect();
+        assert_eq!(BigUint::from_factoradic_digits_le(&digits), Some(n));
+    }
+
+    #[test]
+    fn permutations_are_ranked_in_lexicographic_order() {
+        let mut permutation = [0; 5];
+        let mut code = [0; 5];
+        let mut decoded = [0; 5];
+        let mut previous: Option<[usize; 5]> = None;
+        for rank in 0u8..120 {
+            unrank_permutation(&rank, &mut permutation).unwrap();
+            assert!(previous < Some(permutation));
+            previous = Some(permutation);
+            assert_eq!(rank_permutation::<u8>(&permutation), Some(rank));
+            lehmer_code(&permutation, &mut code).unwrap();
+            permutation_from_lehmer_code(&code, &mut decoded).unwrap();
+            assert_eq!(decoded, permutation);
+        }
+        assert_eq!(unrank_permutation(&120u8, &mut permutation), None);
+        assert_eq!(rank_permutation::<u8>(&[0, 0, 1]), None);
+        assert_eq!(rank_permutation::<u8>(&[0, 3, 1]), None);
+        assert_eq!(permutation_from_lehmer_code(&[0, 1], &mut decoded), None);
+        // 6! = 720 does not fit into u8
+        assert_eq!(rank_permutation::<u8>(&[5, 4, 3, 2, 1, 0]), None);
+        assert_eq!(rank_permutation::<u16>(&[5, 4, 3, 2, 1, 0]), Some(719));
+    }
+
+    #[test]
+    fn permutations_of_many_elements_are_ranked_with_big_integers() {
+        use num_bigint::BigUint;
+
+        let mut permutation = [0; 40];
+        let rank = BigUint::from(7u32).pow(50);
+        unrank_permutation(&rank, &mut permutation).unwrap();
+        assert_eq!(rank_permutation::<BigUint>(&permutation), Some(rank));
+    }
+}
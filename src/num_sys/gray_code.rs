@@ -0,0 +1,60 @@
+//! The [binary-reflected Gray code](https://en.wikipedia.org/wiki/Gray_code), in which the codes of
+//! consecutive integers differ in exactly one bit.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::gray_code::GrayCode;
+//!
+//! assert_eq!(5u8.gray_encode(), 0b111);
+//! assert_eq!(0b111u8.gray_decode(), 5);
+//! ```
+
+pub trait GrayCode: Sized {
+    fn gray_encode(self) -> Self;
+    fn gray_decode(self) -> Self;
+}
+
+macro_rules! impl_gray_code {
+    ($($t:ty),+) => {
+        $(
+            impl GrayCode for $t {
+                #[inline(always)]
+                fn gray_encode(self) -> Self {
+                    self ^ (self >> 1)
+                }
+
+                #[inline]
+                fn gray_decode(self) -> Self {
+                    // every bit is the XOR of the bits of the code from it up,
+                    // which is computed with the prefix XOR in log2(BITS) steps
+                    let mut n = self;
+                    let mut shift = 1;
+                    while shift < <$t>::BITS {
+                        n ^= n >> shift;
+                        shift <<= 1;
+                    }
+                    n
+                }
+            }
+        )+
+    };
+}
+
+impl_gray_code!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::gray_code::GrayCode;
+
+    #[test]
+    fn consecutive_gray_codes_differ_in_one_bit() {
+        for n in 0u16..u16::MAX {
+            assert_eq!((n.gray_encode() ^ (n + 1).gray_encode()).count_ones(), 1);
+            assert_eq!(n.gray_encode().gray_decode(), n);
+        }
+        for n in [0u128, 1, u128::MAX, u128::MAX / 3, 1 << 127] {
+            assert_eq!(n.gray_encode().gray_decode(), n);
+            assert_eq!(n.gray_decode().gray_encode(), n);
+        }
+        assert_eq!(u8::MAX.gray_encode(), 0b1000_0000);
+    }
+}
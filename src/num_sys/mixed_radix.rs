@@ -0,0 +1,98 @@
+//! [Mixed radix](https://en.wikipedia.org/wiki/Mixed_radix) numeral systems, where every position
+//! has its own radix, as in the units of time.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::mixed_radix::MixedRadix;
+//!
+//! // seconds, minutes and hours, with the days leading
+//! let radices = [60, 60, 24];
+//! let mut digits = [0; 3];
+//! let days = 200_000u32.to_mixed_radix_le(&radices, &mut digits).unwrap();
+//! assert_eq!((days, digits), (2, [20, 33, 7]));
+//! assert_eq!(u32::from_mixed_radix_le(days, &digits, &radices), Some(200_000));
+//! ```
+
+use crate::num_sys::sign_magnitude::{Magnitude, SignMagnitude};
+
+/// Conversions to and from mixed radix representations, given by the radices from the least
+/// significant position, and the unbounded leading digit of type `Self`.
+///
+/// # Panics
+///
+/// The methods panic if a radix is 0.
+pub trait MixedRadix: Sized {
+    /// Writes the digits into the beginning of `digits` and returns the leading digit
+    /// or [None] if `self` is negative or `digits` is shorter than `radices`.
+    fn to_mixed_radix_le(&self, radices: &[u32], digits: &mut [u32]) -> Option<Self>;
+
+    /// Returns [None] if `leading` is negative, the lengths of `digits` and `radices` differ,
+    /// a digit is not less than its radix or the value is not representable by `Self`.
+    fn from_mixed_radix_le(leading: Self, digits: &[u32], radices: &[u32]) -> Option<Self>;
+}
+
+impl<T: SignMagnitude> MixedRadix for T {
+    fn to_mixed_radix_le(&self, radices: &[u32], digits: &mut [u32]) -> Option<Self> {
+        let (is_negative, mut magnitude) = self.to_sign_magnitude();
+        if (is_negative && !magnitude.is_zero()) || digits.len() < radices.len() {
+            return None;
+        }
+        for (d, &radix) in digits.iter_mut().zip(radices) {
+            assert!(radix != 0, "The radices must be positive");
+            *d = magnitude.div_rem_assign_u32(radix);
+        }
+        T::from_sign_magnitude(false, magnitude)
+    }
+
+    fn from_mixed_radix_le(leading: Self, digits: &[u32], radices: &[u32]) -> Option<Self> {
+        let (is_negative, mut magnitude) = leading.to_sign_magnitude();
+        if (is_negative && !magnitude.is_zero()) || digits.len() != radices.len() {
+            return None;
+        }
+        for (&d, &radix) in digits.iter().zip(radices).rev() {
+            assert!(radix != 0, "The radices must be positive");
+            if d >= radix {
+                return None;
+            }
+            magnitude = magnitude.checked_mul_add_u32(radix, d)?;
+        }
+        T::from_sign_magnitude(false, magnitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::mixed_radix::MixedRadix;
+
+    #[test]
+    fn mixed_radix_representations_round_trip() {
+        let radices = [7, 1, 10, 3, 256];
+        let mut digits = [0; 5];
+        for n in (0..1_000_000u64).step_by(997) {
+            let leading = n.to_mixed_radix_le(&radices, &mut digits).unwrap();
+            assert!(digits.iter().zip(&radices).all(|(d, r)| d < r));
+            assert_eq!(
+                u64::from_mixed_radix_le(leading, &digits, &radices),
+                Some(n)
+            );
+        }
+        assert_eq!((-1i32).to_mixed_radix_le(&radices, &mut digits), None);
+        assert_eq!(0u8.to_mixed_radix_le(&radices, &mut [0; 4]), None);
+        assert_eq!(u8::from_mixed_radix_le(0, &[7, 0, 0, 0, 0], &radices), None);
+        assert_eq!(u8::from_mixed_radix_le(1, &[0; 5], &radices), None);
+        assert_eq!(u8::from_mixed_radix_le(5, &[], &[]), Some(5));
+    }
+
+    #[test]
+    fn mixed_radix_representations_round_trip_for_big_integers() {
+        use num_bigint::BigUint;
+
+        let radices = [u32::MAX, 2, 1_000_000_007];
+        let mut digits = [0; 3];
+        let n = BigUint::from(10u32).pow(50) + 12_345u32;
+        let leading = n.to_mixed_radix_le(&radices, &mut digits).unwrap();
+        assert_eq!(
+            BigUint::from_mixed_radix_le(leading, &digits, &radices),
+            Some(n)
+        );
+    }
+}
@@ -9,6 +9,12 @@ pub trait GetLastDigitAsU8 {
     fn get_last_digit_as_u8(&self) -> u8;
 }
 
+/// The trait that indicates that the implementer has an upper bound on the length of its
+/// canonical representation base 10 (including the minus sign, if any) that fits into [core::primitive::usize].
+///
+/// * For primitive integers, it is the length of the longest representation among all values of the type.
+/// * For [big integers](https://en.wikipedia.org/wiki/Arbitrary-precision_arithmetic), it is an upper bound
+///   on the length of the given value, computed from its number of bits.
 pub trait GetMaxLenBase10AsUsize {
     fn get_max_len_base_10_as_usize(&self) -> usize;
 }
@@ -19,12 +25,40 @@ pub trait GetMaxLenBase10AsUsize {
 /// The trait itself may not specify the order of digits. However,
 /// 1. for primitive integers it is much simpler first to obtain least significant digits;
 /// 2. [big integers](https://en.wikipedia.org/wiki/Arbitrary-precision_arithmetic) are commonly stored as
-/// [little endian](https://en.wikipedia.org/wiki/Endianness) sequences of primitive unsigned integers
+///    [little endian](https://en.wikipedia.org/wiki/Endianness) sequences of primitive unsigned integers
+///
+/// The trait does not specify whether the length of provided vector matches its capacity.
 ///
-/// The trait does not specify whether the length of provided vector matches its capacity
-#[cfg(any(doc, test, doctest, feature = "std"))]
+/// The implementations provided by this crate for integers return the digits base 10 of the absolute value
+/// in little endian order, i.e. the same digits as
+/// [NewVecU8OfDigitsBase10Le][crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::NewVecU8OfDigitsBase10Le].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
 pub trait CreateVecU8OfDigits {
-    fn create_vec_u8_of_digits(&self) -> std::vec::Vec<u8>;
+    fn create_vec_u8_of_digits(&self) -> alloc::vec::Vec<u8>;
 }
 
+pub mod balanced_ternary;
 pub mod base_10;
+pub mod batch;
+pub mod bcd;
+pub mod bijective;
+pub mod check_digits;
+pub mod consts;
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+pub mod digit_histogram;
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+pub mod digits;
+pub mod divisibility;
+pub mod factoradic;
+pub mod gray_code;
+pub mod mixed_radix;
+pub mod negabase;
+pub mod primorial;
+pub mod rational_expansion;
+pub mod roman;
+pub mod sign_magnitude;
+pub mod words;
+pub mod zeckendorf;
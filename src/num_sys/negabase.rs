@@ -0,0 +1,174 @@
+//! Positional numeral systems with [negative bases](https://en.wikipedia.org/wiki/Negative_base) `-k`,
+//! which represent negative integers without a sign.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::negabase::Negabase;
+//!
+//! // 6 = 16 - 8 - 2 in base -2
+//! assert_eq!(6u8.to_negabase_string(2), "11010");
+//! // 19 = 100 - 90 + 9 and -19 = -20 + 1 in base -10
+//! assert_eq!(19i32.to_negabase_string(10), "199");
+//! assert_eq!(i32::from_negabase_str("21", 10), Some(-19));
+//! ```
+
+use crate::num_sys::sign_magnitude::{Magnitude, SignMagnitude, SignedAcc};
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::{string::String, vec::Vec};
+
+/// The iterator over the digits in base `-radix` from the least significant one.
+/// Zero has the single digit 0.
+#[derive(Clone, Debug)]
+pub struct NegabaseDigitsLe<M> {
+    // the remaining value is `magnitude`, negated if `is_negative`
+    is_negative: bool,
+    magnitude: M,
+    radix: u32,
+    is_done: bool,
+}
+
+impl<M: Magnitude> Iterator for NegabaseDigitsLe<M> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.is_done {
+            return None;
+        }
+        // n = q * (-radix) + d with 0 <= d < radix
+        let rem = self.magnitude.div_rem_assign_u32(self.radix);
+        let digit = if self.is_negative && rem != 0 {
+            self.magnitude = self.magnitude.checked_mul_add_u32(1, 1)?;
+            self.radix - rem
+        } else {
+            rem
+        };
+        self.is_negative = !self.is_negative;
+        self.is_done = self.magnitude.is_zero();
+        Some(digit)
+    }
+}
+
+/// Conversions to and from the representations in base `-radix`.
+///
+/// # Panics
+///
+/// The methods panic if `radix` is less than 2 or, for the string forms, greater than 36.
+pub trait Negabase: Sized {
+    type Magnitude: Magnitude;
+
+    fn negabase_digits_le(&self, radix: u32) -> NegabaseDigitsLe<Self::Magnitude>;
+
+    /// Returns [None] if a digit is not less than `radix`, `digits` is empty
+    /// or the value is not representable by `Self`.
+    fn from_negabase_digits_le(digits: &[u32], radix: u32) -> Option<Self>;
+
+    /// Parses the digits base `-radix`, written from the most significant one with `0-9a-z`
+    /// (case-insensitive), without a sign.
+    fn from_negabase_str(s: &str, radix: u32) -> Option<Self>;
+
+    /// Writes the digits base `-radix` from the most significant one with `0-9a-z`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_negabase_string(&self, radix: u32) -> String {
+        assert!(radix <= 36, "The radix must be within 2...36");
+        let digits: Vec<u32> = self.negabase_digits_le(radix).collect();
+        digits
+            .into_iter()
+            .rev()
+            // the digits are less than radix <= 36
+            .map(|d| char::from_digit(d, radix).unwrap())
+            .collect()
+    }
+}
+
+impl<T: SignMagnitude> Negabase for T {
+    type Magnitude = T::Magnitude;
+
+    fn negabase_digits_le(&self, radix: u32) -> NegabaseDigitsLe<T::Magnitude> {
+        assert!(radix >= 2, "The radix must be at least 2");
+        let (is_negative, magnitude) = self.to_sign_magnitude();
+        NegabaseDigitsLe {
+            is_negative,
+            magnitude,
+            radix,
+            is_done: false,
+        }
+    }
+
+    fn from_negabase_digits_le(digits: &[u32], radix: u32) -> Option<Self> {
+        assert!(radix >= 2, "The radix must be at least 2");
+        from_negabase_digits_be(digits.iter().rev().copied(), radix)
+    }
+
+    fn from_negabase_str(s: &str, radix: u32) -> Option<Self> {
+        assert!((2..=36).contains(&radix), "The radix must be within 2...36");
+        let digits = s.chars().map(|c| c.to_digit(radix).unwrap_or(radix));
+        from_negabase_digits_be(digits, radix)
+    }
+}
+
+fn from_negabase_digits_be<T: SignMagnitude, I: Iterator<Item = u32>>(
+    digits: I,
+    radix: u32,
+) -> Option<T> {
+    let mut acc = SignedAcc::zero();
+    let mut is_empty = true;
+    for d in digits {
+        if d >= radix {
+            return None;
+        }
+        acc = acc.checked_mul_add((true, radix), (false, d))?;
+        is_empty = false;
+    }
+    if is_empty {
+        return None;
+    }
+    acc.into_int()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::negabase::Negabase;
+
+    #[test]
+    fn negabase_representations_round_trip_for_primitive_integers() {
+        for radix in [2, 3, 10, 36] {
+            for n in -1000i32..=1000 {
+                let digits: Vec<u32> = n.negabase_digits_le(radix).collect();
+                assert!(digits.iter().all(|&d| d < radix));
+                assert_eq!(i32::from_negabase_digits_le(&digits, radix), Some(n));
+                assert_eq!(
+                    i32::from_negabase_str(&n.to_negabase_string(radix), radix),
+                    Some(n)
+                );
+            }
+        }
+        for n in [i128::MIN, i128::MAX, 0] {
+            assert_eq!(
+                i128::from_negabase_str(&n.to_negabase_string(2), 2),
+                Some(n)
+            );
+        }
+        assert_eq!(
+            u128::from_negabase_str(&u128::MAX.to_negabase_string(7), 7),
+            Some(u128::MAX)
+        );
+        assert_eq!(0u8.to_negabase_string(2), "0");
+        assert_eq!((-1i8).to_negabase_string(2), "11");
+        // -1 is not representable by u8, and 256 does not fit into it
+        assert_eq!(u8::from_negabase_str("11", 2), None);
+        assert_eq!(u8::from_negabase_str("100000000", 2), None);
+        assert_eq!(u8::from_negabase_str("12", 2), None);
+        assert_eq!(u8::from_negabase_str("", 2), None);
+    }
+
+    #[test]
+    fn negabase_representations_round_trip_for_big_integers() {
+        use num_bigint::BigInt;
+
+        let n = -BigInt::from(3u32).pow(200) + 17u32;
+        for radix in [2, 10] {
+            let s = n.to_negabase_string(radix);
+            assert_eq!(BigInt::from_negabase_str(&s, radix), Some(n.clone()));
+        }
+    }
+}
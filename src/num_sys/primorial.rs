@@ -0,0 +1,137 @@
+//! The [primorial number system](https://en.wikipedia.org/wiki/Primorial_number_system), the mixed radix
+//! numeral system with the primes 2, 3, 5, 7, ... as the radices, so the place values are the primorials
+//! 1, 2, 6, 30, 210, ...
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::primorial::Primorial;
+//!
+//! // 100 = 3 * 30 + 1 * 6 + 2 * 2 + 0 * 1
+//! let digits: Vec<u32> = 100u64.primorial_digits_le().unwrap().collect();
+//! assert_eq!(digits, [0, 2, 1, 3]);
+//! assert_eq!(u64::from_primorial_digits_le(&digits), Some(100));
+//! ```
+
+use crate::num_sys::sign_magnitude::{Magnitude, SignMagnitude};
+
+/// The iterator over the digits in the primorial number system from the least significant one.
+/// Zero has the single digit 0.
+#[derive(Clone, Debug)]
+pub struct PrimorialDigitsLe<M> {
+    magnitude: M,
+    prime: u32,
+    is_done: bool,
+}
+
+impl<M: Magnitude> Iterator for PrimorialDigitsLe<M> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.is_done {
+            return None;
+        }
+        let digit = self.magnitude.div_rem_assign_u32(self.prime);
+        self.is_done = self.magnitude.is_zero();
+        if !self.is_done {
+            self.prime = next_prime(self.prime)?;
+        }
+        Some(digit)
+    }
+}
+
+pub trait Primorial: Sized {
+    type Magnitude: Magnitude;
+
+    /// Returns [None] for negative integers.
+    fn primorial_digits_le(&self) -> Option<PrimorialDigitsLe<Self::Magnitude>>;
+
+    /// Returns [None] if a digit is not less than the prime at its position
+    /// or the value is not representable by `Self`.
+    fn from_primorial_digits_le(digits: &[u32]) -> Option<Self>;
+}
+
+impl<T: SignMagnitude> Primorial for T {
+    type Magnitude = T::Magnitude;
+
+    fn primorial_digits_le(&self) -> Option<PrimorialDigitsLe<T::Magnitude>> {
+        match self.to_sign_magnitude() {
+            (true, magnitude) if !magnitude.is_zero() => None,
+            (_, magnitude) => Some(PrimorialDigitsLe {
+                magnitude,
+                prime: 2,
+                is_done: false,
+            }),
+        }
+    }
+
+    fn from_primorial_digits_le(digits: &[u32]) -> Option<Self> {
+        if digits.is_empty() {
+            return T::from_sign_magnitude(false, T::Magnitude::from_u32(0));
+        }
+        // Horner's method needs the primes from the one at the most significant position down
+        let mut prime = 2;
+        for _ in 1..digits.len() {
+            prime = next_prime(prime)?;
+        }
+        let mut magnitude = T::Magnitude::from_u32(0);
+        for (i, &d) in digits.iter().enumerate().rev() {
+            if d >= prime {
+                return None;
+            }
+            magnitude = magnitude.checked_mul_add_u32(prime, d)?;
+            if i > 0 {
+                prime = prev_prime(prime);
+            }
+        }
+        T::from_sign_magnitude(false, magnitude)
+    }
+}
+
+fn is_prime(n: u32) -> bool {
+    match n {
+        0 | 1 => false,
+        2 | 3 => true,
+        _ if n.is_multiple_of(2) => false,
+        _ => (3..)
+            .step_by(2)
+            .take_while(|&d: &u32| d.saturating_mul(d) <= n)
+            .all(|d| !n.is_multiple_of(d)),
+    }
+}
+
+fn next_prime(p: u32) -> Option<u32> {
+    (p.checked_add(1)?..=u32::MAX).find(|&n| is_prime(n))
+}
+
+// `p` must be greater than 2
+fn prev_prime(p: u32) -> u32 {
+    (2..p).rev().find(|&n| is_prime(n)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::primorial::Primorial;
+
+    #[test]
+    fn primorial_representations_round_trip() {
+        let primes = [2u32, 3, 5, 7, 11, 13, 17, 19, 23, 29];
+        for n in (0u64..=1_000_000).step_by(331).chain([u64::MAX]) {
+            let digits: Vec<u32> = n.primorial_digits_le().unwrap().collect();
+            assert!(digits.iter().zip(primes).all(|(&d, p)| d < p));
+            assert_eq!(u64::from_primorial_digits_le(&digits), Some(n));
+        }
+        // 30030 = 2 * 3 * 5 * 7 * 11 * 13
+        let digits: Vec<u32> = 30030u16.primorial_digits_le().unwrap().collect();
+        assert_eq!(digits, [0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(0u8.primorial_digits_le().unwrap().collect::<Vec<_>>(), [0]);
+        assert!((-1i64).primorial_digits_le().is_none());
+        assert_eq!(u32::from_primorial_digits_le(&[1, 3]), None);
+        assert_eq!(u8::from_primorial_digits_le(&[0, 0, 0, 0, 2]), None);
+        assert_eq!(u8::from_primorial_digits_le(&[]), Some(0));
+
+        use num_bigint::BigUint;
+
+        let n = BigUint::from(97u32).pow(200) - 1u32;
+        let digits: Vec<u32> = n.primorial_digits_le().unwrap().collect();
+        assert_eq!(BigUint::from_primorial_digits_le(&digits), Some(n));
+    }
+}
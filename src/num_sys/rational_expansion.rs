@@ -0,0 +1,555 @@
+//! Exact positional expansions of fractions, which consist of the integer part, the pre-period
+//! and the repeating period (the [repetend](https://en.wikipedia.org/wiki/Repeating_decimal)) of the fractional part.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::rational_expansion::{expand_fraction, RepetendNotation};
+//!
+//! let one_seventh = expand_fraction(&1u32, &7u32, 10).unwrap();
+//! assert_eq!((one_seventh.pre_period_len(), one_seventh.period_len()), (0, 6));
+//! assert_eq!(one_seventh.to_string(), "0.(142857)");
+//!
+//! let expansion = expand_fraction(&-25i32, &12i32, 10).unwrap();
+//! assert_eq!(expansion.to_string(), "-2.08(3)");
+//! assert_eq!(expansion.display(RepetendNotation::Vinculum).to_string(), "-2.083\u{305}");
+//!
+//! // 5/8 is terminating in base 2
+//! assert_eq!(expand_fraction(&5u8, &8u8, 2).unwrap().to_string(), "0.101");
+//! ```
+
+use crate::num_sys::sign_magnitude::{Magnitude, SignMagnitude};
+use core::fmt::{self, Display, Write};
+
+/// The notation of the repeating period.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RepetendNotation {
+    /// The period in parentheses, e.g. `0.08(3)`.
+    #[default]
+    Parentheses,
+    /// The period overlined with the combining overline `U+0305` after every digit, e.g. `0.083̅`.
+    Vinculum,
+}
+
+/// The expansion of a fraction in lowest terms in the given radix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RationalExpansion<M> {
+    is_negative: bool,
+    integer_part: M,
+    // the numerator of the fractional part, which is less than the denominator
+    numerator: M,
+    denominator: M,
+    radix: u32,
+    pre_period_len: u64,
+    period_len: u64,
+}
+
+/// Expands `numerator / denominator` in base `radix` or returns [None] if `denominator` is 0 or the period
+/// is longer than [u64::MAX] digits.
+///
+/// The fraction is reduced to lowest terms with the denominator `q`. The length of the pre-period is the number
+/// of the divisions of `q` by its greatest common divisor with `radix` until they are coprime, and the length
+/// of the period is the multiplicative order of `radix` modulo the rest `q'` of `q`. The short periods are found
+/// by stepping through the powers of `radix` modulo `q'`. The longer ones are found from the factorizations of `q'`
+/// and of the [Carmichael function](https://en.wikipedia.org/wiki/Carmichael_function) λ(q'), which the order
+/// divides, with the modular exponentiation, if the `alloc` feature is enabled and the factorizations are
+/// complete within [EcmLimits::default][crate::number_theory::factorization::EcmLimits], and by the stepping
+/// otherwise, which takes time proportional to the length of the period.
+///
+/// # Panics
+///
+/// Panics if `radix` is less than 2.
+pub fn expand_fraction<T: SignMagnitude>(
+    numerator: &T,
+    denominator: &T,
+    radix: u32,
+) -> Option<RationalExpansion<T::Magnitude>> {
+    assert!(radix >= 2, "The radix must be at least 2");
+    let (numerator_is_negative, numerator) = numerator.to_sign_magnitude();
+    let (denominator_is_negative, denominator) = denominator.to_sign_magnitude();
+    if denominator.is_zero() {
+        return None;
+    }
+    let is_negative = numerator_is_negative != denominator_is_negative && !numerator.is_zero();
+    let divisor = gcd(numerator.clone(), denominator.clone());
+    let (numerator, denominator) = (
+        numerator.div_rem(&divisor).0,
+        denominator.div_rem(&divisor).0,
+    );
+    let (integer_part, numerator) = numerator.div_rem(&denominator);
+
+    let mut rest = denominator.clone();
+    let mut pre_period_len = 0;
+    loop {
+        let divisor = gcd_u32(radix, rest.clone().div_rem_assign_u32(radix));
+        if divisor == 1 {
+            break;
+        }
+        rest.div_rem_assign_u32(divisor);
+        pre_period_len += 1;
+    }
+
+    let period_len = if rest == T::Magnitude::from_u32(1) {
+        0
+    } else {
+        // the rest is coprime with the radix
+        period_len(&rest, radix)?
+    };
+
+    Some(RationalExpansion {
+        is_negative,
+        integer_part,
+        numerator,
+        denominator,
+        radix,
+        pre_period_len,
+        period_len,
+    })
+}
+
+// The multiplicative order of the radix modulo the rest, which is greater than 1 and coprime with it,
+// or None if it exceeds u64::MAX
+fn period_len<M: Magnitude>(rest: &M, radix: u32) -> Option<u64> {
+    // the short periods are stepped through faster than the rest is factorized
+    if let Some(len) = step_period_len(rest, radix, 1 << 16) {
+        return Some(len);
+    }
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    if let Some(len) = order::factorized_period_len(rest, radix) {
+        return len;
+    }
+    step_period_len(rest, radix, u64::MAX)
+}
+
+// The number of the steps after which the powers of the radix modulo the rest return to 1 or None if there are
+// more than max_len of them
+fn step_period_len<M: Magnitude>(rest: &M, radix: u32, max_len: u64) -> Option<u64> {
+    let one = M::from_u32(1);
+    let mut power = one.clone();
+    for len in 1..=max_len {
+        power.mul_u32_div_rem_assign(radix, rest);
+        if power == one {
+            return Some(len);
+        }
+    }
+    None
+}
+
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+mod order {
+    use crate::num_sys::sign_magnitude::Magnitude;
+    use crate::number_theory::factorization::Factorize;
+    #[cfg(any(doc, test, doctest, feature = "bigint"))]
+    use crate::number_theory::{
+        factorization::{EcmLimits, FactorizeWithLimits},
+        gcd::Lcm,
+    };
+    #[cfg(any(doc, test, doctest, feature = "bigint"))]
+    use num_bigint::BigUint;
+    #[cfg(any(doc, test, doctest, feature = "bigint"))]
+    use num_traits::{One, ToPrimitive, Zero};
+
+    // The multiplicative order of the radix modulo the rest like period_len, or None if the rest or λ of it
+    // is not factorized within the default limits
+    pub(super) fn factorized_period_len<M: Magnitude>(rest: &M, radix: u32) -> Option<Option<u64>> {
+        if let Some(n) = to_u128(rest) {
+            return Some(u64::try_from(order_u128(u128::from(radix), n)).ok());
+        }
+        #[cfg(any(doc, test, doctest, feature = "bigint"))]
+        return order_big(&BigUint::from(radix), &to_big_uint(rest)).map(|order| order.to_u64());
+        #[cfg(not(any(doc, test, doctest, feature = "bigint")))]
+        None
+    }
+
+    // The magnitude below 2^128 by its digits base 2^16
+    fn to_u128<M: Magnitude>(m: &M) -> Option<u128> {
+        let (mut m, mut n) = (m.clone(), 0);
+        for shift in (0..u128::BITS).step_by(16) {
+            n |= u128::from(m.div_rem_assign_u32(1 << 16)) << shift;
+        }
+        m.is_zero().then_some(n)
+    }
+
+    #[cfg(any(doc, test, doctest, feature = "bigint"))]
+    fn to_big_uint<M: Magnitude>(m: &M) -> BigUint {
+        let (mut m, mut digits) = (m.clone(), alloc::vec::Vec::new());
+        while !m.is_zero() {
+            let low = m.div_rem_assign_u32(1 << 16);
+            digits.push(m.div_rem_assign_u32(1 << 16) << 16 | low);
+        }
+        BigUint::new(digits)
+    }
+
+    // a * b mod n for a, b < n
+    fn mul_mod_u128(a: u128, b: u128, n: u128) -> u128 {
+        if n <= u128::from(u64::MAX) {
+            return a * b % n;
+        }
+        // a + b mod n for a, b < n
+        let add = |a: u128, b: u128| {
+            let (sum, is_overflowing) = a.overflowing_add(b);
+            if is_overflowing || sum >= n {
+                sum.wrapping_sub(n)
+            } else {
+                sum
+            }
+        };
+        let (mut a, mut b, mut product) = (a, b, 0);
+        while b != 0 {
+            if b & 1 == 1 {
+                product = add(product, a);
+            }
+            a = add(a, a);
+            b >>= 1;
+        }
+        product
+    }
+
+    fn pow_mod_u128(base: u128, exp: u128, n: u128) -> u128 {
+        (0..u128::BITS - exp.leading_zeros())
+            .rev()
+            .fold(1 % n, |acc, i| {
+                let acc = mul_mod_u128(acc, acc, n);
+                if exp >> i & 1 == 1 {
+                    mul_mod_u128(acc, base, n)
+                } else {
+                    acc
+                }
+            })
+    }
+
+    // The Carmichael function λ(p^e), the exponent of the multiplicative group modulo p^e
+    fn carmichael_prime_power(p: u128, e: u32) -> u128 {
+        if p == 2 && e >= 3 {
+            1 << (e - 2)
+        } else {
+            p.pow(e - 1) * (p - 1)
+        }
+    }
+
+    // The order of a modulo n > 1 coprime with it divides λ(n), so it is found by dividing λ(n) by its primes
+    // while a to the power of the quotient is still 1
+    fn order_u128(a: u128, n: u128) -> u128 {
+        let a = a % n;
+        // λ(n) = lcm(λ(p^e)) < n
+        let lambda = n
+            .factorize()
+            .unwrap()
+            .factors()
+            .iter()
+            .fold(1, |lambda, &(p, e)| {
+                let lambda_p = carmichael_prime_power(p, e);
+                lambda / super::gcd(lambda, lambda_p) * lambda_p
+            });
+        let mut order = lambda;
+        for &(q, _) in lambda.factorize().unwrap().factors() {
+            while order % q == 0 && pow_mod_u128(a, order / q, n) == 1 {
+                order /= q;
+            }
+        }
+        order
+    }
+
+    #[cfg(any(doc, test, doctest, feature = "bigint"))]
+    fn order_big(a: &BigUint, n: &BigUint) -> Option<BigUint> {
+        let factorize = |n: &BigUint| {
+            let (factorization, cofactor) = n.factorize_with_limits(&EcmLimits::default())?;
+            cofactor.is_one().then_some(factorization)
+        };
+        let lambda = factorize(n)?
+            .factors()
+            .iter()
+            .fold(BigUint::one(), |lambda, (p, e)| {
+                let lambda_p = p.pow(e - 1) * (p - 1u8);
+                lambda.lcm(&lambda_p)
+            });
+        let mut order = lambda.clone();
+        for (q, _) in factorize(&lambda)?.factors() {
+            while Zero::is_zero(&(&order % q)) && a.modpow(&(&order / q), n).is_one() {
+                order /= q;
+            }
+        }
+        Some(order)
+    }
+}
+
+fn gcd<M: Magnitude>(mut a: M, mut b: M) -> M {
+    while !b.is_zero() {
+        let rem = a.div_rem(&b).1;
+        a = core::mem::replace(&mut b, rem);
+    }
+    a
+}
+
+fn gcd_u32(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl<M: Magnitude> RationalExpansion<M> {
+    #[inline(always)]
+    pub fn is_negative(&self) -> bool {
+        self.is_negative
+    }
+
+    /// Returns the absolute value of the integer part.
+    #[inline(always)]
+    pub fn integer_part(&self) -> &M {
+        &self.integer_part
+    }
+
+    /// Returns the numerator of the fractional part in lowest terms.
+    #[inline(always)]
+    pub fn fractional_numerator(&self) -> &M {
+        &self.numerator
+    }
+
+    /// Returns the denominator in lowest terms.
+    #[inline(always)]
+    pub fn denominator(&self) -> &M {
+        &self.denominator
+    }
+
+    #[inline(always)]
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    #[inline(always)]
+    pub fn pre_period_len(&self) -> u64 {
+        self.pre_period_len
+    }
+
+    /// Returns the length of the period, which is 0 for the terminating expansions.
+    #[inline(always)]
+    pub fn period_len(&self) -> u64 {
+        self.period_len
+    }
+
+    #[inline(always)]
+    pub fn is_terminating(&self) -> bool {
+        self.period_len == 0
+    }
+
+    pub fn pre_period_digits(&self) -> FractionalDigits<'_, M> {
+        FractionalDigits {
+            remainder: self.numerator.clone(),
+            denominator: &self.denominator,
+            radix: self.radix,
+            len: self.pre_period_len,
+        }
+    }
+
+    pub fn period_digits(&self) -> FractionalDigits<'_, M> {
+        let mut digits = self.pre_period_digits();
+        digits.by_ref().for_each(drop);
+        digits.len = self.period_len;
+        digits
+    }
+
+    /// Returns the value that displays the expansion with the digits `0-9` and `a-z`
+    /// or fails to be formatted if the radix is greater than 36.
+    #[inline(always)]
+    pub fn display(&self, notation: RepetendNotation) -> DisplayRationalExpansion<'_, M> {
+        DisplayRationalExpansion {
+            expansion: self,
+            notation,
+        }
+    }
+
+    fn write_integer_part<W: Write>(&self, w: &mut W) -> fmt::Result {
+        // the greatest power of the radix not exceeding the integer part
+        let mut power = M::from_u32(1);
+        while let Some(next) = power.checked_mul_add_u32(self.radix, 0) {
+            if next > self.integer_part {
+                break;
+            }
+            power = next;
+        }
+        let mut rest = self.integer_part.clone();
+        loop {
+            let (digit, rem) = rest.div_rem(&power);
+            // the digit is less than the radix
+            write_digit(w, digit.to_u32().unwrap(), self.radix)?;
+            if power == M::from_u32(1) {
+                return Ok(());
+            }
+            rest = rem;
+            power.div_rem_assign_u32(self.radix);
+        }
+    }
+}
+
+impl<M: Magnitude> Display for RationalExpansion<M> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(RepetendNotation::Parentheses).fmt(f)
+    }
+}
+
+fn write_digit<W: Write>(w: &mut W, digit: u32, radix: u32) -> fmt::Result {
+    if radix > 36 {
+        return Err(fmt::Error);
+    }
+    w.write_char(char::from_digit(digit, radix).ok_or(fmt::Error)?)
+}
+
+/// The iterator over the digits of a part of the fractional part, computed with the long division.
+#[derive(Clone, Debug)]
+pub struct FractionalDigits<'a, M> {
+    remainder: M,
+    denominator: &'a M,
+    radix: u32,
+    len: u64,
+}
+
+impl<M: Magnitude> Iterator for FractionalDigits<'_, M> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(
+            self.remainder
+                .mul_u32_div_rem_assign(self.radix, self.denominator),
+        )
+    }
+}
+
+/// The [Display] adapter returned by [RationalExpansion::display].
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayRationalExpansion<'a, M> {
+    expansion: &'a RationalExpansion<M>,
+    notation: RepetendNotation,
+}
+
+impl<M: Magnitude> Display for DisplayRationalExpansion<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expansion = self.expansion;
+        let radix = expansion.radix;
+        if expansion.is_negative {
+            f.write_char('-')?;
+        }
+        expansion.write_integer_part(f)?;
+        if expansion.pre_period_len == 0 && expansion.period_len == 0 {
+            return Ok(());
+        }
+        f.write_char('.')?;
+        for d in expansion.pre_period_digits() {
+            write_digit(f, d, radix)?;
+        }
+        if expansion.period_len == 0 {
+            return Ok(());
+        }
+        match self.notation {
+            RepetendNotation::Parentheses => {
+                f.write_char('(')?;
+                for d in expansion.period_digits() {
+                    write_digit(f, d, radix)?;
+                }
+                f.write_char(')')
+            }
+            RepetendNotation::Vinculum => {
+                for d in expansion.period_digits() {
+                    write_digit(f, d, radix)?;
+                    f.write_char('\u{305}')?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::rational_expansion::{expand_fraction, RepetendNotation};
+
+    #[test]
+    fn fractions_are_expanded_with_their_periods() {
+        let cases: [(i64, i64, u32, &str); 12] = [
+            (1, 7, 10, "0.(142857)"),
+            (22, 7, 10, "3.(142857)"),
+            (1, 12, 10, "0.08(3)"),
+            (3, 6, 10, "0.5"),
+            (0, -5, 10, "0"),
+            (-40, 8, 10, "-5"),
+            (7, -3, 10, "-2.(3)"),
+            (-1, 3, 3, "-0.1"),
+            (1, 10, 2, "0.0(0011)"),
+            (255, 16, 16, "f.f"),
+            (1, 97, 10, "0.(010309278350515463917525773195876288659793814432989690721649484536082474226804123711340206185567)"),
+            (1_000_000, 1, 36, "lfls"),
+        ];
+        for (numerator, denominator, radix, s) in cases {
+            let expansion = expand_fraction(&numerator, &denominator, radix).unwrap();
+            assert_eq!(expansion.to_string(), s);
+        }
+        let expansion = expand_fraction(&1u8, &6u8, 10).unwrap();
+        assert_eq!(
+            expansion.display(RepetendNotation::Vinculum).to_string(),
+            "0.16\u{305}"
+        );
+        assert!(expand_fraction(&1u8, &0u8, 10).is_none());
+        assert!(expand_fraction(&1u8, &3u8, 10).unwrap().period_len() == 1);
+        assert!(expand_fraction(&3u8, &40u8, 10).unwrap().is_terminating());
+        let expansion = expand_fraction(&1u8, &3u8, 40).unwrap();
+        let mut s = String::new();
+        assert!(core::fmt::write(&mut s, format_args!("{}", expansion)).is_err());
+    }
+
+    #[test]
+    fn periods_are_found_for_big_denominators() {
+        // 16^32 - 1, for which the long division steps do not fit into u128
+        let expansion = expand_fraction(&1u128, &u128::MAX, 16).unwrap();
+        assert_eq!(expansion.period_len(), 32);
+        let period: Vec<u32> = expansion.period_digits().collect();
+        assert_eq!(period[31], 1);
+        assert!(period[..31].iter().all(|&d| d == 0));
+        let expansion = expand_fraction(&(u128::MAX - 1), &u128::MAX, 16).unwrap();
+        assert_eq!(expansion.to_string(), format!("0.({:x})", u128::MAX - 1));
+    }
+
+    #[test]
+    fn periods_are_found_for_large_prime_denominators() {
+        // 10 is a primitive root modulo 1_000_000_007
+        let expansion = expand_fraction(&1u64, &1_000_000_007u64, 10).unwrap();
+        assert_eq!(expansion.period_len(), 1_000_000_006);
+        let digits: Vec<u32> = expansion.period_digits().take(12).collect();
+        assert_eq!(digits, [0, 0, 0, 0, 0, 0, 0, 0, 0, 9, 9, 9]);
+        // (2^61 - 2) / 2 for the Mersenne prime 2^61 - 1
+        let expansion = expand_fraction(&1u64, &((1u64 << 61) - 1), 10).unwrap();
+        assert_eq!(expansion.period_len(), 1_152_921_504_606_846_975);
+        // the product of the Mersenne primes 2^61 - 1 and 2^31 - 1 exceeds u64::MAX
+        let denominator = ((1u128 << 61) - 1) * ((1u128 << 31) - 1);
+        let expansion = expand_fraction(&1u128, &denominator, 7).unwrap();
+        assert_eq!(expansion.period_len(), 7_560_141_013_815_390);
+        // the period of 1 / (2^127 - 1) has more than u64::MAX digits
+        assert!(expand_fraction(&1u128, &i128::MAX.unsigned_abs(), 10).is_none());
+
+        use num_bigint::BigUint;
+
+        // 10^40 / (10^40 - 1)
+        let denominator = BigUint::from(10u32).pow(40) - 1u32;
+        let expansion = expand_fraction(&(&denominator + 1u32), &denominator, 10).unwrap();
+        assert_eq!(expansion.to_string(), format!("1.({:0>40})", 1));
+        // the period of 1 / (2^89 - 1) has more than u64::MAX digits
+        let denominator = (BigUint::from(1u32) << 89u32) - 1u32;
+        assert!(expand_fraction(&BigUint::from(1u32), &denominator, 10).is_none());
+        // the factorization of 10^150 - 1 is out of reach of the default limits
+        let denominator = BigUint::from(10u32).pow(150) - 1u32;
+        let expansion = expand_fraction(&BigUint::from(1u32), &denominator, 10).unwrap();
+        assert_eq!(expansion.period_len(), 150);
+        // lcm(1_000_000_006, 998_244_352)
+        let denominator = BigUint::from(1_000_000_007u64) * 998_244_353u64;
+        let expansion = expand_fraction(&BigUint::from(1u32), &denominator, 10).unwrap();
+        assert_eq!(expansion.period_len(), 499_122_178_994_733_056);
+        let expansion =
+            expand_fraction(&BigUint::from(1u32), &BigUint::from(1u32 << 20), 2).unwrap();
+        assert_eq!(
+            (expansion.pre_period_len(), expansion.period_len()),
+            (20, 0)
+        );
+    }
+}
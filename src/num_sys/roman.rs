@@ -0,0 +1,396 @@
+//! [Roman numerals](https://en.wikipedia.org/wiki/Roman_numerals) for the values in `1..=3999`
+//! and, with the extended notations, for larger ones.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::roman::{FromRoman, RomanError, RomanNotation, RomanParsing, ToRoman};
+//!
+//! assert_eq!(1994u16.roman(RomanNotation::Standard).unwrap().to_string(), "MCMXCIV");
+//! assert_eq!(u16::from_roman("MCMXCIV"), Ok(1994));
+//! assert_eq!(u16::from_roman("IIII"), Err(RomanError::NonCanonical));
+//! assert_eq!(
+//!     u16::from_roman_with("iiii", RomanNotation::Standard, RomanParsing::Lenient),
+//!     Ok(4)
+//! );
+//! assert_eq!(4000u32.roman(RomanNotation::Vinculum).unwrap().to_string(), "I\u{305}V\u{305}");
+//! ```
+
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Write};
+use num_traits::PrimInt;
+
+/// The notation of Roman numerals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RomanNotation {
+    /// The subtractive notation with `I`, `V`, `X`, `L`, `C`, `D` and `M` for the values in `1..=3999`.
+    #[default]
+    Standard,
+    /// The [vinculum](https://en.wikipedia.org/wiki/Roman_numerals#Vinculum) notation for the values
+    /// in `1..=3_999_999`, where the values from 4000 write the thousands with the standard numerals overlined
+    /// with the combining overline `U+0305` after each letter.
+    Vinculum,
+    /// The [apostrophus](https://en.wikipedia.org/wiki/Roman_numerals#Apostrophus) notation for the values
+    /// in `1..=999_999`, where the thousands are written additively with `CIↃ` (1000), `IↃↃ` (5000),
+    /// `CCIↃↃ` (10 000), `IↃↃↃ` (50 000), `CCCIↃↃↃ` (100 000) and `IↃↃↃↃ` (500 000),
+    /// using the reversed C `U+2183`.
+    Apostrophus,
+}
+
+impl RomanNotation {
+    /// The greatest value representable in the notation.
+    pub const fn max_value(self) -> u32 {
+        match self {
+            RomanNotation::Standard => 3999,
+            RomanNotation::Vinculum => 3_999_999,
+            RomanNotation::Apostrophus => 999_999,
+        }
+    }
+}
+
+/// The mode of parsing Roman numerals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RomanParsing {
+    /// Only the canonical representation in the notation is accepted.
+    #[default]
+    Strict,
+    /// Lowercase letters, additive forms like `IIII` and any smaller numeral before a greater one
+    /// (subtracted from the total, e.g. `IC` is 99 and `VX` is 5) are accepted.
+    Lenient,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RomanError {
+    /// The value is not representable in the notation or in the target type.
+    OutOfRange,
+    /// The input is empty.
+    Empty,
+    /// The input contains a character that does not start a numeral of the notation.
+    InvalidSymbol(char),
+    /// The input is valid in the lenient mode but is not the canonical representation of its value.
+    NonCanonical,
+}
+
+impl Display for RomanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RomanError::OutOfRange => {
+                f.write_str("the value is out of range of the Roman numerals")
+            }
+            RomanError::Empty => f.write_str("cannot parse a Roman numeral from an empty string"),
+            RomanError::InvalidSymbol(c) => write!(f, "invalid symbol {:?} in a Roman numeral", c),
+            RomanError::NonCanonical => f.write_str("the Roman numeral is not canonical"),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(any(doc, test, doctest, feature = "std"))]
+impl std::error::Error for RomanError {}
+
+/// A value together with the notation, which is displayed as a Roman numeral without allocations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Roman {
+    value: u32,
+    notation: RomanNotation,
+}
+
+impl Roman {
+    /// Returns [RomanError::OutOfRange] if `value` is 0 or exceeds [RomanNotation::max_value].
+    pub fn new(value: u32, notation: RomanNotation) -> Result<Self, RomanError> {
+        if value == 0 || value > notation.max_value() {
+            return Err(RomanError::OutOfRange);
+        }
+        Ok(Self { value, notation })
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    #[inline(always)]
+    pub fn notation(&self) -> RomanNotation {
+        self.notation
+    }
+}
+
+// (one, five, ten) for units, tens and hundreds
+const STANDARD_PLACES: [(&str, &str, &str); 3] =
+    [("I", "V", "X"), ("X", "L", "C"), ("C", "D", "M")];
+// (one, five) for thousands, tens of thousands and hundreds of thousands
+const APOSTROPHUS_PLACES: [(&str, &str); 3] = [
+    ("CI\u{2183}", "I\u{2183}\u{2183}"),
+    ("CCI\u{2183}\u{2183}", "I\u{2183}\u{2183}\u{2183}"),
+    (
+        "CCCI\u{2183}\u{2183}\u{2183}",
+        "I\u{2183}\u{2183}\u{2183}\u{2183}",
+    ),
+];
+const OVERLINE: char = '\u{305}';
+
+fn write_symbol<W: Write>(w: &mut W, symbol: &str, overlined: bool) -> fmt::Result {
+    if !overlined {
+        return w.write_str(symbol);
+    }
+    for c in symbol.chars() {
+        w.write_char(c)?;
+        w.write_char(OVERLINE)?;
+    }
+    Ok(())
+}
+
+// Writes `n` in `0..=3999` in the standard notation
+fn write_standard<W: Write>(w: &mut W, n: u32, overlined: bool) -> fmt::Result {
+    for _ in 0..n / 1000 {
+        write_symbol(w, "M", overlined)?;
+    }
+    for (place, &(one, five, ten)) in STANDARD_PLACES.iter().enumerate().rev() {
+        let digit = n / 10u32.pow(place as u32) % 10;
+        match digit {
+            4 => {
+                write_symbol(w, one, overlined)?;
+                write_symbol(w, five, overlined)?;
+            }
+            9 => {
+                write_symbol(w, one, overlined)?;
+                write_symbol(w, ten, overlined)?;
+            }
+            _ => {
+                if digit >= 5 {
+                    write_symbol(w, five, overlined)?;
+                }
+                for _ in 0..digit % 5 {
+                    write_symbol(w, one, overlined)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Display for Roman {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.value;
+        match self.notation {
+            RomanNotation::Standard => write_standard(f, n, false),
+            RomanNotation::Vinculum if n < 4000 => write_standard(f, n, false),
+            RomanNotation::Vinculum => {
+                write_standard(f, n / 1000, true)?;
+                write_standard(f, n % 1000, false)
+            }
+            RomanNotation::Apostrophus => {
+                for (place, &(one, five)) in APOSTROPHUS_PLACES.iter().enumerate().rev() {
+                    let digit = n / 1000 / 10u32.pow(place as u32) % 10;
+                    if digit >= 5 {
+                        f.write_str(five)?;
+                    }
+                    for _ in 0..digit % 5 {
+                        f.write_str(one)?;
+                    }
+                }
+                write_standard(f, n % 1000, false)
+            }
+        }
+    }
+}
+
+pub trait ToRoman {
+    fn roman(&self, notation: RomanNotation) -> Result<Roman, RomanError>;
+
+    /// Returns the canonical Roman numeral in the [standard notation][RomanNotation::Standard].
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_roman(&self) -> Result<String, RomanError> {
+        Ok(self.roman(RomanNotation::Standard)?.to_string())
+    }
+}
+
+impl<T: PrimInt> ToRoman for T {
+    #[inline]
+    fn roman(&self, notation: RomanNotation) -> Result<Roman, RomanError> {
+        let value = self.to_u32().ok_or(RomanError::OutOfRange)?;
+        Roman::new(value, notation)
+    }
+}
+
+pub trait FromRoman: Sized {
+    fn from_roman_with(
+        s: &str,
+        notation: RomanNotation,
+        parsing: RomanParsing,
+    ) -> Result<Self, RomanError>;
+
+    /// Parses the canonical Roman numeral in the [standard notation][RomanNotation::Standard].
+    #[inline(always)]
+    fn from_roman(s: &str) -> Result<Self, RomanError> {
+        Self::from_roman_with(s, RomanNotation::Standard, RomanParsing::Strict)
+    }
+}
+
+impl<T: PrimInt> FromRoman for T {
+    fn from_roman_with(
+        s: &str,
+        notation: RomanNotation,
+        parsing: RomanParsing,
+    ) -> Result<Self, RomanError> {
+        let value = parse_roman(s, notation, parsing)?;
+        T::from(value).ok_or(RomanError::OutOfRange)
+    }
+}
+
+// The apostrophus numerals, such that the longer ones precede their prefixes
+const APOSTROPHUS_NUMERALS: [(&str, u32); 6] = [
+    ("CCCI\u{2183}\u{2183}\u{2183}", 100_000),
+    ("I\u{2183}\u{2183}\u{2183}\u{2183}", 500_000),
+    ("I\u{2183}\u{2183}\u{2183}", 50_000),
+    ("CCI\u{2183}\u{2183}", 10_000),
+    ("I\u{2183}\u{2183}", 5000),
+    ("CI\u{2183}", 1000),
+];
+
+// Returns the value of the numeral at the start of `s` and its length in bytes
+fn next_numeral(s: &str, notation: RomanNotation) -> Result<(u32, usize), RomanError> {
+    if notation == RomanNotation::Apostrophus {
+        if let Some(&(numeral, value)) = APOSTROPHUS_NUMERALS
+            .iter()
+            .find(|(numeral, _)| s.starts_with(numeral))
+        {
+            return Ok((value, numeral.len()));
+        }
+    }
+    // `s` is not empty
+    let c = s.chars().next().unwrap();
+    let value = match c.to_ascii_uppercase() {
+        'I' => 1,
+        'V' => 5,
+        'X' => 10,
+        'L' => 50,
+        'C' => 100,
+        'D' => 500,
+        'M' => 1000,
+        _ => return Err(RomanError::InvalidSymbol(c)),
+    };
+    let len = c.len_utf8();
+    if notation == RomanNotation::Vinculum && s[len..].starts_with(OVERLINE) {
+        return Ok((value * 1000, len + OVERLINE.len_utf8()));
+    }
+    Ok((value, len))
+}
+
+// Compares the written fragments with the prefix of the remaining input
+struct PrefixMatcher<'a>(&'a str);
+
+impl Write for PrefixMatcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+        Ok(())
+    }
+}
+
+fn parse_roman(s: &str, notation: RomanNotation, parsing: RomanParsing) -> Result<u32, RomanError> {
+    if s.is_empty() {
+        return Err(RomanError::Empty);
+    }
+    let (mut total, mut pending, mut rest) = (0i64, None::<u32>, s);
+    while !rest.is_empty() {
+        let (value, len) = next_numeral(rest, notation)?;
+        if let Some(prev) = pending {
+            if prev < value {
+                total -= i64::from(prev);
+            } else {
+                total += i64::from(prev);
+            }
+        }
+        pending = Some(value);
+        rest = &rest[len..];
+    }
+    total += i64::from(pending.unwrap_or(0));
+    let value = u32::try_from(total).map_err(|_| RomanError::OutOfRange)?;
+    let roman = Roman::new(value, notation)?;
+    if parsing == RomanParsing::Strict {
+        let mut matcher = PrefixMatcher(s);
+        if write!(matcher, "{}", roman).is_err() || !matcher.0.is_empty() {
+            return Err(RomanError::NonCanonical);
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::roman::*;
+
+    #[test]
+    fn standard_roman_numerals_round_trip() {
+        for n in 1..=3999u16 {
+            let roman = n.to_roman().unwrap();
+            assert_eq!(u16::from_roman(&roman), Ok(n));
+            assert_eq!(
+                u16::from_roman_with(
+                    &roman.to_lowercase(),
+                    RomanNotation::Standard,
+                    RomanParsing::Lenient
+                ),
+                Ok(n)
+            );
+        }
+        assert_eq!(3999i32.to_roman().unwrap(), "MMMCMXCIX");
+        assert_eq!(0u8.to_roman(), Err(RomanError::OutOfRange));
+        assert_eq!((-1i8).to_roman(), Err(RomanError::OutOfRange));
+        assert_eq!(4000u16.to_roman(), Err(RomanError::OutOfRange));
+    }
+
+    #[test]
+    fn strict_parsing_rejects_non_canonical_numerals() {
+        for s in ["IIII", "VX", "IC", "XM", "MMMM", "iv", "VV", "CDD"] {
+            assert!(u16::from_roman(s).is_err(), "{}", s);
+        }
+        assert_eq!(u16::from_roman("IIII"), Err(RomanError::NonCanonical));
+        assert_eq!(u16::from_roman(""), Err(RomanError::Empty));
+        assert_eq!(u16::from_roman("XIZ"), Err(RomanError::InvalidSymbol('Z')));
+        assert_eq!(u8::from_roman("CCLVI"), Err(RomanError::OutOfRange));
+        let lenient = |s| u16::from_roman_with(s, RomanNotation::Standard, RomanParsing::Lenient);
+        assert_eq!(lenient("IIII"), Ok(4));
+        assert_eq!(lenient("VX"), Ok(5));
+        assert_eq!(lenient("IC"), Ok(99));
+        assert_eq!(lenient("MMMM"), Err(RomanError::OutOfRange));
+    }
+
+    #[test]
+    fn extended_roman_numerals_round_trip() {
+        for (notation, step) in [
+            (RomanNotation::Vinculum, 997),
+            (RomanNotation::Apostrophus, 331),
+        ] {
+            for n in (1..=notation.max_value()).step_by(step) {
+                let roman = n.roman(notation).unwrap().to_string();
+                assert_eq!(
+                    u32::from_roman_with(&roman, notation, RomanParsing::Strict),
+                    Ok(n)
+                );
+            }
+        }
+        assert_eq!(
+            1_004_009u32
+                .roman(RomanNotation::Vinculum)
+                .unwrap()
+                .to_string(),
+            "M\u{305}I\u{305}V\u{305}IX"
+        );
+        assert_eq!(
+            16_500u32
+                .roman(RomanNotation::Apostrophus)
+                .unwrap()
+                .to_string(),
+            "CCI\u{2183}\u{2183}I\u{2183}\u{2183}CI\u{2183}D"
+        );
+        assert_eq!(
+            u32::from_roman_with(
+                "CI\u{2183}CI\u{2183}",
+                RomanNotation::Apostrophus,
+                RomanParsing::Strict
+            ),
+            Ok(2000)
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! The [sign-magnitude](https://en.wikipedia.org/wiki/Signed_number_representations#Sign%E2%80%93magnitude)
+//! view of integers, which lets the conversions between numeral systems share one implementation
+//! for primitive and big integers.
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// Unsigned integers that the absolute values of integers are represented with.
+pub trait Magnitude: Sized + Clone + Ord {
+    fn from_u32(n: u32) -> Self;
+    fn is_zero(&self) -> bool;
+    fn to_u32(&self) -> Option<u32>;
+    /// Divides `self` by `divisor` in place and returns the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is 0.
+    fn div_rem_assign_u32(&mut self, divisor: u32) -> u32;
+    /// Returns `self * factor + addend` or [None] if it does not fit into `Self`.
+    fn checked_mul_add_u32(&self, factor: u32, addend: u32) -> Option<Self>;
+    /// Returns `self - subtrahend`, which must not be negative.
+    fn sub_u32(&self, subtrahend: u32) -> Self;
+    /// Returns the quotient and the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is 0.
+    fn div_rem(&self, divisor: &Self) -> (Self, Self);
+    /// Replaces `self`, which must be less than `divisor`, with `self * factor % divisor` and returns
+    /// the quotient `self * factor / divisor`, which is less than `factor`. This is the step of the long division.
+    fn mul_u32_div_rem_assign(&mut self, factor: u32, divisor: &Self) -> u32;
+}
+
+impl Magnitude for u128 {
+    #[inline(always)]
+    fn from_u32(n: u32) -> Self {
+        u128::from(n)
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    #[inline(always)]
+    fn to_u32(&self) -> Option<u32> {
+        u32::try_from(*self).ok()
+    }
+
+    #[inline]
+    fn div_rem_assign_u32(&mut self, divisor: u32) -> u32 {
+        let divisor = u128::from(divisor);
+        let rem = *self % divisor;
+        *self /= divisor;
+        // the remainder is less than the divisor
+        rem as u32
+    }
+
+    #[inline]
+    fn checked_mul_add_u32(&self, factor: u32, addend: u32) -> Option<Self> {
+        self.checked_mul(u128::from(factor))?
+            .checked_add(u128::from(addend))
+    }
+
+    #[inline(always)]
+    fn sub_u32(&self, subtrahend: u32) -> Self {
+        self - u128::from(subtrahend)
+    }
+
+    #[inline(always)]
+    fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        (self / divisor, self % divisor)
+    }
+
+    fn mul_u32_div_rem_assign(&mut self, factor: u32, divisor: &Self) -> u32 {
+        if let Some(product) = self.checked_mul(u128::from(factor)) {
+            let quotient = product / divisor;
+            *self = product % divisor;
+            // the quotient is less than the factor
+            return quotient as u32;
+        }
+        // the product does not fit into u128, so it is accumulated modulo the divisor
+        // bit by bit from the most significant bit of the factor
+        let (addend, mut quotient, mut rem) = (*self, 0u32, 0u128);
+        for i in (0..u32::BITS - factor.leading_zeros()).rev() {
+            quotient <<= 1;
+            if rem >= divisor - rem {
+                rem -= divisor - rem;
+                quotient += 1;
+            } else {
+                rem <<= 1;
+            }
+            if factor >> i & 1 == 1 {
+                if rem >= divisor - addend {
+                    rem -= divisor - addend;
+                    quotient += 1;
+                } else {
+                    rem += addend;
+                }
+            }
+        }
+        *self = rem;
+        quotient
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl Magnitude for BigUint {
+    #[inline(always)]
+    fn from_u32(n: u32) -> Self {
+        BigUint::from(n)
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        num_traits::Zero::is_zero(self)
+    }
+
+    #[inline(always)]
+    fn to_u32(&self) -> Option<u32> {
+        num_traits::ToPrimitive::to_u32(self)
+    }
+
+    fn div_rem_assign_u32(&mut self, divisor: u32) -> u32 {
+        let rem = &*self % divisor;
+        *self /= divisor;
+        // the remainder is less than the divisor
+        num_traits::ToPrimitive::to_u32(&rem).unwrap()
+    }
+
+    #[inline]
+    fn checked_mul_add_u32(&self, factor: u32, addend: u32) -> Option<Self> {
+        Some(self * factor + addend)
+    }
+
+    #[inline(always)]
+    fn sub_u32(&self, subtrahend: u32) -> Self {
+        self - subtrahend
+    }
+
+    #[inline]
+    fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        (self / divisor, self % divisor)
+    }
+
+    fn mul_u32_div_rem_assign(&mut self, factor: u32, divisor: &Self) -> u32 {
+        let product = &*self * factor;
+        let quotient = &product / divisor;
+        *self = product % divisor;
+        // the quotient is less than the factor
+        num_traits::ToPrimitive::to_u32(&quotient).unwrap()
+    }
+}
+
+/// Integers that can be split into the sign and the absolute value and put together again.
+pub trait SignMagnitude: Sized {
+    type Magnitude: Magnitude;
+
+    /// Returns whether `self` is negative and its absolute value.
+    fn to_sign_magnitude(&self) -> (bool, Self::Magnitude);
+    /// Returns the integer with the given sign and absolute value or [None] if it is not representable.
+    /// The sign of zero is ignored.
+    fn from_sign_magnitude(is_negative: bool, magnitude: Self::Magnitude) -> Option<Self>;
+}
+
+macro_rules! impl_sign_magnitude {
+    (@UNSIGNED [$($t:ty),+]) => {
+        $(
+            impl SignMagnitude for $t {
+                type Magnitude = u128;
+
+                #[inline(always)]
+                fn to_sign_magnitude(&self) -> (bool, u128) {
+                    (false, *self as u128)
+                }
+
+                #[inline]
+                fn from_sign_magnitude(is_negative: bool, magnitude: u128) -> Option<Self> {
+                    if is_negative && magnitude != 0 {
+                        return None;
+                    }
+                    <$t>::try_from(magnitude).ok()
+                }
+            }
+        )+
+    };
+    (@SIGNED [$($t:ty),+]) => {
+        $(
+            impl SignMagnitude for $t {
+                type Magnitude = u128;
+
+                #[inline(always)]
+                fn to_sign_magnitude(&self) -> (bool, u128) {
+                    (*self < 0, self.unsigned_abs() as u128)
+                }
+
+                #[inline]
+                fn from_sign_magnitude(is_negative: bool, magnitude: u128) -> Option<Self> {
+                    let n = if is_negative {
+                        0i128.checked_sub_unsigned(magnitude)?
+                    } else {
+                        i128::try_from(magnitude).ok()?
+                    };
+                    <$t>::try_from(n).ok()
+                }
+            }
+        )+
+    };
+}
+
+impl_sign_magnitude!(@UNSIGNED [u8, u16, u32, u64, u128, usize]);
+impl_sign_magnitude!(@SIGNED [i8, i16, i32, i64, i128, isize]);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl SignMagnitude for BigUint {
+    type Magnitude = BigUint;
+
+    #[inline(always)]
+    fn to_sign_magnitude(&self) -> (bool, BigUint) {
+        (false, self.clone())
+    }
+
+    #[inline]
+    fn from_sign_magnitude(is_negative: bool, magnitude: BigUint) -> Option<Self> {
+        if is_negative && !Magnitude::is_zero(&magnitude) {
+            return None;
+        }
+        Some(magnitude)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl SignMagnitude for BigInt {
+    type Magnitude = BigUint;
+
+    #[inline(always)]
+    fn to_sign_magnitude(&self) -> (bool, BigUint) {
+        (self.sign() == Sign::Minus, self.magnitude().clone())
+    }
+
+    #[inline]
+    fn from_sign_magnitude(is_negative: bool, magnitude: BigUint) -> Option<Self> {
+        let sign = if is_negative { Sign::Minus } else { Sign::Plus };
+        Some(BigInt::from_biguint(sign, magnitude))
+    }
+}
+
+/// A signed accumulator for evaluating the representations with Horner's method.
+#[derive(Clone, Debug)]
+pub(crate) struct SignedAcc<M> {
+    pub(crate) is_negative: bool,
+    pub(crate) magnitude: M,
+}
+
+impl<M: Magnitude> SignedAcc<M> {
+    #[inline]
+    pub(crate) fn zero() -> Self {
+        Self {
+            is_negative: false,
+            magnitude: M::from_u32(0),
+        }
+    }
+
+    /// Returns `self * factor + addend`, where the factors and the addends are given by their signs
+    /// and absolute values, or [None] on overflow.
+    pub(crate) fn checked_mul_add(
+        &self,
+        (factor_is_negative, factor): (bool, u32),
+        (addend_is_negative, addend): (bool, u32),
+    ) -> Option<Self> {
+        let product = self.magnitude.checked_mul_add_u32(factor, 0)?;
+        if product.is_zero() {
+            return Some(Self {
+                is_negative: addend_is_negative && addend != 0,
+                magnitude: M::from_u32(addend),
+            });
+        }
+        let product_is_negative = self.is_negative != factor_is_negative;
+        if product_is_negative == addend_is_negative {
+            return Some(Self {
+                is_negative: product_is_negative,
+                magnitude: product.checked_mul_add_u32(1, addend)?,
+            });
+        }
+        Some(match product.to_u32() {
+            Some(p) if p < addend => Self {
+                is_negative: addend_is_negative,
+                magnitude: M::from_u32(addend - p),
+            },
+            Some(p) if p == addend => Self::zero(),
+            _ => Self {
+                is_negative: product_is_negative,
+                magnitude: product.sub_u32(addend),
+            },
+        })
+    }
+
+    #[inline]
+    pub(crate) fn into_int<T: SignMagnitude<Magnitude = M>>(self) -> Option<T> {
+        T::from_sign_magnitude(self.is_negative, self.magnitude)
+    }
+}
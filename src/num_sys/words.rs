@@ -0,0 +1,382 @@
+//! English words for integers in the [short scale](https://en.wikipedia.org/wiki/Long_and_short_scales),
+//! up to the vigintillions (`10^63`).
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::words::{AndConvention, NumberForm, ToWords, WordsOptions};
+//!
+//! assert_eq!(123.to_words(WordsOptions::default()).unwrap(), "one hundred twenty-three");
+//! let british_ordinal = WordsOptions {
+//!     form: NumberForm::Ordinal,
+//!     and: AndConvention::British,
+//! };
+//! assert_eq!(
+//!     1021u16.to_words(british_ordinal).unwrap(),
+//!     "one thousand and twenty-first"
+//! );
+//! ```
+
+use crate::num_sys::base_10::int::new_vec_u8_of_digits_base_10::U8DigitsBase10BeInto;
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+use max_len_base_10_as_usize::MaxLenBase10AsUsize;
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint, Sign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NumberForm {
+    /// "twenty-one"
+    #[default]
+    Cardinal,
+    /// "twenty-first"
+    Ordinal,
+}
+
+/// The convention of inserting "and" before the tens and units.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AndConvention {
+    /// "one hundred one", "one thousand five"
+    #[default]
+    Us,
+    /// "one hundred and one", "one thousand and five"
+    British,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WordsOptions {
+    pub form: NumberForm,
+    pub and: AndConvention,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WordsError {
+    /// The absolute value is at least `10^66`, which has no short-scale name.
+    OutOfRange,
+    /// The writer has failed.
+    Fmt(fmt::Error),
+}
+
+impl Display for WordsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordsError::OutOfRange => f.write_str("the value is too large to be named"),
+            WordsError::Fmt(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(any(doc, test, doctest, feature = "std"))]
+impl std::error::Error for WordsError {}
+
+impl From<fmt::Error> for WordsError {
+    #[inline(always)]
+    fn from(e: fmt::Error) -> Self {
+        WordsError::Fmt(e)
+    }
+}
+
+pub trait ToWords {
+    fn write_words<W: Write>(&self, w: &mut W, options: WordsOptions) -> Result<(), WordsError>;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(any(doc, test, doctest, feature = "alloc"))]
+    fn to_words(&self, options: WordsOptions) -> Result<String, WordsError> {
+        let mut s = String::new();
+        self.write_words(&mut s, options)?;
+        Ok(s)
+    }
+}
+
+const UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+// The names of 1000^i
+const SCALES: [&str; 22] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+    "duodecillion",
+    "tredecillion",
+    "quattuordecillion",
+    "quindecillion",
+    "sexdecillion",
+    "septendecillion",
+    "octodecillion",
+    "novemdecillion",
+    "vigintillion",
+];
+const MAX_DIGITS: usize = 3 * SCALES.len();
+
+// Writes the words separated by the given separators, holding back the last word
+// to write it in the requested form
+struct WordEmitter<'a, W: Write> {
+    w: &'a mut W,
+    pending: Option<&'static str>,
+}
+
+impl<W: Write> WordEmitter<'_, W> {
+    fn emit(&mut self, separator: &str, word: &'static str) -> fmt::Result {
+        if let Some(prev) = self.pending.replace(word) {
+            self.w.write_str(prev)?;
+            self.w.write_str(separator)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self, form: NumberForm) -> fmt::Result {
+        let word = self.pending.unwrap_or(UNITS[0]);
+        if form == NumberForm::Cardinal {
+            return self.w.write_str(word);
+        }
+        let irregular = match word {
+            "one" => Some("first"),
+            "two" => Some("second"),
+            "three" => Some("third"),
+            "five" => Some("fifth"),
+            "eight" => Some("eighth"),
+            "nine" => Some("ninth"),
+            "twelve" => Some("twelfth"),
+            _ => None,
+        };
+        match (irregular, word.strip_suffix('y')) {
+            (Some(ordinal), _) => self.w.write_str(ordinal),
+            (None, Some(stem)) => {
+                self.w.write_str(stem)?;
+                self.w.write_str("ieth")
+            }
+            (None, None) => {
+                self.w.write_str(word)?;
+                self.w.write_str("th")
+            }
+        }
+    }
+}
+
+fn write_words<W: Write>(
+    w: &mut W,
+    is_negative: bool,
+    digits_be: &[u8],
+    options: WordsOptions,
+) -> Result<(), WordsError> {
+    let digits_be = &digits_be[digits_be.iter().take_while(|&&d| d == 0).count()..];
+    if digits_be.len() > MAX_DIGITS {
+        return Err(WordsError::OutOfRange);
+    }
+    if is_negative && !digits_be.is_empty() {
+        w.write_str("minus ")?;
+    }
+    let and = match options.and {
+        AndConvention::Us => " ",
+        AndConvention::British => " and ",
+    };
+    let mut emitter = WordEmitter { w, pending: None };
+    let group_count = digits_be.len().div_ceil(3);
+    let mut separator = "";
+    for scale in (0..group_count).rev() {
+        // the digits of the group of 1000^scale
+        let end = digits_be.len() - 3 * scale;
+        let group = &digits_be[end.saturating_sub(3)..end];
+        let group = group
+            .iter()
+            .fold(0usize, |acc, &d| acc * 10 + usize::from(d));
+        if group == 0 {
+            continue;
+        }
+        let (hundreds, rest) = (group / 100, group % 100);
+        if hundreds != 0 {
+            emitter.emit(separator, UNITS[hundreds])?;
+            emitter.emit(" ", "hundred")?;
+            separator = and;
+        } else if scale == 0 && !separator.is_empty() {
+            // "one thousand and five" in the British convention
+            separator = and;
+        }
+        if rest != 0 {
+            if rest < 20 {
+                emitter.emit(separator, UNITS[rest])?;
+            } else {
+                emitter.emit(separator, TENS[rest / 10])?;
+                if rest % 10 != 0 {
+                    emitter.emit("-", UNITS[rest % 10])?;
+                }
+            }
+        }
+        if scale != 0 {
+            emitter.emit(" ", SCALES[scale])?;
+        }
+        separator = " ";
+    }
+    emitter.finish(options.form)?;
+    Ok(())
+}
+
+macro_rules! impl_to_words {
+    ($($t:ty),+) => {
+        $(
+            impl ToWords for $t {
+                fn write_words<W: Write>(
+                    &self,
+                    w: &mut W,
+                    options: WordsOptions,
+                ) -> Result<(), WordsError> {
+                    let mut buf = [0u8; <$t as MaxLenBase10AsUsize>::MAX_LEN_BASE_10_AS_USIZE];
+                    // the buffer fits all digits of the type
+                    let len = self.u8_digits_be_into(&mut buf).unwrap();
+                    #[allow(unused_comparisons)]
+                    let is_negative = *self < 0;
+                    write_words(w, is_negative, &buf[..len], options)
+                }
+            }
+        )+
+    };
+}
+
+impl_to_words!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl ToWords for BigUint {
+    fn write_words<W: Write>(&self, w: &mut W, options: WordsOptions) -> Result<(), WordsError> {
+        // a cheap bound that rejects the huge values before the conversion
+        if self.bits() > 4 * MAX_DIGITS as u64 {
+            return Err(WordsError::OutOfRange);
+        }
+        write_words(w, false, &self.to_radix_be(10), options)
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl ToWords for BigInt {
+    #[inline]
+    fn write_words<W: Write>(&self, w: &mut W, options: WordsOptions) -> Result<(), WordsError> {
+        if self.sign() == Sign::Minus {
+            // the check of the range precedes any output
+            if self.magnitude().bits() > 4 * MAX_DIGITS as u64 {
+                return Err(WordsError::OutOfRange);
+            }
+            let digits = self.magnitude().to_radix_be(10);
+            return write_words(w, true, &digits, options);
+        }
+        self.magnitude().write_words(w, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::words::*;
+
+    const ORDINAL: WordsOptions = WordsOptions {
+        form: NumberForm::Ordinal,
+        and: AndConvention::Us,
+    };
+    const BRITISH: WordsOptions = WordsOptions {
+        form: NumberForm::Cardinal,
+        and: AndConvention::British,
+    };
+
+    #[test]
+    fn cardinal_words_work_for_primitive_integers() {
+        let us = WordsOptions::default();
+        assert_eq!(0u8.to_words(us).unwrap(), "zero");
+        assert_eq!(13i8.to_words(us).unwrap(), "thirteen");
+        assert_eq!(40u8.to_words(us).unwrap(), "forty");
+        assert_eq!(
+            (-128i8).to_words(us).unwrap(),
+            "minus one hundred twenty-eight"
+        );
+        assert_eq!(1_000_017u32.to_words(us).unwrap(), "one million seventeen");
+        assert_eq!(
+            u128::MAX
+                .to_words(us)
+                .unwrap()
+                .split(' ')
+                .take(5)
+                .collect::<Vec<_>>(),
+            ["three", "hundred", "forty", "undecillion", "two"]
+        );
+        assert_eq!(101u8.to_words(BRITISH).unwrap(), "one hundred and one");
+        assert_eq!(1005u16.to_words(BRITISH).unwrap(), "one thousand and five");
+        assert_eq!(
+            2_300_040u32.to_words(BRITISH).unwrap(),
+            "two million three hundred thousand and forty"
+        );
+    }
+
+    #[test]
+    fn ordinal_words_work_for_primitive_integers() {
+        let cases = [
+            (0, "zeroth"),
+            (1, "first"),
+            (2, "second"),
+            (3, "third"),
+            (4, "fourth"),
+            (5, "fifth"),
+            (8, "eighth"),
+            (9, "ninth"),
+            (11, "eleventh"),
+            (12, "twelfth"),
+            (20, "twentieth"),
+            (21, "twenty-first"),
+            (100, "one hundredth"),
+            (1_000_000, "one millionth"),
+            (-2, "minus second"),
+        ];
+        for (n, words) in cases {
+            assert_eq!(n.to_words(ORDINAL).unwrap(), words);
+        }
+    }
+
+    #[test]
+    fn words_work_for_big_integers() {
+        use num_bigint::{BigInt, BigUint};
+
+        let vigintillion = BigUint::from(10u32).pow(63);
+        assert_eq!(
+            vigintillion.to_words(WordsOptions::default()).unwrap(),
+            "one vigintillion"
+        );
+        assert_eq!(
+            (-BigInt::from(vigintillion.clone() * 999u32 + 1u32))
+                .to_words(ORDINAL)
+                .unwrap(),
+            "minus nine hundred ninety-nine vigintillion first"
+        );
+        assert_eq!(
+            (vigintillion * 1000u32).to_words(WordsOptions::default()),
+            Err(WordsError::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! The [Zeckendorf representation](https://en.wikipedia.org/wiki/Zeckendorf%27s_theorem) of positive integers as
+//! the sums of non-consecutive Fibonacci numbers and the [Fibonacci coding](https://en.wikipedia.org/wiki/Fibonacci_coding),
+//! the universal code built on it.
+//!
+//! The Fibonacci numbers are indexed from `F(1) = F(2) = 1`, and the representations use the indices from 2.
+//!
+//! ```rust
+//! use dd_maths_traits::num_sys::zeckendorf::{FibonacciCoding, Zeckendorf};
+//!
+//! // 100 = 89 + 8 + 3 = F(11) + F(6) + F(4)
+//! let terms: Vec<(u32, u64)> = 100u64.zeckendorf_terms().collect();
+//! assert_eq!(terms, [(11, 89), (6, 8), (4, 3)]);
+//! assert_eq!(u64::from_zeckendorf_indices([11, 6, 4]), Some(100));
+//!
+//! // the bits for F(2), F(3), ... followed by the terminating 1
+//! let code: Vec<bool> = 4u64.fibonacci_code().unwrap().collect();
+//! assert_eq!(code, [true, false, true, true]);
+//! assert_eq!(u64::decode_fibonacci_code(&mut code.into_iter()), Some(4));
+//! ```
+
+use num_traits::{CheckedAdd, CheckedSub, Unsigned};
+
+/// The iterator over the indices and the values of the Fibonacci numbers in the Zeckendorf representation,
+/// from the greatest one. Zero has no terms.
+#[derive(Clone, Debug)]
+pub struct ZeckendorfTerms<T> {
+    rest: T,
+    // F(index - 1) and F(index)
+    lower: T,
+    upper: T,
+    index: u32,
+}
+
+impl<T: Unsigned + Clone + Ord + CheckedAdd + CheckedSub> ZeckendorfTerms<T> {
+    fn new(n: T) -> Self {
+        let (mut lower, mut upper, mut index) = (T::one(), T::one(), 2);
+        // the greatest Fibonacci number not exceeding n
+        while let Some(next) = lower.checked_add(&upper).filter(|next| *next <= n) {
+            lower = core::mem::replace(&mut upper, next);
+            index += 1;
+        }
+        Self {
+            rest: n,
+            lower,
+            upper,
+            index,
+        }
+    }
+
+    // Can be called only while `index` is at least 3
+    fn step_down(&mut self) {
+        let lower = self.upper.clone() - self.lower.clone();
+        self.upper = core::mem::replace(&mut self.lower, lower);
+        self.index -= 1;
+    }
+}
+
+impl<T: Unsigned + Clone + Ord + CheckedAdd + CheckedSub> Iterator for ZeckendorfTerms<T> {
+    type Item = (u32, T);
+
+    fn next(&mut self) -> Option<(u32, T)> {
+        while !self.rest.is_zero() {
+            if self.upper <= self.rest {
+                self.rest = self.rest.clone() - self.upper.clone();
+                let term = (self.index, self.upper.clone());
+                // the rest is less than F(index - 1), so the next term is at most F(index - 2),
+                // which is at least F(2) when the rest is not zero
+                if !self.rest.is_zero() {
+                    self.step_down();
+                    self.step_down();
+                }
+                return Some(term);
+            }
+            // the rest is less than the current Fibonacci number, which is thus greater than 1
+            self.step_down();
+        }
+        None
+    }
+}
+
+pub trait Zeckendorf: Sized {
+    /// Returns the terms of the Zeckendorf representation from the greatest one.
+    fn zeckendorf_terms(&self) -> ZeckendorfTerms<Self>;
+
+    /// Returns the sum of the Fibonacci numbers with the given indices or [None] if the indices are not
+    /// a Zeckendorf representation, i.e. they are not decreasing by at least 2 down to at least 2,
+    /// or the sum is not representable by `Self`.
+    fn from_zeckendorf_indices<I: IntoIterator<Item = u32>>(indices: I) -> Option<Self>;
+}
+
+impl<T: Unsigned + Clone + Ord + CheckedAdd + CheckedSub> Zeckendorf for T {
+    fn zeckendorf_terms(&self) -> ZeckendorfTerms<T> {
+        ZeckendorfTerms::new(self.clone())
+    }
+
+    fn from_zeckendorf_indices<I: IntoIterator<Item = u32>>(indices: I) -> Option<Self> {
+        let mut indices = indices.into_iter();
+        let Some(first) = indices.next() else {
+            return Some(T::zero());
+        };
+        if first < 2 {
+            return None;
+        }
+        let (mut lower, mut upper) = (T::one(), T::one());
+        for _ in 2..first {
+            let next = lower.checked_add(&upper)?;
+            lower = core::mem::replace(&mut upper, next);
+        }
+        let mut terms = ZeckendorfTerms {
+            rest: T::zero(),
+            lower,
+            upper,
+            index: first,
+        };
+        let mut sum = terms.upper.clone();
+        for index in indices {
+            if index < 2 || index + 2 > terms.index {
+                return None;
+            }
+            while terms.index > index {
+                terms.step_down();
+            }
+            sum = sum.checked_add(&terms.upper)?;
+        }
+        Some(sum)
+    }
+}
+
+/// The iterator over the bits of the Fibonacci code, i.e. the Zeckendorf representation from the least
+/// significant bit, for `F(2)`, followed by the terminating 1.
+#[derive(Clone, Debug)]
+pub struct FibonacciCode<T> {
+    rest: T,
+    // the least term of the Zeckendorf representation of the rest
+    least_term: Option<(u32, T)>,
+    index: u32,
+    is_done: bool,
+}
+
+impl<T: Unsigned + Clone + Ord + CheckedAdd + CheckedSub> Iterator for FibonacciCode<T> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.is_done {
+            return None;
+        }
+        match self.least_term.take() {
+            Some((index, term)) if index == self.index => {
+                self.rest = self.rest.clone() - term;
+                self.least_term = self.rest.zeckendorf_terms().last();
+                self.index += 1;
+                Some(true)
+            }
+            Some(least_term) => {
+                self.least_term = Some(least_term);
+                self.index += 1;
+                Some(false)
+            }
+            None => {
+                self.is_done = true;
+                Some(true)
+            }
+        }
+    }
+}
+
+pub trait FibonacciCoding: Sized {
+    /// Returns the bits of the Fibonacci code or [None] for zero, which has no code.
+    fn fibonacci_code(&self) -> Option<FibonacciCode<Self>>;
+
+    /// Consumes the bits of one Fibonacci code, up to and including the terminating `11`, so that the codes
+    /// can be decoded from a stream one by one.
+    ///
+    /// Returns [None] if the bits end before the code does or the value is not representable by `Self`.
+    fn decode_fibonacci_code<I: Iterator<Item = bool>>(bits: &mut I) -> Option<Self>;
+}
+
+impl<T: Unsigned + Clone + Ord + CheckedAdd + CheckedSub> FibonacciCoding for T {
+    fn fibonacci_code(&self) -> Option<FibonacciCode<Self>> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(FibonacciCode {
+            rest: self.clone(),
+            least_term: self.zeckendorf_terms().last(),
+            index: 2,
+            is_done: false,
+        })
+    }
+
+    fn decode_fibonacci_code<I: Iterator<Item = bool>>(bits: &mut I) -> Option<Self> {
+        let mut sum = T::zero();
+        // F(index) and F(index + 1), which may not be representable by `T` past the last term
+        let (mut current, mut next) = (Some(T::one()), T::one().checked_add(&T::one()));
+        let mut previous_bit = false;
+        loop {
+            let bit = bits.next()?;
+            if bit && previous_bit {
+                return Some(sum);
+            }
+            if bit {
+                sum = sum.checked_add(current.as_ref()?)?;
+            }
+            previous_bit = bit;
+            let after_next = match (&current, &next) {
+                (Some(current), Some(next)) => current.checked_add(next),
+                _ => None,
+            };
+            current = core::mem::replace(&mut next, after_next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::num_sys::zeckendorf::{FibonacciCoding, Zeckendorf};
+
+    #[test]
+    fn zeckendorf_representations_round_trip() {
+        for n in 0u32..=10_000 {
+            let terms: Vec<(u32, u32)> = n.zeckendorf_terms().collect();
+            assert_eq!(terms.iter().map(|(_, f)| f).sum::<u32>(), n);
+            assert!(terms.windows(2).all(|w| w[0].0 >= w[1].0 + 2));
+            assert!(terms.iter().all(|&(i, _)| i >= 2));
+            let indices = terms.iter().map(|&(i, _)| i);
+            assert_eq!(u32::from_zeckendorf_indices(indices), Some(n));
+        }
+        for n in [u64::MAX, u64::MAX - 1, 12_200_160_415_121_876_738] {
+            let indices: Vec<u32> = n.zeckendorf_terms().map(|(i, _)| i).collect();
+            assert_eq!(u64::from_zeckendorf_indices(indices), Some(n));
+        }
+        // consecutive and repeated indices
+        assert_eq!(u32::from_zeckendorf_indices([5, 4]), None);
+        assert_eq!(u32::from_zeckendorf_indices([5, 5]), None);
+        assert_eq!(u32::from_zeckendorf_indices([3, 1]), None);
+        // F(14) = 377
+        assert_eq!(u8::from_zeckendorf_indices([14]), None);
+    }
+
+    #[test]
+    fn fibonacci_codes_round_trip() {
+        let cases: [(u32, &str); 6] = [
+            (1, "11"),
+            (2, "011"),
+            (3, "0011"),
+            (4, "1011"),
+            (11, "001011"),
+            (12, "101011"),
+        ];
+        for (n, code) in cases {
+            let bits: String = n
+                .fibonacci_code()
+                .unwrap()
+                .map(|b| if b { '1' } else { '0' })
+                .collect();
+            assert_eq!(bits, code);
+        }
+        assert!(0u32.fibonacci_code().is_none());
+
+        // the codes are self-delimiting
+        let values = [1u128, 7, 1000, u128::MAX, 65, u128::MAX - 5];
+        let mut stream = values.iter().flat_map(|n| n.fibonacci_code().unwrap());
+        for &n in &values {
+            assert_eq!(u128::decode_fibonacci_code(&mut stream), Some(n));
+        }
+        assert_eq!(u128::decode_fibonacci_code(&mut stream), None);
+        assert_eq!(
+            u8::decode_fibonacci_code(&mut [true, false].into_iter()),
+            None
+        );
+
+        use num_bigint::BigUint;
+
+        let n = BigUint::from(10u32).pow(100) + 1u32;
+        let bits: Vec<bool> = n.fibonacci_code().unwrap().collect();
+        assert_eq!(
+            BigUint::decode_fibonacci_code(&mut bits.into_iter()),
+            Some(n)
+        );
+    }
+}
@@ -0,0 +1,875 @@
+//! [Integer factorization](https://en.wikipedia.org/wiki/Integer_factorization) into the prime powers.
+//!
+//! The primitive integers are factorized by the trial division by the primes below 2^10 and then by
+//! [Pollard's rho algorithm](https://en.wikipedia.org/wiki/Pollard%27s_rho_algorithm) with Brent's cycle detection,
+//! while the factors are recognized as primes with the deterministic tests of [IsPrime], so the factorization is
+//! exact and quick as long as the second greatest prime factor is below about 2^40. The big integers are factorized
+//! by the shortened Pollard's rho algorithm and then by
+//! [Lenstra's elliptic-curve factorization](https://en.wikipedia.org/wiki/Lenstra_elliptic-curve_factorization)
+//! with the effort bounded by [EcmLimits] in [FactorizeWithLimits].
+//!
+//! ```rust
+//! use dd_maths_traits::number_theory::factorization::{Factorization, Factorize};
+//! use dd_maths_traits::number_theory::gcd::{Gcd, Lcm};
+//!
+//! let a = 360u64.factorize().unwrap();
+//! assert_eq!(a.factors(), [(2, 3), (3, 2), (5, 1)]);
+//! assert_eq!(a.value(), Some(360));
+//! assert_eq!(a.divisors().unwrap().count(), 24);
+//!
+//! let b = 84u64.factorize().unwrap();
+//! assert_eq!(a.gcd(&b).value(), Some(12));
+//! assert_eq!(a.lcm(&b).value(), Some(2520));
+//! assert_eq!((a * b).value(), Some(30_240));
+//!
+//! assert_eq!(
+//!     18_446_744_073_709_551_615u64.factorize().unwrap().factors(),
+//!     [(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65_537, 1), (6_700_417, 1)]
+//! );
+//! assert_eq!(0u64.factorize(), None);
+//! ```
+
+use crate::number_theory::{
+    gcd::{Gcd, Lcm},
+    modular::{DynModInt, Modulus},
+    primality::{IsPrime, Montgomery128, OddModulus},
+    primes::SMALL_PRIMES,
+};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use crate::number_theory::{primality::BigOddModulus, primes::PrimesInRange};
+use alloc::vec::Vec;
+use core::{
+    cmp::Ordering,
+    ops::{Mul, MulAssign},
+};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::BigUint;
+use num_traits::{CheckedMul, One};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_traits::{ToPrimitive, Zero};
+
+/// The factorization of a positive integer into the increasing primes with the positive exponents.
+///
+/// The product of two factorizations adds the exponents, and the greatest common divisor and the least common
+/// multiple take the minimum and the maximum of them, so they are never 0 and can exceed the maximum of `T`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Factorization<T> {
+    factors: Vec<(T, u32)>,
+}
+
+impl<T> Factorization<T> {
+    /// Returns the factorization of 1.
+    #[inline]
+    pub const fn one() -> Self {
+        Self {
+            factors: Vec::new(),
+        }
+    }
+
+    /// Returns the increasing primes with the positive exponents.
+    #[inline(always)]
+    pub fn factors(&self) -> &[(T, u32)] {
+        &self.factors
+    }
+
+    #[inline(always)]
+    pub fn into_factors(self) -> Vec<(T, u32)> {
+        self.factors
+    }
+
+    #[inline]
+    pub fn is_one(&self) -> bool {
+        self.factors.is_empty()
+    }
+
+    /// Returns the number of the divisors or [None] if it exceeds [u64::MAX].
+    pub fn divisor_count(&self) -> Option<u64> {
+        self.factors
+            .iter()
+            .try_fold(1u64, |count, (_, e)| count.checked_mul(u64::from(*e) + 1))
+    }
+}
+
+impl<T: Ord> Factorization<T> {
+    /// Returns the factorization of the product of the powers of the primes, which are not checked for primality
+    /// and can repeat in any order.
+    pub fn from_prime_powers<I: IntoIterator<Item = (T, u32)>>(powers: I) -> Self {
+        let mut powers: Vec<(T, u32)> = powers.into_iter().filter(|(_, e)| *e != 0).collect();
+        powers.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let mut factors: Vec<(T, u32)> = Vec::with_capacity(powers.len());
+        for (p, e) in powers {
+            match factors.last_mut() {
+                Some((last, exponent)) if *last == p => *exponent += e,
+                _ => factors.push((p, e)),
+            }
+        }
+        Self { factors }
+    }
+}
+
+impl<T: Ord + Clone> Factorization<T> {
+    // The factors of both factorizations with the combined exponents, where the missing ones are 0
+    fn merge(&self, other: &Self, combine: impl Fn(u32, u32) -> u32) -> Self {
+        let (mut a, mut b) = (
+            self.factors.iter().peekable(),
+            other.factors.iter().peekable(),
+        );
+        let mut factors = Vec::with_capacity(self.factors.len() + other.factors.len());
+        loop {
+            let (p, e) = match (a.peek(), b.peek()) {
+                (None, None) => break,
+                (Some((p, e)), None) => (p, combine(*e, 0)),
+                (None, Some((q, f))) => (q, combine(0, *f)),
+                (Some((p, e)), Some((q, f))) => match p.cmp(q) {
+                    Ordering::Less => (p, combine(*e, 0)),
+                    Ordering::Greater => (q, combine(0, *f)),
+                    Ordering::Equal => (p, combine(*e, *f)),
+                },
+            };
+            let p = p.clone();
+            if a.peek().is_some_and(|(q, _)| *q == p) {
+                a.next();
+            }
+            if b.peek().is_some_and(|(q, _)| *q == p) {
+                b.next();
+            }
+            if e != 0 {
+                factors.push((p, e));
+            }
+        }
+        Self { factors }
+    }
+}
+
+impl<T: Clone + CheckedMul + One> Factorization<T> {
+    /// Returns the factorized integer or [None] if it is not representable.
+    pub fn value(&self) -> Option<T> {
+        self.factors.iter().try_fold(T::one(), |acc, (p, e)| {
+            (0..*e).try_fold(acc, |acc, _| acc.checked_mul(p))
+        })
+    }
+
+    /// Returns the iterator over the divisors, which starts with 1 and is not increasing,
+    /// or [None] if the factorized integer is not representable.
+    pub fn divisors(&self) -> Option<Divisors<'_, T>> {
+        self.value()?;
+        Some(Divisors {
+            factors: &self.factors,
+            exponents: alloc::vec![0; self.factors.len()],
+            products: alloc::vec![T::one(); self.factors.len() + 1],
+            is_done: false,
+        })
+    }
+}
+
+impl<T> Default for Factorization<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::one()
+    }
+}
+
+impl<T: Ord + Clone> Mul for Factorization<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        self.merge(&other, |e, f| e + f)
+    }
+}
+
+impl<T: Ord + Clone> Mul<&Factorization<T>> for &Factorization<T> {
+    type Output = Factorization<T>;
+
+    #[inline]
+    fn mul(self, other: &Factorization<T>) -> Factorization<T> {
+        self.merge(other, |e, f| e + f)
+    }
+}
+
+impl<T: Ord + Clone> MulAssign for Factorization<T> {
+    #[inline]
+    fn mul_assign(&mut self, other: Self) {
+        *self = self.merge(&other, |e, f| e + f);
+    }
+}
+
+impl<T: Ord + Clone> Gcd for Factorization<T> {
+    #[inline]
+    fn checked_gcd(&self, other: &Self) -> Option<Self> {
+        Some(self.merge(other, u32::min))
+    }
+}
+
+impl<T: Ord + Clone> Lcm for Factorization<T> {
+    #[inline]
+    fn checked_lcm(&self, other: &Self) -> Option<Self> {
+        Some(self.merge(other, u32::max))
+    }
+}
+
+/// The iterator over the divisors of a [Factorization].
+#[derive(Clone, Debug)]
+pub struct Divisors<'a, T> {
+    factors: &'a [(T, u32)],
+    // the exponents of the next divisor, the least significant one first, and the products of the powers
+    // from each of them on, so that the divisor is the first product
+    exponents: Vec<u32>,
+    products: Vec<T>,
+    is_done: bool,
+}
+
+impl<T: Clone + CheckedMul> Iterator for Divisors<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.is_done {
+            return None;
+        }
+        let divisor = self.products[0].clone();
+        let next = (0..self.factors.len()).find(|&i| self.exponents[i] < self.factors[i].1);
+        match next {
+            Some(i) => {
+                self.exponents[i] += 1;
+                self.exponents[..i].fill(0);
+                // the divisors do not exceed the representable integer
+                self.products[i] = self.products[i].clone() * self.factors[i].0.clone();
+                for j in 0..i {
+                    self.products[j] = self.products[i].clone();
+                }
+            }
+            None => self.is_done = true,
+        }
+        Some(divisor)
+    }
+}
+
+pub trait Factorize: Sized {
+    /// Returns the factorization into the primes or [None] for 0.
+    fn factorize(&self) -> Option<Factorization<Self>>;
+}
+
+// The gcd of a residue and the modulus n, which splits n if it is neither 1 nor n
+enum Split<T> {
+    Trivial,
+    Factor(T),
+    Whole,
+}
+
+impl<T: PartialEq + One> Split<T> {
+    fn new(gcd: T, n: &T) -> Self {
+        if gcd.is_one() {
+            Split::Trivial
+        } else if gcd == *n {
+            Split::Whole
+        } else {
+            Split::Factor(gcd)
+        }
+    }
+}
+
+// Pollard's rho algorithm for x ↦ x² + c with Brent's cycle detection, which multiplies the differences in
+// the batches before taking their gcd with the modulus, and returns [None] if the cycle closes without splitting
+// the modulus or after about max_steps steps
+fn pollard_brent<M: OddModulus, T>(
+    n: &M,
+    c: u64,
+    max_steps: u64,
+    split: impl Fn(&M::Residue) -> Split<T>,
+) -> Option<T> {
+    const BATCH: u64 = 128;
+    let c = n.residue(c);
+    let f = |x: &M::Residue| n.add(&n.mul(x, x), &c);
+    let (mut y, mut product) = (n.residue(2), n.residue(1));
+    let mut r = 1u64;
+    while r <= max_steps {
+        let x = y.clone();
+        for _ in 0..r {
+            y = f(&y);
+        }
+        let mut k = 0;
+        while k < r {
+            let batch_start = y.clone();
+            for _ in 0..BATCH.min(r - k) {
+                y = f(&y);
+                product = n.mul(&product, &n.sub(&x, &y));
+            }
+            match split(&product) {
+                Split::Trivial => k += BATCH,
+                Split::Factor(d) => return Some(d),
+                Split::Whole => {
+                    // the batch went past a factor, so it is repeated difference by difference
+                    let mut y = batch_start;
+                    loop {
+                        y = f(&y);
+                        match split(&n.sub(&x, &y)) {
+                            Split::Trivial => {}
+                            Split::Factor(d) => return Some(d),
+                            Split::Whole => return None,
+                        }
+                    }
+                }
+            }
+        }
+        r = r.checked_mul(2)?;
+    }
+    None
+}
+
+// The primes below 2^10 for the trial division
+fn trial_primes() -> impl Iterator<Item = u16> {
+    SMALL_PRIMES.iter().copied().take_while(|&p| p < 1 << 10)
+}
+
+fn factorize_u64(mut n: u64, factors: &mut Vec<(u64, u32)>) {
+    for p in trial_primes().map(u64::from) {
+        if p * p > n {
+            break;
+        }
+        let mut e = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            e += 1;
+        }
+        if e != 0 {
+            factors.push((p, e));
+        }
+    }
+    split_u64(n, factors);
+}
+
+// Factorizes n, which is 1, a prime or an odd composite without the factors below 2^10
+fn split_u64(n: u64, factors: &mut Vec<(u64, u32)>) {
+    if n == 1 {
+        return;
+    }
+    if n.is_prime() {
+        factors.push((n, 1));
+        return;
+    }
+    let modulus = Modulus::new(n).unwrap();
+    let d = (1..)
+        .find_map(|c| {
+            pollard_brent(&modulus, c, u64::MAX, |a: &DynModInt| {
+                Split::new(a.value().gcd(&n), &n)
+            })
+        })
+        .unwrap();
+    split_u64(d, factors);
+    split_u64(n / d, factors);
+}
+
+fn factorize_u128(mut n: u128, factors: &mut Vec<(u128, u32)>) {
+    for p in trial_primes().map(u128::from) {
+        if p * p > n {
+            break;
+        }
+        let mut e = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            e += 1;
+        }
+        if e != 0 {
+            factors.push((p, e));
+        }
+    }
+    split_u128(n, factors);
+}
+
+fn split_u128(n: u128, factors: &mut Vec<(u128, u32)>) {
+    if let Ok(n) = u64::try_from(n) {
+        let mut small = Vec::new();
+        split_u64(n, &mut small);
+        factors.extend(small.into_iter().map(|(p, e)| (u128::from(p), e)));
+        return;
+    }
+    if n.is_prime() {
+        factors.push((n, 1));
+        return;
+    }
+    // the residues are in the Montgomery form, which keeps their gcd with n
+    let modulus = Montgomery128::new(n);
+    let d = (1..)
+        .find_map(|c| pollard_brent(&modulus, c, u64::MAX, |a: &u128| Split::new(a.gcd(&n), &n)))
+        .unwrap();
+    split_u128(d, factors);
+    split_u128(n / d, factors);
+}
+
+macro_rules! impl_factorize_for_prim_unsigned_int {
+    ($(($t:ty, $factorize:ident, $as:ty)),+) => {
+        $(
+            impl Factorize for $t {
+                fn factorize(&self) -> Option<Factorization<Self>> {
+                    if *self == 0 {
+                        return None;
+                    }
+                    let mut factors = Vec::new();
+                    $factorize(*self as $as, &mut factors);
+                    // the factors do not exceed the integer
+                    Some(Factorization::from_prime_powers(
+                        factors.into_iter().map(|(p, e)| (p as $t, e)),
+                    ))
+                }
+            }
+        )+
+    };
+}
+
+impl_factorize_for_prim_unsigned_int!(
+    (u8, factorize_u64, u64),
+    (u16, factorize_u64, u64),
+    (u32, factorize_u64, u64),
+    (u64, factorize_u64, u64),
+    (usize, factorize_u64, u64),
+    (u128, factorize_u128, u128)
+);
+
+/// The limits of the effort of Lenstra's elliptic-curve factorization for each composite factor.
+///
+/// The curves are tried with the increasing bounds `B1` of the first stage, from 2000 up to `max_b1`, as in the
+/// usual schedule for the factors of 15, 20, 25, ... digits, and the bound of the second stage is `100 * B1`.
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EcmLimits {
+    pub max_b1: u64,
+    pub max_curves: u32,
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl EcmLimits {
+    /// The limits that never stop the search, so the factorization can take arbitrarily long.
+    pub const UNLIMITED: Self = Self {
+        max_b1: u64::MAX,
+        max_curves: u32::MAX,
+    };
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl Default for EcmLimits {
+    /// The limits that usually find the factors up to about 35 digits.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_b1: 1_000_000,
+            max_curves: 3_000,
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+pub trait FactorizeWithLimits: Factorize {
+    /// Returns the factorization of the part of the integer split into the primes within the limits with
+    /// the remaining cofactor, i.e. the product of the composite factors that were not split, which is 1 if
+    /// the factorization is complete, or [None] for 0.
+    fn factorize_with_limits(&self, limits: &EcmLimits) -> Option<(Factorization<Self>, Self)>;
+}
+
+// The Montgomery curve By² = x³ + Ax² + x modulo n with (A + 2) / 4 = a24_numer / a24_denom
+// and the points (X : Z) without the y-coordinates
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+struct MontgomeryCurve<'a> {
+    n: &'a BigUint,
+    a24_numer: BigUint,
+    a24_denom: BigUint,
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+type Point = (BigUint, BigUint);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl MontgomeryCurve<'_> {
+    #[inline]
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a >= b {
+            a - b
+        } else {
+            a + self.n - b
+        }
+    }
+
+    // (X + Z)²(X - Z)² and 4XZ((X - Z)² + (A + 2) / 4 * 4XZ) scaled by a24_denom
+    fn double(&self, (x, z): &Point) -> Point {
+        let n = self.n;
+        let sum = (x + z) % n;
+        let sum = &sum * &sum % n;
+        let difference = self.sub(x, z);
+        let difference = &difference * &difference % n;
+        let product = self.sub(&sum, &difference);
+        let x = sum * &difference % n * &self.a24_denom % n;
+        let z = (difference * &self.a24_denom + &product * &self.a24_numer) % n * product % n;
+        (x, z)
+    }
+
+    // P + Q from P, Q and P - Q
+    fn add(&self, (xp, zp): &Point, (xq, zq): &Point, (xd, zd): &Point) -> Point {
+        let n = self.n;
+        let u = self.sub(xp, zp) * ((xq + zq) % n) % n;
+        let v = (xp + zp) % n * self.sub(xq, zq) % n;
+        let sum = (&u + &v) % n;
+        let difference = self.sub(&u, &v);
+        (
+            zd * (&sum * &sum % n) % n,
+            xd * (&difference * &difference % n) % n,
+        )
+    }
+
+    // [k]P for the positive k with the Montgomery ladder
+    fn mul(&self, p: &Point, k: u64) -> Point {
+        let (mut r0, mut r1) = (p.clone(), self.double(p));
+        for i in (0..u64::BITS - 1 - k.leading_zeros()).rev() {
+            if k >> i & 1 == 1 {
+                r0 = self.add(&r1, &r0, p);
+                r1 = self.double(&r1);
+            } else {
+                r1 = self.add(&r0, &r1, p);
+                r0 = self.double(&r0);
+            }
+        }
+        r0
+    }
+}
+
+// The factor of n found on the curve with Suyama's parametrization by sigma or [None]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn ecm_curve(n: &BigUint, sigma: u64, b1: u64) -> Option<BigUint> {
+    let split = |a: &BigUint| match Split::new(a.gcd(n), n) {
+        Split::Factor(d) => Some(d),
+        Split::Trivial | Split::Whole => None,
+    };
+    // u = σ² - 5, v = 4σ, (X : Z) = (u³ : v³) and (A + 2) / 4 = (v - u)³(3u + v) / (16u³v)
+    let sigma = BigUint::from(sigma);
+    let u = (&sigma * &sigma - 5u8) % n;
+    let v = sigma * 4u8 % n;
+    let u3 = &u * &u % n * &u % n;
+    let curve = MontgomeryCurve {
+        n,
+        a24_numer: BigUint::zero(),
+        a24_denom: u3.clone() * &v * 16u8 % n,
+    };
+    let w = curve.sub(&v, &u);
+    let curve = MontgomeryCurve {
+        a24_numer: &w * &w % n * &w % n * ((u * 3u8 + &v) % n) % n,
+        ..curve
+    };
+    if let Some(d) = split(&curve.a24_denom) {
+        return Some(d);
+    }
+    let mut q = (u3, v.modpow(&BigUint::from(3u8), n));
+
+    // the first stage multiplies by the greatest powers of the primes up to B1
+    let mut buf = [0; 1024];
+    for p in PrimesInRange::new(2, b1, &mut buf).unwrap() {
+        let mut power = p;
+        while power <= b1 / p {
+            power *= p;
+        }
+        q = curve.mul(&q, power);
+    }
+    if let Some(d) = split(&q.1) {
+        return Some(d);
+    }
+
+    // the second stage looks for the prime mD ± j in (B1, 100 * B1] with the baby steps [j]Q for j coprime
+    // with D and the giant steps [mD]Q, whose x-coordinates coincide modulo the factor then
+    const D: u64 = 210;
+    let (first, last) = (b1 / D, b1.saturating_mul(100) / D + 1);
+    if first < 2 {
+        return None;
+    }
+    let double = curve.double(&q);
+    let mut babies = Vec::new();
+    let (mut previous, mut current) = (q.clone(), curve.add(&double, &q, &q));
+    babies.push(q.clone());
+    for j in (3..D / 2).step_by(2) {
+        if j % 3 != 0 && j % 5 != 0 && j % 7 != 0 {
+            babies.push(current.clone());
+        }
+        let next = curve.add(&current, &double, &previous);
+        previous = core::mem::replace(&mut current, next);
+    }
+    let step = curve.mul(&q, D);
+    let (mut previous, mut current) = (curve.mul(&q, (first - 1) * D), curve.mul(&q, first * D));
+    let mut product = BigUint::one();
+    for _ in first..=last {
+        for (x, z) in &babies {
+            let difference = curve.sub(&(&current.0 * z % n), &(x * &current.1 % n));
+            product = product * difference % n;
+        }
+        let next = curve.add(&current, &step, &previous);
+        previous = core::mem::replace(&mut current, next);
+    }
+    split(&product)
+}
+
+// Lenstra's elliptic-curve factorization of the odd composite n that is not a perfect power, with the curves
+// chosen pseudorandomly from n for the reproducibility
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn ecm(n: &BigUint, limits: &EcmLimits) -> Option<BigUint> {
+    // the bounds B1 with the numbers of the curves for the factors of 15, 20, ..., 50 digits
+    const SCHEDULE: [(u64, u32); 8] = [
+        (2_000, 25),
+        (11_000, 90),
+        (50_000, 300),
+        (250_000, 700),
+        (1_000_000, 1_800),
+        (3_000_000, 5_100),
+        (11_000_000, 10_600),
+        (43_000_000, 19_300),
+    ];
+    let mut state = n.iter_u64_digits().next().unwrap() | 1;
+    let mut curves = 0;
+    let mut level = 0;
+    loop {
+        let (b1, count) = SCHEDULE[level];
+        for _ in 0..count {
+            if curves == limits.max_curves {
+                return None;
+            }
+            curves += 1;
+            // xorshift
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            if let Some(d) = ecm_curve(n, 6 + (state >> 32), b1.min(limits.max_b1)) {
+                return Some(d);
+            }
+        }
+        // the last level repeats until the curves run out
+        level = (level + 1).min(SCHEDULE.len() - 1);
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn factorize_big(n: &BigUint, limits: &EcmLimits) -> (Vec<(BigUint, u32)>, BigUint) {
+    let (mut factors, mut cofactor) = (Vec::new(), BigUint::one());
+    let mut n = n.clone();
+    for p in trial_primes() {
+        if n.bits() <= 64 {
+            break;
+        }
+        let mut e = 0;
+        while (&n % p).is_zero() {
+            n /= p;
+            e += 1;
+        }
+        if e != 0 {
+            factors.push((BigUint::from(p), e));
+        }
+    }
+    split_big(n, 1, limits, &mut factors, &mut cofactor);
+    (factors, cofactor)
+}
+
+// Factorizes n^e for n, which is 1, a prime, at most 64 bits or an odd composite without the factors below 2^10,
+// and multiplies the cofactor by the composites that are not split within the limits
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn split_big(
+    n: BigUint,
+    e: u32,
+    limits: &EcmLimits,
+    factors: &mut Vec<(BigUint, u32)>,
+    cofactor: &mut BigUint,
+) {
+    if let Some(n) = n.to_u64() {
+        let mut small = Vec::new();
+        factorize_u64(n, &mut small);
+        factors.extend(small.into_iter().map(|(p, f)| (BigUint::from(p), e * f)));
+        return;
+    }
+    if n.is_prime() {
+        factors.push((n, e));
+        return;
+    }
+    // the factors are greater than 2^10, so the exponent of a perfect power is less than bits / 10
+    for k in (2..=(n.bits() / 10) as u32).rev() {
+        let root = n.nth_root(k);
+        if root.pow(k) == n {
+            return split_big(root, e * k, limits, factors, cofactor);
+        }
+    }
+    let modulus = BigOddModulus(n.clone());
+    let split = |a: &BigUint| Split::new(a.gcd(&n), &n);
+    let d = (1..=4)
+        .find_map(|c| pollard_brent(&modulus, c, 1 << 14, split))
+        .or_else(|| ecm(&n, limits));
+    match d {
+        Some(d) => {
+            let other = &n / &d;
+            split_big(d, e, limits, factors, cofactor);
+            split_big(other, e, limits, factors, cofactor);
+        }
+        None => *cofactor *= n.pow(e),
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl Factorize for BigUint {
+    /// Returns the factorization with [EcmLimits::UNLIMITED], so it can take arbitrarily long.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factorization is still incomplete after [u32::MAX] curves, so that the returned one
+    /// is always of `self`.
+    #[inline]
+    fn factorize(&self) -> Option<Factorization<Self>> {
+        let (factorization, cofactor) = self.factorize_with_limits(&EcmLimits::UNLIMITED)?;
+        assert!(
+            cofactor.is_one(),
+            "the elliptic curves left a composite cofactor"
+        );
+        Some(factorization)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl FactorizeWithLimits for BigUint {
+    fn factorize_with_limits(&self, limits: &EcmLimits) -> Option<(Factorization<Self>, Self)> {
+        if self.is_zero() {
+            return None;
+        }
+        let (factors, cofactor) = factorize_big(self, limits);
+        Some((Factorization::from_prime_powers(factors), cofactor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number_theory::{
+        factorization::*,
+        primality::{IsPrime, PrimeNeighbors},
+    };
+    use num_bigint::BigUint;
+
+    fn assert_factorization<
+        T: Factorize + IsPrime + Ord + Clone + CheckedMul + One + core::fmt::Debug,
+    >(
+        n: T,
+    ) {
+        let factorization = n.factorize().unwrap();
+        let factors = factorization.factors();
+        assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(factors.iter().all(|(p, e)| p.is_prime() && *e > 0));
+        assert_eq!(factorization.value(), Some(n));
+    }
+
+    #[test]
+    fn primitive_integers_are_factorized() {
+        for n in 1u32..20_000 {
+            assert_factorization(n);
+        }
+        assert_eq!(0u8.factorize(), None);
+        assert!(1u16.factorize().unwrap().is_one());
+        assert_eq!(
+            255u8.factorize().unwrap().factors(),
+            [(3, 1), (5, 1), (17, 1)]
+        );
+        // the products of the primes close to 2^32 and the powers that need Pollard's rho
+        let (p, q) = (4_294_967_291u64, 4_294_967_279u64);
+        assert_eq!((p * q).factorize().unwrap().factors(), [(q, 1), (p, 1)]);
+        assert_eq!(
+            (65_521u64.pow(4)).factorize().unwrap().factors(),
+            [(65_521, 4)]
+        );
+        for n in [u64::MAX - 1, 600_851_475_143, 1 << 63, 3_215_031_751 * 7919] {
+            assert_factorization(n);
+        }
+
+        let (p, q) = (
+            (1u128 << 36).next_prime().unwrap(),
+            (1u128 << 80).next_prime().unwrap(),
+        );
+        assert_eq!((p * q).factorize().unwrap().factors(), [(p, 1), (q, 1)]);
+        assert_eq!(
+            (p * p * 1024).factorize().unwrap().factors(),
+            [(2, 10), (p, 2)]
+        );
+        for n in [u128::MAX, u128::MAX - 1, (1 << 89) - 1, 1 << 127] {
+            assert_factorization(n);
+        }
+    }
+
+    #[test]
+    fn factorizations_multiply_and_have_divisors() {
+        for a in 1u64..60 {
+            for b in 1u64..60 {
+                let (x, y) = (a.factorize().unwrap(), b.factorize().unwrap());
+                assert_eq!(x.gcd(&y).value(), Some(a.gcd(&b)));
+                assert_eq!(x.lcm(&y).value(), Some(a.lcm(&b)));
+                assert_eq!((&x * &y).value(), Some(a * b));
+                let mut z = x.clone();
+                z *= y.clone();
+                assert_eq!(z, x * y);
+            }
+        }
+        for n in 1u32..2000 {
+            let factorization = n.factorize().unwrap();
+            let mut divisors: Vec<u32> = factorization.divisors().unwrap().collect();
+            divisors.sort_unstable();
+            let expected: Vec<u32> = (1..=n).filter(|d| n.is_multiple_of(*d)).collect();
+            assert_eq!(divisors, expected);
+            assert_eq!(factorization.divisor_count(), Some(expected.len() as u64));
+        }
+        // 2^8 * 3^8 does not fit in u8
+        let x = Factorization::from_prime_powers([(3u8, 5), (2, 8), (3, 3), (5, 0)]);
+        assert_eq!(x.factors(), [(2, 8), (3, 8)]);
+        assert_eq!(x.value(), None);
+        assert!(x.divisors().is_none());
+        assert_eq!(x.divisor_count(), Some(81));
+        assert_eq!(
+            Factorization::<u8>::default()
+                .divisors()
+                .unwrap()
+                .collect::<Vec<_>>(),
+            [1]
+        );
+    }
+
+    #[test]
+    fn big_integers_are_factorized_with_ecm() {
+        let big = |n: u128| BigUint::from(n);
+        let p = big((1u128 << 34).next_prime().unwrap());
+        let q = big((1u128 << 38).next_prime().unwrap());
+        let r = big((1u128 << 70).next_prime().unwrap());
+        let n = &p * &q * &r * &r * 360u32;
+        let factorization = n.factorize().unwrap();
+        assert_eq!(
+            factorization.factors(),
+            [
+                (big(2), 3),
+                (big(3), 2),
+                (big(5), 1),
+                (p.clone(), 1),
+                (q.clone(), 1),
+                (r.clone(), 2)
+            ]
+        );
+        assert_eq!(factorization.value(), Some(n));
+        // the perfect powers
+        let n = r.pow(3) * &p;
+        let factorization = n.factorize().unwrap();
+        assert_eq!(factorization.factors(), [(p.clone(), 1), (r.clone(), 3)]);
+        assert_eq!(factorization.value(), Some(n));
+        assert_eq!(BigUint::zero().factorize(), None);
+        assert!(BigUint::one().factorize().unwrap().is_one());
+
+        // the composite s * r is left without the curves
+        let s = big((1u128 << 60).next_prime().unwrap());
+        let limits = EcmLimits {
+            max_b1: 2_000,
+            max_curves: 0,
+        };
+        let (factorization, cofactor) = (&s * &r * 12u32).factorize_with_limits(&limits).unwrap();
+        assert_eq!(factorization.factors(), [(big(2), 2), (big(3), 1)]);
+        assert_eq!(cofactor, &s * &r);
+
+        // the factors of 13 digits are found by the curves of the first bound
+        let t = big((1u128 << 42).next_prime().unwrap());
+        let d = ecm(&(&t * &r), &EcmLimits::default()).unwrap();
+        assert!(d == t || d == r);
+    }
+}
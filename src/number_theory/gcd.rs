@@ -0,0 +1,520 @@
+//! The [greatest common divisor](https://en.wikipedia.org/wiki/Greatest_common_divisor), the
+//! [least common multiple](https://en.wikipedia.org/wiki/Least_common_multiple) and the
+//! [extended Euclidean algorithm](https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm).
+//!
+//! Like [GetLastDigitBase10AsU8][crate::num_sys::base_10::GetLastDigitBase10AsU8], the traits look at the absolute
+//! values of the signed integers, so the results are non-negative, and `gcd(0, 0)` is 0. The primitive integers
+//! use the [binary GCD algorithm](https://en.wikipedia.org/wiki/Binary_GCD_algorithm) and the big integers use
+//! [Lehmer's algorithm](https://en.wikipedia.org/wiki/Lehmer%27s_GCD_algorithm).
+//!
+//! ```rust
+//! use dd_maths_traits::number_theory::gcd::{gcd_all, lcm_all, ExtendedGcd, Gcd, Lcm};
+//!
+//! assert_eq!(12u32.gcd(&18), 6);
+//! assert_eq!((-12i32).gcd(&18), 6);
+//! assert_eq!(4u8.lcm(&6), 12);
+//! assert_eq!(i8::MIN.checked_gcd(&0), None);
+//!
+//! let bezout = 240u32.extended_gcd(&46);
+//! assert_eq!((bezout.gcd, bezout.x, bezout.y), (2, -9, 47));
+//!
+//! assert_eq!(gcd_all(&[12u64, 18, 27]), Some(3));
+//! assert_eq!(lcm_all(&[2u8, 3, 5, 7]), Some(210));
+//! assert_eq!(lcm_all(&[2u8, 3, 5, 7, 11]), None);
+//! ```
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint, Sign};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_traits::ToPrimitive;
+use num_traits::{One, Zero};
+
+pub trait Gcd: Sized {
+    /// Returns the non-negative greatest common divisor or [None] if it is not representable, which happens only
+    /// for the minimum of a signed primitive integer and either 0 or itself.
+    fn checked_gcd(&self, other: &Self) -> Option<Self>;
+
+    /// # Panics
+    ///
+    /// Panics if the greatest common divisor is not representable.
+    #[inline]
+    fn gcd(&self, other: &Self) -> Self {
+        self.checked_gcd(other)
+            .expect("attempt to compute the gcd with overflow")
+    }
+}
+
+pub trait Lcm: Gcd {
+    /// Returns the non-negative least common multiple, which is 0 if either of the integers is 0,
+    /// or [None] if it is not representable.
+    fn checked_lcm(&self, other: &Self) -> Option<Self>;
+
+    /// # Panics
+    ///
+    /// Panics if the least common multiple is not representable.
+    #[inline]
+    fn lcm(&self, other: &Self) -> Self {
+        self.checked_lcm(other)
+            .expect("attempt to compute the lcm with overflow")
+    }
+}
+
+/// The greatest common divisor `gcd = a * x + b * y` of `a` and `b` with the Bézout coefficients `x` and `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExtendedGcdResult<T, C> {
+    pub gcd: T,
+    pub x: C,
+    pub y: C,
+}
+
+pub trait ExtendedGcd: Gcd {
+    /// The type of the Bézout coefficients, i.e. the signed counterpart of the unsigned integers.
+    type Coefficient;
+
+    /// Returns the non-negative greatest common divisor with the Bézout coefficients found by the extended Euclidean
+    /// algorithm, which satisfy `|x| <= |b| / (2 * gcd)` and `|y| <= |a| / (2 * gcd)` unless one of the integers
+    /// divides the other one, or [None] if the greatest common divisor is not representable.
+    fn checked_extended_gcd(
+        &self,
+        other: &Self,
+    ) -> Option<ExtendedGcdResult<Self, Self::Coefficient>>;
+
+    /// # Panics
+    ///
+    /// Panics if the greatest common divisor is not representable.
+    #[inline]
+    fn extended_gcd(&self, other: &Self) -> ExtendedGcdResult<Self, Self::Coefficient> {
+        self.checked_extended_gcd(other)
+            .expect("attempt to compute the gcd with overflow")
+    }
+}
+
+/// Returns the greatest common divisor of all the integers, which is 0 for the empty slice,
+/// or [None] if it is not representable.
+pub fn gcd_all<T: Gcd + PartialEq + Zero + One>(values: &[T]) -> Option<T> {
+    let mut acc = T::zero();
+    for value in values {
+        if acc.is_one() {
+            break;
+        }
+        acc = acc.checked_gcd(value)?;
+    }
+    Some(acc)
+}
+
+/// Returns the least common multiple of all the integers, which is 1 for the empty slice,
+/// or [None] if it is not representable.
+pub fn lcm_all<T: Lcm + Zero + One>(values: &[T]) -> Option<T> {
+    let mut acc = T::one();
+    for value in values {
+        acc = acc.checked_lcm(value)?;
+        if acc.is_zero() {
+            break;
+        }
+    }
+    Some(acc)
+}
+
+// Euclid's algorithm on the magnitudes, whose Bézout coefficients alternate in sign and are kept as
+// the magnitudes with the sign of x, since they do not exceed the magnitudes of the integers
+macro_rules! extended_euclid {
+    ($u:ty, $a:expr, $b:expr) => {{
+        let (mut r0, mut r1): ($u, $u) = ($a, $b);
+        let (mut s0, mut s1): ($u, $u) = (1, 0);
+        let (mut t0, mut t1): ($u, $u) = (0, 1);
+        let mut is_x_negative = false;
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (s0, s1) = (s1, s0 + q * s1);
+            (t0, t1) = (t1, t0 + q * t1);
+            is_x_negative = !is_x_negative;
+        }
+        (r0, s0, t0, is_x_negative)
+    }};
+}
+
+macro_rules! impl_gcd_for_prim_unsigned_int {
+    ($(($u:ty, $s:ty)),+) => {
+        $(
+            impl Gcd for $u {
+                #[inline]
+                fn checked_gcd(&self, other: &Self) -> Option<Self> {
+                    let (mut a, mut b) = (*self, *other);
+                    if a == 0 || b == 0 {
+                        return Some(a | b);
+                    }
+                    // the common powers of 2 are restored in the end and the rest of the powers of 2 are dropped,
+                    // since the difference of the odd integers is even
+                    let shift = (a | b).trailing_zeros();
+                    a >>= a.trailing_zeros();
+                    loop {
+                        b >>= b.trailing_zeros();
+                        if a > b {
+                            core::mem::swap(&mut a, &mut b);
+                        }
+                        b -= a;
+                        if b == 0 {
+                            return Some(a << shift);
+                        }
+                    }
+                }
+            }
+
+            impl Lcm for $u {
+                #[inline]
+                fn checked_lcm(&self, other: &Self) -> Option<Self> {
+                    if *self == 0 || *other == 0 {
+                        return Some(0);
+                    }
+                    (self / self.gcd(other)).checked_mul(*other)
+                }
+            }
+
+            impl ExtendedGcd for $u {
+                type Coefficient = $s;
+
+                fn checked_extended_gcd(
+                    &self,
+                    other: &Self,
+                ) -> Option<ExtendedGcdResult<Self, $s>> {
+                    let (gcd, x, y, is_x_negative) = extended_euclid!($u, *self, *other);
+                    let (x, y) = (<$s>::try_from(x).ok()?, <$s>::try_from(y).ok()?);
+                    let (x, y) = if is_x_negative { (-x, y) } else { (x, -y) };
+                    Some(ExtendedGcdResult { gcd, x, y })
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_gcd_for_prim_signed_int {
+    ($(($s:ty, $u:ty)),+) => {
+        $(
+            impl Gcd for $s {
+                #[inline]
+                fn checked_gcd(&self, other: &Self) -> Option<Self> {
+                    <$s>::try_from(self.unsigned_abs().gcd(&other.unsigned_abs())).ok()
+                }
+            }
+
+            impl Lcm for $s {
+                #[inline]
+                fn checked_lcm(&self, other: &Self) -> Option<Self> {
+                    let lcm = self.unsigned_abs().checked_lcm(&other.unsigned_abs())?;
+                    <$s>::try_from(lcm).ok()
+                }
+            }
+
+            impl ExtendedGcd for $s {
+                type Coefficient = $s;
+
+                fn checked_extended_gcd(
+                    &self,
+                    other: &Self,
+                ) -> Option<ExtendedGcdResult<Self, $s>> {
+                    let (gcd, x, y, is_x_negative) =
+                        extended_euclid!($u, self.unsigned_abs(), other.unsigned_abs());
+                    // the coefficient of a negative integer takes the opposite sign
+                    let with_sign = |magnitude: $u, is_negative: bool| {
+                        if is_negative {
+                            // the magnitude of the minimum wraps to itself
+                            (magnitude <= <$s>::MIN.unsigned_abs())
+                                .then(|| (magnitude as $s).wrapping_neg())
+                        } else {
+                            <$s>::try_from(magnitude).ok()
+                        }
+                    };
+                    Some(ExtendedGcdResult {
+                        gcd: <$s>::try_from(gcd).ok()?,
+                        x: with_sign(x, is_x_negative != (*self < 0))?,
+                        y: with_sign(y, is_x_negative == (*other < 0))?,
+                    })
+                }
+            }
+        )+
+    };
+}
+
+impl_gcd_for_prim_unsigned_int!(
+    (u8, i8),
+    (u16, i16),
+    (u32, i32),
+    (u64, i64),
+    (u128, i128),
+    (usize, isize)
+);
+impl_gcd_for_prim_signed_int!(
+    (i8, u8),
+    (i16, u16),
+    (i32, u32),
+    (i64, u64),
+    (i128, u128),
+    (isize, usize)
+);
+
+// Lehmer's algorithm for a >= b, which replaces the runs of the steps of Euclid's algorithm that depend only
+// on the leading 63 bits with a single multiplication by the 2x2 matrix of the cosequences and also applies it
+// to the pair of the Bézout coefficients of a if they are given, returning the gcd
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn lehmer_gcd(mut a: BigUint, mut b: BigUint, mut x: Option<&mut (BigInt, BigInt)>) -> BigUint {
+    while b.bits() > 64 {
+        let shift = a.bits() - 63;
+        let mut x_hat = i128::from((&a >> shift).to_u64().unwrap());
+        let mut y_hat = i128::from((&b >> shift).to_u64().unwrap());
+        // the cosequences stay below 2^63 in magnitude, so the products do not overflow
+        let (mut m00, mut m01, mut m10, mut m11) = (1i128, 0i128, 0i128, 1i128);
+        loop {
+            let (d0, d1) = (y_hat + m10, y_hat + m11);
+            let (n0, n1) = (x_hat + m00, x_hat + m01);
+            if d0 <= 0 || d1 <= 0 || n0 < 0 || n1 < 0 {
+                break;
+            }
+            // the quotients of both ends of the range of the leading bits must agree
+            let q = n0 / d0;
+            if q != n1 / d1 {
+                break;
+            }
+            (m00, m10) = (m10, m00 - q * m10);
+            (m01, m11) = (m11, m01 - q * m11);
+            (x_hat, y_hat) = (y_hat, x_hat - q * y_hat);
+        }
+        if m01 == 0 {
+            // the leading bits determine no step, so a step with the full precision is made
+            let (q, r) = (&a / &b, &a % &b);
+            if let Some((s0, s1)) = x.as_deref_mut() {
+                let s2 = &*s0 - BigInt::from(q) * &*s1;
+                *s0 = core::mem::replace(s1, s2);
+            }
+            (a, b) = (b, r);
+            continue;
+        }
+        let (m00, m01, m10, m11) = (
+            BigInt::from(m00),
+            BigInt::from(m01),
+            BigInt::from(m10),
+            BigInt::from(m11),
+        );
+        let apply = |u: &BigInt, v: &BigInt| (&m00 * u + &m01 * v, &m10 * u + &m11 * v);
+        // the remainders stay non-negative
+        let (u, v) = apply(&BigInt::from(a), &BigInt::from(b));
+        (a, b) = (u.into_parts().1, v.into_parts().1);
+        if let Some((s0, s1)) = x.as_deref_mut() {
+            (*s0, *s1) = apply(s0, s1);
+        }
+    }
+    while !b.is_zero() {
+        let (q, r) = (&a / &b, &a % &b);
+        if let Some((s0, s1)) = x.as_deref_mut() {
+            let s2 = &*s0 - BigInt::from(q) * &*s1;
+            *s0 = core::mem::replace(s1, s2);
+        }
+        (a, b) = (b, r);
+    }
+    a
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn extended_lehmer_gcd(a: &BigUint, b: &BigUint) -> ExtendedGcdResult<BigUint, BigInt> {
+    if a < b {
+        let ExtendedGcdResult { gcd, x, y } = extended_lehmer_gcd(b, a);
+        return ExtendedGcdResult { gcd, x: y, y: x };
+    }
+    let mut coefficients = (BigInt::one(), BigInt::zero());
+    let gcd = lehmer_gcd(a.clone(), b.clone(), Some(&mut coefficients));
+    let x = coefficients.0;
+    let y = if b.is_zero() {
+        BigInt::zero()
+    } else {
+        (BigInt::from(gcd.clone()) - BigInt::from(a.clone()) * &x) / BigInt::from(b.clone())
+    };
+    ExtendedGcdResult { gcd, x, y }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl Gcd for BigUint {
+    #[inline]
+    fn checked_gcd(&self, other: &Self) -> Option<Self> {
+        let (a, b) = if self < other {
+            (other, self)
+        } else {
+            (self, other)
+        };
+        Some(lehmer_gcd(a.clone(), b.clone(), None))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl Lcm for BigUint {
+    fn checked_lcm(&self, other: &Self) -> Option<Self> {
+        if self.is_zero() || other.is_zero() {
+            return Some(BigUint::zero());
+        }
+        Some(self / self.gcd(other) * other)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl ExtendedGcd for BigUint {
+    type Coefficient = BigInt;
+
+    #[inline]
+    fn checked_extended_gcd(&self, other: &Self) -> Option<ExtendedGcdResult<Self, BigInt>> {
+        Some(extended_lehmer_gcd(self, other))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl Gcd for BigInt {
+    #[inline]
+    fn checked_gcd(&self, other: &Self) -> Option<Self> {
+        Some(self.magnitude().gcd(other.magnitude()).into())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl Lcm for BigInt {
+    #[inline]
+    fn checked_lcm(&self, other: &Self) -> Option<Self> {
+        Some(self.magnitude().lcm(other.magnitude()).into())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl ExtendedGcd for BigInt {
+    type Coefficient = BigInt;
+
+    fn checked_extended_gcd(&self, other: &Self) -> Option<ExtendedGcdResult<Self, BigInt>> {
+        let ExtendedGcdResult { gcd, x, y } =
+            extended_lehmer_gcd(self.magnitude(), other.magnitude());
+        // the coefficient of a negative integer takes the opposite sign
+        let with_sign = |c: BigInt, n: &BigInt| if n.sign() == Sign::Minus { -c } else { c };
+        Some(ExtendedGcdResult {
+            gcd: gcd.into(),
+            x: with_sign(x, self),
+            y: with_sign(y, other),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number_theory::gcd::{gcd_all, lcm_all, ExtendedGcd, Gcd, Lcm};
+
+    fn euclid(mut a: i64, mut b: i64) -> i64 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a.abs()
+    }
+
+    #[test]
+    fn gcd_of_primitive_integers_matches_euclid() {
+        for a in 0u8..=255 {
+            for b in 0u8..=255 {
+                let gcd = euclid(a.into(), b.into());
+                assert_eq!(i64::from(a.gcd(&b)), gcd);
+                let lcm = if gcd == 0 {
+                    0
+                } else {
+                    i64::from(a) * i64::from(b) / gcd
+                };
+                assert_eq!(
+                    a.checked_lcm(&b).map(i64::from),
+                    (lcm <= 255).then_some(lcm)
+                );
+
+                let bezout = a.extended_gcd(&b);
+                let (x, y) = (i64::from(bezout.x), i64::from(bezout.y));
+                assert_eq!(i64::from(bezout.gcd), i64::from(a) * x + i64::from(b) * y);
+                if a != 0 && b != 0 && a % b != 0 && b % a != 0 {
+                    assert!(x.abs() <= i64::from(b) / (2 * gcd));
+                    assert!(y.abs() <= i64::from(a) / (2 * gcd));
+                }
+            }
+        }
+        for a in -128i8..=127 {
+            for b in -128i8..=127 {
+                let gcd = euclid(a.into(), b.into());
+                assert_eq!(
+                    a.checked_gcd(&b).map(i64::from),
+                    (gcd <= 127).then_some(gcd)
+                );
+                let Some(bezout) = a.checked_extended_gcd(&b) else {
+                    assert_eq!(gcd, 128);
+                    continue;
+                };
+                let (x, y) = (i64::from(bezout.x), i64::from(bezout.y));
+                assert_eq!(i64::from(bezout.gcd), i64::from(a) * x + i64::from(b) * y);
+            }
+        }
+        assert_eq!(u128::MAX.gcd(&(u128::MAX - 2)), 1);
+        assert_eq!((3u128 << 100).gcd(&(6u128 << 90)), 6 << 90);
+        assert_eq!(i64::MIN.checked_lcm(&1), None);
+        assert_eq!((-4i64).lcm(&6), 12);
+        assert_eq!(0u32.extended_gcd(&0).gcd, 0);
+    }
+
+    #[test]
+    fn gcd_of_big_integers_matches_euclid() {
+        use num_bigint::{BigInt, BigUint};
+        use num_traits::{One, Zero};
+
+        fn euclid(mut a: BigUint, mut b: BigUint) -> BigUint {
+            while !b.is_zero() {
+                (a, b) = (b.clone(), a % b);
+            }
+            a
+        }
+
+        let (mut f0, mut f1) = (BigUint::zero(), BigUint::one());
+        let mut values = Vec::new();
+        for i in 0..400 {
+            (f0, f1) = (f1.clone(), f0 + f1);
+            if i % 37 == 0 {
+                values.push(f0.clone());
+                values.push(&f0 * &f1 * BigUint::from(6u32).pow(i / 7));
+                values.push(BigUint::from(3u32).pow(i) + 1u32);
+            }
+        }
+        for a in &values {
+            for b in &values {
+                let gcd = euclid(a.clone(), b.clone());
+                assert_eq!(a.gcd(b), gcd);
+                let bezout = a.extended_gcd(b);
+                assert_eq!(bezout.gcd, gcd);
+                assert_eq!(
+                    BigInt::from(gcd),
+                    BigInt::from(a.clone()) * &bezout.x + BigInt::from(b.clone()) * &bezout.y
+                );
+
+                let (a, b) = (-BigInt::from(a.clone()), BigInt::from(b.clone()));
+                let bezout = a.extended_gcd(&b);
+                assert_eq!(bezout.gcd, &a * &bezout.x + &b * &bezout.y);
+                assert_eq!(
+                    a.lcm(&b) * &bezout.gcd,
+                    (&a * &b).magnitude().clone().into()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gcd_and_lcm_of_slices_detect_overflow() {
+        assert_eq!(gcd_all::<u32>(&[]), Some(0));
+        assert_eq!(gcd_all(&[0i32, -6, 9]), Some(3));
+        assert_eq!(gcd_all(&[i8::MIN, i8::MIN]), None);
+        assert_eq!(gcd_all(&[1i8, i8::MIN, i8::MIN]), Some(1));
+        assert_eq!(lcm_all::<u32>(&[]), Some(1));
+        assert_eq!(lcm_all(&[4u32, 6, 0, 10]), Some(0));
+        let range: Vec<u64> = (1..=47).collect();
+        assert_eq!(lcm_all(&range[..46]), Some(9_419_588_158_802_421_600));
+        assert_eq!(lcm_all(&range), None);
+    }
+}
@@ -0,0 +1,9 @@
+//! [Number theory](https://en.wikipedia.org/wiki/Number_theory) of the primitive and big integers.
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+pub mod factorization;
+pub mod gcd;
+pub mod modular;
+pub mod primality;
+pub mod primes;
@@ -0,0 +1,628 @@
+//! [Modular arithmetic](https://en.wikipedia.org/wiki/Modular_arithmetic) with the moduli up to [u64::MAX].
+//!
+//! [ModInt] has the modulus as a const generic parameter and [DynModInt] has it chosen at runtime as a [Modulus],
+//! which precomputes the constants of the reduction. The residues modulo the odd moduli are kept in the
+//! [Montgomery form](https://en.wikipedia.org/wiki/Montgomery_modular_multiplication) and the products modulo
+//! the even ones are reduced with [Barrett reduction](https://en.wikipedia.org/wiki/Barrett_reduction), so neither
+//! divides in the multiplication.
+//!
+//! ```rust
+//! use dd_maths_traits::number_theory::modular::{ModInt, Modulus};
+//!
+//! type Mod7 = ModInt<7>;
+//!
+//! let a = Mod7::from(5u8);
+//! assert_eq!((a * a).value(), 4);
+//! assert_eq!((a / Mod7::from(3u8)).value(), 4);
+//! assert_eq!(Mod7::from(-1i32).value(), 6);
+//! assert_eq!(a.pow(6).value(), 1);
+//!
+//! let m = Modulus::new(1 << 40).unwrap();
+//! let b = m.residue(3u8);
+//! assert_eq!(b.inv().unwrap() * b, m.residue(1u8));
+//! assert_eq!(m.residue(2u8).inv(), None);
+//! ```
+
+use crate::number_theory::gcd::ExtendedGcd;
+use core::{
+    fmt::{self, Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint, Sign};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_traits::ToPrimitive;
+use num_traits::{One, Zero};
+
+/// The integers that can be reduced modulo a [u64] modulus.
+pub trait RemEuclidU64 {
+    /// Returns the least non-negative remainder, which is the same as the one of
+    /// [i128::rem_euclid] for the signed integers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is 0.
+    fn rem_euclid_u64(&self, modulus: u64) -> u64;
+}
+
+macro_rules! impl_rem_euclid_u64 {
+    (@PRIM_UNSIGNED_INTS [$($t:ty),+]) => {
+        $(
+            impl RemEuclidU64 for $t {
+                #[inline(always)]
+                fn rem_euclid_u64(&self, modulus: u64) -> u64 {
+                    // the remainder is less than the modulus
+                    (*self as u128 % modulus as u128) as u64
+                }
+            }
+        )+
+    };
+    (@PRIM_SIGNED_INTS [$($t:ty),+]) => {
+        $(
+            impl RemEuclidU64 for $t {
+                #[inline(always)]
+                fn rem_euclid_u64(&self, modulus: u64) -> u64 {
+                    // the remainder is non-negative and less than the modulus
+                    (*self as i128).rem_euclid(modulus as i128) as u64
+                }
+            }
+        )+
+    };
+}
+
+impl_rem_euclid_u64!(@PRIM_UNSIGNED_INTS [u8, u16, u32, u64, u128, usize]);
+impl_rem_euclid_u64!(@PRIM_SIGNED_INTS [i8, i16, i32, i64, i128, isize]);
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl RemEuclidU64 for BigUint {
+    #[inline]
+    fn rem_euclid_u64(&self, modulus: u64) -> u64 {
+        (self % modulus).to_u64().unwrap()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl RemEuclidU64 for BigInt {
+    fn rem_euclid_u64(&self, modulus: u64) -> u64 {
+        let rem = self.magnitude().rem_euclid_u64(modulus);
+        if self.sign() == Sign::Minus && rem != 0 {
+            modulus - rem
+        } else {
+            rem
+        }
+    }
+}
+
+/// A positive modulus with the precomputed constants of its reduction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Modulus {
+    m: u64,
+    reduction: Reduction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Reduction {
+    // The residues are kept in the Montgomery form aR mod m with R = 2^64
+    Montgomery {
+        // m^(-1) mod R
+        m_inv: u64,
+        r_mod_m: u64,
+        r2_mod_m: u64,
+    },
+    // The residues are kept as they are and mu = ⌊(2^128 - 1) / m⌋
+    Barrett {
+        mu: u128,
+    },
+}
+
+impl Modulus {
+    /// Returns [None] if the modulus is 0.
+    pub const fn new(m: u64) -> Option<Self> {
+        if m == 0 {
+            return None;
+        }
+        let reduction = if m % 2 == 1 {
+            // Newton's iteration doubles the number of the correct low bits of the inverse,
+            // and m is its own inverse modulo 8
+            let mut m_inv = m;
+            let mut i = 0;
+            while i < 5 {
+                m_inv = m_inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(m_inv)));
+                i += 1;
+            }
+            let m128 = m as u128;
+            Reduction::Montgomery {
+                m_inv,
+                r_mod_m: ((u64::MAX % m + 1) % m),
+                r2_mod_m: ((u128::MAX % m128 + 1) % m128) as u64,
+            }
+        } else {
+            Reduction::Barrett {
+                mu: u128::MAX / m as u128,
+            }
+        };
+        Some(Self { m, reduction })
+    }
+
+    #[inline(always)]
+    pub const fn get(&self) -> u64 {
+        self.m
+    }
+
+    #[inline]
+    pub fn residue<T: RemEuclidU64>(self, value: T) -> DynModInt {
+        DynModInt {
+            repr: self.encode(value.rem_euclid_u64(self.m)),
+            modulus: self,
+        }
+    }
+
+    // Montgomery's REDC, which returns tR^(-1) mod m for t < mR. Since t - qm with q = t * m^(-1) mod R
+    // is divisible by R and their low halves are equal, (t - qm) / R is the difference of the high halves
+    #[inline(always)]
+    const fn redc(&self, t: u128, m_inv: u64) -> u64 {
+        let q = (t as u64).wrapping_mul(m_inv);
+        let (t_hi, qm_hi) = (
+            (t >> 64) as u64,
+            ((q as u128 * self.m as u128) >> 64) as u64,
+        );
+        if t_hi < qm_hi {
+            t_hi.wrapping_sub(qm_hi).wrapping_add(self.m)
+        } else {
+            t_hi - qm_hi
+        }
+    }
+
+    // Returns x mod m for x < m^2 with the estimate ⌊x * mu / 2^128⌋ of the quotient, which is short
+    // of the quotient by at most 2
+    #[inline(always)]
+    const fn barrett(&self, x: u128, mu: u128) -> u64 {
+        let q = mul_hi_u128(x, mu);
+        let mut r = x - q * self.m as u128;
+        while r >= self.m as u128 {
+            r -= self.m as u128;
+        }
+        r as u64
+    }
+
+    // the value must be less than the modulus
+    #[inline]
+    const fn encode(&self, value: u64) -> u64 {
+        match self.reduction {
+            Reduction::Montgomery {
+                m_inv, r2_mod_m, ..
+            } => self.redc(value as u128 * r2_mod_m as u128, m_inv),
+            Reduction::Barrett { .. } => value,
+        }
+    }
+
+    #[inline]
+    const fn decode(&self, repr: u64) -> u64 {
+        match self.reduction {
+            Reduction::Montgomery { m_inv, .. } => self.redc(repr as u128, m_inv),
+            Reduction::Barrett { .. } => repr,
+        }
+    }
+
+    #[inline]
+    const fn one_repr(&self) -> u64 {
+        match self.reduction {
+            Reduction::Montgomery { r_mod_m, .. } => r_mod_m,
+            Reduction::Barrett { .. } => 1 % self.m,
+        }
+    }
+
+    #[inline]
+    fn add(&self, a: u64, b: u64) -> u64 {
+        let (sum, is_overflowing) = a.overflowing_add(b);
+        if is_overflowing || sum >= self.m {
+            sum.wrapping_sub(self.m)
+        } else {
+            sum
+        }
+    }
+
+    #[inline]
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.m)
+        }
+    }
+
+    #[inline]
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        let product = a as u128 * b as u128;
+        match self.reduction {
+            // the Montgomery form of the product is abR = (aR)(bR)R^(-1)
+            Reduction::Montgomery { m_inv, .. } => self.redc(product, m_inv),
+            Reduction::Barrett { mu } => self.barrett(product, mu),
+        }
+    }
+
+    fn pow(&self, mut base: u64, mut exp: u64) -> u64 {
+        let mut acc = self.one_repr();
+        while exp > 0 {
+            if exp % 2 == 1 {
+                acc = self.mul(acc, base);
+            }
+            base = self.mul(base, base);
+            exp /= 2;
+        }
+        acc
+    }
+
+    fn inv(&self, repr: u64) -> Option<u64> {
+        let bezout = self.decode(repr).extended_gcd(&self.m);
+        if bezout.gcd != 1 {
+            return None;
+        }
+        // the coefficient does not exceed the half of the modulus in magnitude
+        let x = bezout.x.unsigned_abs() % self.m;
+        let x = if bezout.x < 0 && x != 0 {
+            self.m - x
+        } else {
+            x
+        };
+        Some(self.encode(x))
+    }
+}
+
+// The high half of the 256-bit product
+#[inline(always)]
+pub(crate) const fn mul_hi_u128(a: u128, b: u128) -> u128 {
+    let (a0, a1) = (a as u64 as u128, a >> 64);
+    let (b0, b1) = (b as u64 as u128, b >> 64);
+    let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    let mid = (p00 >> 64) + (p01 as u64 as u128) + (p10 as u64 as u128);
+    p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64)
+}
+
+/// A residue modulo the positive `M`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64> {
+    repr: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    const MODULUS: Modulus = match Modulus::new(M) {
+        Some(modulus) => modulus,
+        None => panic!("the modulus must be positive"),
+    };
+
+    #[inline(always)]
+    pub const fn modulus() -> u64 {
+        M
+    }
+
+    #[inline]
+    pub const fn new(value: u64) -> Self {
+        Self {
+            repr: Self::MODULUS.encode(value % M),
+        }
+    }
+
+    /// Returns the least non-negative representative.
+    #[inline]
+    pub const fn value(self) -> u64 {
+        Self::MODULUS.decode(self.repr)
+    }
+
+    #[inline]
+    pub fn pow(self, exp: u64) -> Self {
+        Self {
+            repr: Self::MODULUS.pow(self.repr, exp),
+        }
+    }
+
+    /// Returns the multiplicative inverse or [None] if the residue is not coprime with the modulus.
+    #[inline]
+    pub fn inv(self) -> Option<Self> {
+        Some(Self {
+            repr: Self::MODULUS.inv(self.repr)?,
+        })
+    }
+
+    /// Returns the quotient or [None] if the divisor is not invertible.
+    #[inline]
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        Some(self * other.inv()?)
+    }
+
+    #[inline(always)]
+    fn binary(self, other: Self, f: fn(&Modulus, u64, u64) -> u64) -> Self {
+        Self {
+            repr: f(&Self::MODULUS, self.repr, other.repr),
+        }
+    }
+}
+
+impl<const M: u64, T: RemEuclidU64> From<T> for ModInt<M> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value.rem_euclid_u64(M))
+    }
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+    #[inline]
+    fn zero() -> Self {
+        Self { repr: 0 }
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.repr == 0
+    }
+}
+
+impl<const M: u64> One for ModInt<M> {
+    #[inline]
+    fn one() -> Self {
+        Self {
+            repr: Self::MODULUS.one_repr(),
+        }
+    }
+}
+
+impl<const M: u64> Debug for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value(), M)
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.value(), f)
+    }
+}
+
+/// A residue modulo the [Modulus] chosen at runtime.
+///
+/// The binary operations panic if the moduli of the operands differ.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    repr: u64,
+    modulus: Modulus,
+}
+
+impl DynModInt {
+    #[inline(always)]
+    pub fn modulus(&self) -> Modulus {
+        self.modulus
+    }
+
+    /// Returns the least non-negative representative.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.modulus.decode(self.repr)
+    }
+
+    #[inline]
+    pub fn pow(self, exp: u64) -> Self {
+        Self {
+            repr: self.modulus.pow(self.repr, exp),
+            ..self
+        }
+    }
+
+    /// Returns the multiplicative inverse or [None] if the residue is not coprime with the modulus.
+    #[inline]
+    pub fn inv(self) -> Option<Self> {
+        Some(Self {
+            repr: self.modulus.inv(self.repr)?,
+            ..self
+        })
+    }
+
+    /// Returns the quotient or [None] if the divisor is not invertible.
+    #[inline]
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        Some(self * other.inv()?)
+    }
+
+    #[inline]
+    fn binary(self, other: Self, f: fn(&Modulus, u64, u64) -> u64) -> Self {
+        assert_eq!(
+            self.modulus.m, other.modulus.m,
+            "the moduli of the operands differ"
+        );
+        Self {
+            repr: f(&self.modulus, self.repr, other.repr),
+            ..self
+        }
+    }
+}
+
+impl Debug for DynModInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value(), self.modulus.m)
+    }
+}
+
+impl Display for DynModInt {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.value(), f)
+    }
+}
+
+macro_rules! impl_ops {
+    ([$($generics:tt)*] $t:ty) => {
+        impl<$($generics)*> Add for $t {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                self.binary(other, Modulus::add)
+            }
+        }
+
+        impl<$($generics)*> Sub for $t {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                self.binary(other, Modulus::sub)
+            }
+        }
+
+        impl<$($generics)*> Mul for $t {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, other: Self) -> Self {
+                self.binary(other, Modulus::mul)
+            }
+        }
+
+        impl<$($generics)*> Div for $t {
+            type Output = Self;
+
+            /// # Panics
+            ///
+            /// Panics if the divisor is not invertible.
+            #[inline]
+            fn div(self, other: Self) -> Self {
+                self.checked_div(other)
+                    .expect("attempt to divide by a residue that is not invertible")
+            }
+        }
+
+        impl<$($generics)*> Neg for $t {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                self.binary(self, |modulus, a, _| modulus.sub(0, a))
+            }
+        }
+
+        impl<$($generics)*> AddAssign for $t {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<$($generics)*> SubAssign for $t {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<$($generics)*> MulAssign for $t {
+            #[inline]
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+
+        impl<$($generics)*> DivAssign for $t {
+            #[inline]
+            fn div_assign(&mut self, other: Self) {
+                *self = *self / other;
+            }
+        }
+    };
+}
+
+impl_ops!([const M: u64] ModInt<M>);
+impl_ops!([] DynModInt);
+
+#[cfg(test)]
+mod tests {
+    use crate::number_theory::modular::{ModInt, Modulus};
+
+    // the values spread over the whole range of u64 with the small ones and the ones close to the modulus
+    fn values(m: u64) -> impl Iterator<Item = u64> {
+        let mut x = 0x2545_F491_4F6C_DD1Du64;
+        let pseudorandom = core::iter::from_fn(move || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            Some(x)
+        });
+        [0, 1, 2, m / 2, m - 1, m.wrapping_add(1), u64::MAX]
+            .into_iter()
+            .chain(pseudorandom.take(200))
+    }
+
+    #[test]
+    fn arithmetic_matches_u128() {
+        let moduli = [
+            1,
+            2,
+            3,
+            10,
+            998_244_353,
+            1 << 32,
+            (1 << 61) - 1,
+            1 << 63,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        for m in moduli {
+            let modulus = Modulus::new(m).unwrap();
+            let m128 = u128::from(m);
+            for a in values(m) {
+                let x = modulus.residue(a);
+                assert_eq!(u128::from(x.value()), u128::from(a) % m128);
+                for b in values(m).step_by(17) {
+                    let (a, y) = (u128::from(a) % m128, modulus.residue(b));
+                    let b = u128::from(b) % m128;
+                    assert_eq!(u128::from((x + y).value()), (a + b) % m128);
+                    assert_eq!(u128::from((x - y).value()), (a + m128 - b) % m128);
+                    assert_eq!(u128::from((x * y).value()), a * b % m128);
+                }
+                assert_eq!(
+                    u128::from((-x).value()),
+                    (m128 - u128::from(a) % m128) % m128
+                );
+                match x.inv() {
+                    Some(inv) => assert_eq!((inv * x).value(), 1 % m),
+                    None => assert!(x.value() == 0 || m % 2 == 0 || m % 3 == 0),
+                }
+            }
+        }
+        assert_eq!(Modulus::new(0), None);
+    }
+
+    #[test]
+    fn const_moduli_agree_with_runtime_ones() {
+        type P = ModInt<998_244_353>;
+
+        let modulus = Modulus::new(P::modulus()).unwrap();
+        let g = P::from(3u8);
+        // 3 is a primitive root modulo 998244353 = 119 * 2^23 + 1
+        assert_eq!(g.pow(998_244_352), P::from(1u8));
+        assert_ne!(g.pow(998_244_352 / 2), P::from(1u8));
+        assert_eq!(
+            g.pow(12_345).value(),
+            modulus.residue(3u8).pow(12_345).value()
+        );
+        assert_eq!(P::from(-5i64) + P::from(5u8), P::from(0u8));
+        assert_eq!(P::from(u128::MAX).value(), (u128::MAX % 998_244_353) as u64);
+
+        let mut x = ModInt::<12>::from(5u8);
+        x /= ModInt::from(7u8);
+        assert_eq!(x.value(), 11);
+        assert_eq!(ModInt::<12>::from(4u8).checked_div(ModInt::from(2u8)), None);
+        assert_eq!(format!("{:?}", x), "11 (mod 12)");
+
+        use num_bigint::BigInt;
+
+        assert_eq!(ModInt::<7>::from(-BigInt::from(10).pow(30)).value(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "the moduli of the operands differ")]
+    fn different_moduli_panic() {
+        let _ = Modulus::new(5).unwrap().residue(1u8) + Modulus::new(7).unwrap().residue(1u8);
+    }
+}
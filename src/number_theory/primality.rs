@@ -0,0 +1,753 @@
+//! [Primality tests](https://en.wikipedia.org/wiki/Primality_test) and the neighboring primes.
+//!
+//! The primitive integers are tested deterministically: up to [u64::MAX] with the
+//! [Miller–Rabin test](https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test) for the sets of bases
+//! known to have no strong pseudoprimes in the range, and above it with the
+//! [Baillie–PSW test](https://en.wikipedia.org/wiki/Baillie%E2%80%93PSW_primality_test), which is known to have
+//! no pseudoprimes below 2^64 and is therefore exact up to it, while no pseudoprime is known at all. The big integers
+//! are tested with the Baillie–PSW test as well, which can be strengthened with the Miller–Rabin tests for more bases
+//! with [IsProbablePrime].
+//!
+//! The primes are positive, so the negative integers are not prime.
+//!
+//! ```rust
+//! use dd_maths_traits::number_theory::primality::{IsPrime, PrimeNeighbors};
+//!
+//! assert!(97u8.is_prime());
+//! assert!(!(-97i8).is_prime());
+//! assert!(18_446_744_073_709_551_557u64.is_prime());
+//! assert!(!3_215_031_751u64.is_prime());
+//! assert!(((1u128 << 89) - 1).is_prime());
+//!
+//! assert_eq!(100u32.next_prime(), Some(101));
+//! assert_eq!(100u32.prev_prime(), Some(97));
+//! assert_eq!(251u8.next_prime(), None);
+//! assert_eq!(2u8.prev_prime(), None);
+//! ```
+
+use crate::number_theory::modular::{mul_hi_u128, DynModInt, Modulus};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_bigint::{BigInt, BigUint, Sign};
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+use num_traits::{One, ToPrimitive, Zero};
+
+pub trait IsPrime {
+    fn is_prime(&self) -> bool;
+}
+
+pub trait PrimeNeighbors: Sized {
+    /// Returns the least prime greater than the integer or [None] if it is not representable.
+    fn next_prime(&self) -> Option<Self>;
+
+    /// Returns the greatest prime less than the integer or [None] if there is none.
+    fn prev_prime(&self) -> Option<Self>;
+}
+
+const SMALL_PRIMES: [u8; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+// Some(is_prime) if the trial division by the small primes decides the primality of n
+// given its remainders modulo them
+fn trial_division(is_small_prime: impl Fn(u64) -> bool, rem: impl Fn(u64) -> u64) -> Option<bool> {
+    for p in SMALL_PRIMES.map(u64::from) {
+        if is_small_prime(p) {
+            return Some(true);
+        }
+        if rem(p) == 0 {
+            return Some(false);
+        }
+    }
+    None
+}
+
+// The ring of the integers modulo the odd n greater than the small primes
+pub(crate) trait OddModulus {
+    type Residue: Clone + PartialEq;
+
+    fn residue(&self, x: u64) -> Self::Residue;
+    fn add(&self, a: &Self::Residue, b: &Self::Residue) -> Self::Residue;
+    fn sub(&self, a: &Self::Residue, b: &Self::Residue) -> Self::Residue;
+    fn mul(&self, a: &Self::Residue, b: &Self::Residue) -> Self::Residue;
+    fn half(&self, a: &Self::Residue) -> Self::Residue;
+    fn rem_u64(&self, m: u64) -> u64;
+
+    fn residue_i64(&self, x: i64) -> Self::Residue {
+        let magnitude = self.residue(x.unsigned_abs());
+        if x < 0 {
+            self.sub(&self.residue(0), &magnitude)
+        } else {
+            magnitude
+        }
+    }
+
+    // the bits of the exponent go from the most significant one
+    fn pow(&self, base: &Self::Residue, bits: impl Iterator<Item = bool>) -> Self::Residue {
+        let mut acc = self.residue(1);
+        for bit in bits {
+            acc = self.mul(&acc, &acc);
+            if bit {
+                acc = self.mul(&acc, base);
+            }
+        }
+        acc
+    }
+}
+
+// Whether n is a strong probable prime to the base, where n - 1 = d * 2^s with the odd d
+fn is_strong_probable_prime<M: OddModulus>(
+    n: &M,
+    base: &M::Residue,
+    d_bits: impl Iterator<Item = bool>,
+    s: u32,
+) -> bool {
+    let one = n.residue(1);
+    let minus_one = n.sub(&n.residue(0), &one);
+    let mut x = n.pow(base, d_bits);
+    if x == one || x == minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = n.mul(&x, &x);
+        if x == minus_one {
+            return true;
+        }
+        if x == one {
+            return false;
+        }
+    }
+    false
+}
+
+fn jacobi_u64(mut a: u64, mut n: u64) -> i32 {
+    let mut j = 1;
+    a %= n;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                j = -j;
+            }
+        }
+        core::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            j = -j;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        j
+    } else {
+        0
+    }
+}
+
+// The Jacobi symbol (d / n) for the odd d with the quadratic reciprocity
+fn jacobi<M: OddModulus>(d: i64, n: &M) -> i32 {
+    let n_mod_4 = n.rem_u64(4);
+    let a = d.unsigned_abs();
+    let mut j = jacobi_u64(n.rem_u64(a), a);
+    if d < 0 && n_mod_4 == 3 {
+        j = -j;
+    }
+    if a % 4 == 3 && n_mod_4 == 3 {
+        j = -j;
+    }
+    j
+}
+
+// Whether n, which is not a perfect square, is a strong Lucas probable prime with the parameters chosen
+// by Selfridge's method A, where n + 1 = d * 2^s with the odd d
+fn is_strong_lucas_probable_prime<M: OddModulus>(
+    n: &M,
+    mut d_bits: impl Iterator<Item = bool>,
+    s: u32,
+) -> bool {
+    // D is the first of 5, -7, 9, -11, ... with (D / n) = -1, which exists, since n is not a perfect square
+    let mut d = 5i64;
+    loop {
+        match jacobi(d, n) {
+            -1 => break,
+            // n is greater than the small primes, so |D| < n for the first ones
+            0 if n.rem_u64(d.unsigned_abs()) == 0 => return false,
+            _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+        }
+    }
+    // P = 1 and Q = (1 - D) / 4
+    let q = n.residue_i64((1 - d) / 4);
+    let d = n.residue_i64(d);
+    // U(1) = 1, V(1) = P, and the leading bit of the exponent is 1
+    let (mut u, mut v, mut q_k) = (n.residue(1), n.residue(1), q.clone());
+    d_bits.next();
+    for bit in d_bits {
+        // U(2k) = U(k)V(k), V(2k) = V(k)^2 - 2Q^k
+        u = n.mul(&u, &v);
+        v = n.sub(&n.mul(&v, &v), &n.add(&q_k, &q_k));
+        q_k = n.mul(&q_k, &q_k);
+        if bit {
+            // U(k + 1) = (PU(k) + V(k)) / 2, V(k + 1) = (DU(k) + PV(k)) / 2
+            (u, v) = (n.half(&n.add(&u, &v)), n.half(&n.add(&n.mul(&d, &u), &v)));
+            q_k = n.mul(&q_k, &q);
+        }
+    }
+    let zero = n.residue(0);
+    if u == zero || v == zero {
+        return true;
+    }
+    for _ in 1..s {
+        v = n.sub(&n.mul(&v, &v), &n.add(&q_k, &q_k));
+        if v == zero {
+            return true;
+        }
+        q_k = n.mul(&q_k, &q_k);
+    }
+    false
+}
+
+impl OddModulus for Modulus {
+    type Residue = DynModInt;
+
+    #[inline]
+    fn residue(&self, x: u64) -> DynModInt {
+        Modulus::residue(*self, x)
+    }
+
+    #[inline]
+    fn add(&self, a: &DynModInt, b: &DynModInt) -> DynModInt {
+        *a + *b
+    }
+
+    #[inline]
+    fn sub(&self, a: &DynModInt, b: &DynModInt) -> DynModInt {
+        *a - *b
+    }
+
+    #[inline]
+    fn mul(&self, a: &DynModInt, b: &DynModInt) -> DynModInt {
+        *a * *b
+    }
+
+    fn half(&self, a: &DynModInt) -> DynModInt {
+        let (a, n) = (a.value(), self.get());
+        // (a + n) / 2 for the odd a and n
+        Modulus::residue(
+            *self,
+            if a.is_multiple_of(2) {
+                a / 2
+            } else {
+                a / 2 + n / 2 + 1
+            },
+        )
+    }
+
+    #[inline]
+    fn rem_u64(&self, m: u64) -> u64 {
+        self.get() % m
+    }
+}
+
+// Montgomery multiplication modulo the odd n with R = 2^128
+pub(crate) struct Montgomery128 {
+    n: u128,
+    // n^(-1) mod R
+    n_inv: u128,
+    r2_mod_n: u128,
+}
+
+impl Montgomery128 {
+    pub(crate) fn new(n: u128) -> Self {
+        let mut n_inv = n;
+        for _ in 0..6 {
+            n_inv = n_inv.wrapping_mul(2u128.wrapping_sub(n.wrapping_mul(n_inv)));
+        }
+        let mut r2_mod_n = u128::MAX % n + 1;
+        let mut m = Montgomery128 { n, n_inv, r2_mod_n };
+        // R^2 mod n = R mod n doubled 128 times
+        for _ in 0..128 {
+            r2_mod_n = m.add(&r2_mod_n, &r2_mod_n);
+        }
+        m.r2_mod_n = r2_mod_n;
+        m
+    }
+}
+
+impl OddModulus for Montgomery128 {
+    type Residue = u128;
+
+    #[inline]
+    fn residue(&self, x: u64) -> u128 {
+        self.mul(&(u128::from(x) % self.n), &self.r2_mod_n)
+    }
+
+    #[inline]
+    fn add(&self, a: &u128, b: &u128) -> u128 {
+        let (sum, is_overflowing) = a.overflowing_add(*b);
+        if is_overflowing || sum >= self.n {
+            sum.wrapping_sub(self.n)
+        } else {
+            sum
+        }
+    }
+
+    #[inline]
+    fn sub(&self, a: &u128, b: &u128) -> u128 {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(*b).wrapping_add(self.n)
+        }
+    }
+
+    // Montgomery's REDC of the 256-bit product, whose low half is cancelled by qn with q = ab * n^(-1) mod R
+    #[inline]
+    fn mul(&self, a: &u128, b: &u128) -> u128 {
+        let (hi, lo) = (mul_hi_u128(*a, *b), a.wrapping_mul(*b));
+        let q = lo.wrapping_mul(self.n_inv);
+        let qn_hi = mul_hi_u128(q, self.n);
+        if hi < qn_hi {
+            hi.wrapping_sub(qn_hi).wrapping_add(self.n)
+        } else {
+            hi - qn_hi
+        }
+    }
+
+    #[inline]
+    fn half(&self, a: &u128) -> u128 {
+        // the halving commutes with the Montgomery form
+        if a.is_multiple_of(2) {
+            a / 2
+        } else {
+            a / 2 + self.n / 2 + 1
+        }
+    }
+
+    #[inline]
+    fn rem_u64(&self, m: u64) -> u64 {
+        // the remainder is less than m
+        (self.n % u128::from(m)) as u64
+    }
+}
+
+fn bits_u128(d: u128) -> impl Iterator<Item = bool> {
+    (0..u128::BITS - d.leading_zeros())
+        .rev()
+        .map(move |i| d >> i & 1 == 1)
+}
+
+fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if let Some(is_prime) = trial_division(|p| n == p, |p| n % p) {
+        return is_prime;
+    }
+    if n < 47 * 47 {
+        return true;
+    }
+    // the sets of bases by Jaeschke and by Sinclair
+    let bases: &[u64] = if n < 4_759_123_141 {
+        &[2, 7, 61]
+    } else {
+        &[2, 325, 9375, 28178, 450775, 9780504, 1795265022]
+    };
+    let modulus = Modulus::new(n).unwrap();
+    let s = (n - 1).trailing_zeros();
+    let d = u128::from((n - 1) >> s);
+    bases.iter().all(|&base| {
+        let base = Modulus::residue(modulus, base);
+        // the bases divisible by n are skipped
+        base.value() == 0 || is_strong_probable_prime(&modulus, &base, bits_u128(d), s)
+    })
+}
+
+fn is_prime_u128(n: u128) -> bool {
+    if let Ok(n) = u64::try_from(n) {
+        return is_prime_u64(n);
+    }
+    // the remainders are less than the small primes
+    if trial_division(|_| false, |p| (n % u128::from(p)) as u64).is_some() {
+        return false;
+    }
+    let modulus = Montgomery128::new(n);
+    let s = (n - 1).trailing_zeros();
+    if !is_strong_probable_prime(&modulus, &modulus.residue(2), bits_u128((n - 1) >> s), s) {
+        return false;
+    }
+    let root = n.isqrt();
+    if root * root == n {
+        return false;
+    }
+    // n is odd and less than u128::MAX, which is divisible by 3
+    let s = (n + 1).trailing_zeros();
+    is_strong_lucas_probable_prime(&modulus, bits_u128((n + 1) >> s), s)
+}
+
+macro_rules! impl_is_prime_for_prim_unsigned_int {
+    ($(($t:ty, $is_prime:ident, $as:ty)),+) => {
+        $(
+            impl IsPrime for $t {
+                #[inline]
+                fn is_prime(&self) -> bool {
+                    $is_prime(*self as $as)
+                }
+            }
+
+            impl PrimeNeighbors for $t {
+                fn next_prime(&self) -> Option<Self> {
+                    if *self < 2 {
+                        return Some(2);
+                    }
+                    // the odd candidates after the integer, which is less than the odd maximum
+                    let mut n = self.checked_add(1)? | 1;
+                    while !n.is_prime() {
+                        n = n.checked_add(2)?;
+                    }
+                    Some(n)
+                }
+
+                fn prev_prime(&self) -> Option<Self> {
+                    match *self {
+                        0..=2 => None,
+                        3 => Some(2),
+                        // the odd candidates before the integer
+                        _ => (3..=(*self - 2) | 1).rev().step_by(2).find(IsPrime::is_prime),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_is_prime_for_prim_signed_int {
+    ($($t:ty),+) => {
+        $(
+            impl IsPrime for $t {
+                #[inline]
+                fn is_prime(&self) -> bool {
+                    *self > 0 && self.unsigned_abs().is_prime()
+                }
+            }
+
+            impl PrimeNeighbors for $t {
+                fn next_prime(&self) -> Option<Self> {
+                    if *self < 2 {
+                        return Some(2);
+                    }
+                    <$t>::try_from(self.unsigned_abs().next_prime()?).ok()
+                }
+
+                fn prev_prime(&self) -> Option<Self> {
+                    if *self < 0 {
+                        return None;
+                    }
+                    // the prime is less than the integer
+                    self.unsigned_abs().prev_prime().map(|p| p as $t)
+                }
+            }
+        )+
+    };
+}
+
+impl_is_prime_for_prim_unsigned_int!(
+    (u8, is_prime_u64, u64),
+    (u16, is_prime_u64, u64),
+    (u32, is_prime_u64, u64),
+    (u64, is_prime_u64, u64),
+    (usize, is_prime_u64, u64),
+    (u128, is_prime_u128, u128)
+);
+impl_is_prime_for_prim_signed_int!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+pub(crate) struct BigOddModulus(pub(crate) BigUint);
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl OddModulus for BigOddModulus {
+    type Residue = BigUint;
+
+    #[inline]
+    fn residue(&self, x: u64) -> BigUint {
+        BigUint::from(x) % &self.0
+    }
+
+    #[inline]
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let sum = a + b;
+        if sum >= self.0 {
+            sum - &self.0
+        } else {
+            sum
+        }
+    }
+
+    #[inline]
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a >= b {
+            a - b
+        } else {
+            a + &self.0 - b
+        }
+    }
+
+    #[inline]
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % &self.0
+    }
+
+    #[inline]
+    fn half(&self, a: &BigUint) -> BigUint {
+        if a.bit(0) {
+            (a + &self.0) >> 1u8
+        } else {
+            a >> 1u8
+        }
+    }
+
+    #[inline]
+    fn rem_u64(&self, m: u64) -> u64 {
+        (&self.0 % m).to_u64().unwrap()
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn bits_big(d: &BigUint) -> impl Iterator<Item = bool> + '_ {
+    (0..d.bits()).rev().map(move |i| d.bit(i))
+}
+
+// The Baillie–PSW test and the Miller–Rabin tests for the given number of the pseudorandom bases
+// in [3, n - 2], which are derived from n for the reproducibility
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+fn is_probable_prime_big(n: &BigUint, rounds: u32) -> bool {
+    if let Some(n) = n.to_u128() {
+        return is_prime_u128(n);
+    }
+    if trial_division(|_| false, |p| (n % p).to_u64().unwrap()).is_some() {
+        return false;
+    }
+    let modulus = BigOddModulus(n.clone());
+    let n_minus_one = n - 1u8;
+    let s = n_minus_one.trailing_zeros().unwrap() as u32;
+    let d = &n_minus_one >> s;
+    if !is_strong_probable_prime(&modulus, &modulus.residue(2), bits_big(&d), s) {
+        return false;
+    }
+    let root = n.sqrt();
+    if &root * &root == *n {
+        return false;
+    }
+    let n_plus_one = n + 1u8;
+    let t = n_plus_one.trailing_zeros().unwrap() as u32;
+    if !is_strong_lucas_probable_prime(&modulus, bits_big(&(&n_plus_one >> t)), t) {
+        return false;
+    }
+    let mut state = n
+        .iter_u64_digits()
+        .fold(0x9E37_79B9_7F4A_7C15u64, |acc, digit| {
+            (acc ^ digit).wrapping_mul(0xBF58_476D_1CE4_E5B9)
+        });
+    (0..rounds).all(|_| {
+        // xorshift64*
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        let base = BigUint::from(state.wrapping_mul(0x2545_F491_4F6C_DD1D)) % (n - 4u8) + 3u8;
+        is_strong_probable_prime(&modulus, &base, bits_big(&d), s)
+    })
+}
+
+/// The primality tests that can be strengthened at the cost of the time.
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+pub trait IsProbablePrime {
+    /// Returns whether the integer passes the Baillie–PSW test and the Miller–Rabin tests for `rounds` more bases,
+    /// which are pseudorandom but derived from the integer, so that the result is reproducible.
+    /// Every round passes a composite integer with the probability less than 1/4.
+    fn is_probable_prime(&self, rounds: u32) -> bool;
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl IsProbablePrime for BigUint {
+    #[inline]
+    fn is_probable_prime(&self, rounds: u32) -> bool {
+        is_probable_prime_big(self, rounds)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl IsProbablePrime for BigInt {
+    #[inline]
+    fn is_probable_prime(&self, rounds: u32) -> bool {
+        self.sign() == Sign::Plus && self.magnitude().is_probable_prime(rounds)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl IsPrime for BigUint {
+    /// Returns whether the integer passes the Baillie–PSW test, which is exact below 2^64.
+    #[inline]
+    fn is_prime(&self) -> bool {
+        self.is_probable_prime(0)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl IsPrime for BigInt {
+    #[inline]
+    fn is_prime(&self) -> bool {
+        self.is_probable_prime(0)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl PrimeNeighbors for BigUint {
+    fn next_prime(&self) -> Option<Self> {
+        if *self < BigUint::from(2u8) {
+            return Some(BigUint::from(2u8));
+        }
+        let mut n = (self + 1u8) | BigUint::one();
+        while !n.is_prime() {
+            n += 2u8;
+        }
+        Some(n)
+    }
+
+    fn prev_prime(&self) -> Option<Self> {
+        if let Some(n) = self.to_u128() {
+            return n.prev_prime().map(BigUint::from);
+        }
+        // the integer is greater than 2
+        let mut n = (self - 2u8) | BigUint::one();
+        while !n.is_prime() {
+            n -= 2u8;
+        }
+        Some(n)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+#[cfg(any(doc, test, doctest, feature = "bigint"))]
+impl PrimeNeighbors for BigInt {
+    fn next_prime(&self) -> Option<Self> {
+        if self.sign() == Sign::Minus || self.is_zero() {
+            return Some(BigInt::from(2u8));
+        }
+        self.magnitude().next_prime().map(BigInt::from)
+    }
+
+    fn prev_prime(&self) -> Option<Self> {
+        if self.sign() == Sign::Minus {
+            return None;
+        }
+        self.magnitude().prev_prime().map(BigInt::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number_theory::primality::{IsPrime, IsProbablePrime, PrimeNeighbors};
+
+    fn sieve(n: usize) -> Vec<bool> {
+        let mut is_prime = vec![true; n];
+        is_prime[0] = false;
+        is_prime[1] = false;
+        for i in 2..n {
+            if is_prime[i] {
+                (i * i..n).step_by(i).for_each(|j| is_prime[j] = false);
+            }
+        }
+        is_prime
+    }
+
+    #[test]
+    fn primality_matches_the_sieve() {
+        let is_prime = sieve(1 << 20);
+        for (n, &expected) in is_prime.iter().enumerate() {
+            assert_eq!((n as u32).is_prime(), expected, "{}", n);
+            assert_eq!((n as u128).is_prime(), expected);
+        }
+        for n in 0..=255u8 {
+            assert_eq!(n.is_prime(), is_prime[n as usize]);
+            assert_eq!((n as i8).is_prime(), n < 128 && is_prime[n as usize]);
+        }
+        for n in 0..1 << 16 {
+            let next = (n + 1..).find(|&m| is_prime[m]).unwrap();
+            assert_eq!((n as u32).next_prime(), Some(next as u32));
+            let prev = (0..n).rev().find(|&m| is_prime[m]);
+            assert_eq!((n as u32).prev_prime(), prev.map(|p| p as u32));
+        }
+    }
+
+    #[test]
+    fn pseudoprimes_are_rejected() {
+        // the strong pseudoprimes to the bases 2, 3, 5, 7, to the first 12 primes,
+        // the Carmichael numbers and the products of two large primes
+        let composites = [
+            2_047u64,
+            3_215_031_751,
+            3_825_123_056_546_413_051,
+            561,
+            41_041,
+            4_294_967_297,
+            4_294_967_279 * 4_294_967_291,
+            18_446_744_073_709_551_615,
+        ];
+        for n in composites {
+            assert!(!n.is_prime(), "{}", n);
+            assert!(!(n as u128).is_prime());
+        }
+        assert!(!318_665_857_834_031_151_167_461u128.is_prime());
+        assert!(!3_317_044_064_679_887_385_961_981u128.is_prime());
+        // the Mersenne primes and composites, and the products of the primes above 2^64
+        for p in [61u32, 89, 107, 127] {
+            assert!(((1u128 << p) - 1).is_prime());
+        }
+        for p in [67u32, 101, 103, 109, 113] {
+            assert!(!((1u128 << p) - 1).is_prime());
+        }
+        let two_64 = 1u128 << 64;
+        assert_eq!(
+            (two_64..two_64 + 20_000).filter(IsPrime::is_prime).count(),
+            425
+        );
+        let p = 18_446_744_073_709_551_629u128;
+        assert!(p.is_prime());
+        assert!(!(p * 5_000_000_029).is_prime());
+        assert!(!(p * 18_446_744_073_709_551_557).is_prime());
+        assert_eq!(u64::MAX.next_prime(), None);
+        assert_eq!(
+            u128::from(u64::MAX).next_prime(),
+            Some(18_446_744_073_709_551_629)
+        );
+        assert_eq!(u128::MAX.prev_prime(), Some(u128::MAX - 158));
+        assert_eq!((u128::MAX - 158).prev_prime(), Some(u128::MAX - 172));
+        assert_eq!(i16::MAX.prev_prime(), Some(32_749));
+        assert_eq!(i16::MAX.next_prime(), None);
+        assert_eq!((-5i32).next_prime(), Some(2));
+    }
+
+    #[test]
+    fn big_integers_are_tested() {
+        use num_bigint::{BigInt, BigUint};
+
+        let mersenne = |p: u32| (BigUint::from(1u8) << p) - 1u8;
+        for p in [127, 521, 607, 1279] {
+            assert!(mersenne(p).is_prime());
+            assert!(mersenne(p).is_probable_prime(8));
+        }
+        for p in [128, 523, 1277] {
+            assert!(!mersenne(p).is_prime());
+        }
+        assert!(!(mersenne(127) * mersenne(521)).is_prime());
+        assert!(!(-BigInt::from(mersenne(127))).is_prime());
+        assert_eq!(BigUint::from(1u8).next_prime(), Some(BigUint::from(2u8)));
+        // 2^128 + 51 is the least prime above 2^128
+        let two_128 = BigUint::from(1u8) << 128u8;
+        let count = (0..5_000u32).filter(|i| (&two_128 + *i).is_prime()).count();
+        assert_eq!(count, 47);
+        assert_eq!(two_128.next_prime(), Some(&two_128 + 51u8));
+        assert_eq!((&two_128 + 51u8).prev_prime(), Some(&two_128 - 159u8));
+    }
+}
@@ -0,0 +1,485 @@
+//! The primes up to [u64::MAX]: the [segmented sieve of Eratosthenes](https://en.wikipedia.org/wiki/Sieve_of_Eratosthenes#Segmented_sieve)
+//! over a range, the lazy unbounded iterator over the primes and the
+//! [prime-counting function](https://en.wikipedia.org/wiki/Prime-counting_function) `π(n)`.
+//!
+//! Nothing is allocated, the buffers are provided by the caller instead. The sieve uses the wheel of 2, 3 and 5,
+//! so a byte of its buffer covers 30 integers with its 8 residues coprime with 30, and a segment covers
+//! `30 * buf.len()` integers. The primes up to the square root of the segment that sieve it are regenerated for
+//! every segment with the sieve over the fixed buffer, so the buffers of at least `√upper / 30` bytes are the most
+//! efficient. `π(n)` is computed with [Meissel's formula](https://en.wikipedia.org/wiki/Meissel%E2%80%93Lehmer_algorithm)
+//! and needs the buffer for the primes up to `√n`.
+//!
+//! ```rust
+//! use dd_maths_traits::number_theory::primes::{prime_pi, primes, PrimesInRange};
+//!
+//! let mut buf = [0u8; 64];
+//! let primes_in_range: Vec<u64> = PrimesInRange::new(90, 110, &mut buf).unwrap().collect();
+//! assert_eq!(primes_in_range, [97, 101, 103, 107, 109]);
+//!
+//! assert_eq!(primes(&mut buf).unwrap().nth(999), Some(7919));
+//!
+//! let mut primes_buf = [0u32; 1229];
+//! assert_eq!(prime_pi(100_000_000, &mut primes_buf), Some(5_761_455));
+//! ```
+
+#[cfg(any(doc, test, doctest, feature = "adt_const_params"))]
+use crate::ranges::ClosedIntvl;
+
+// The residues modulo 30 coprime with it, in the order of the bits of the bytes of the sieve
+const WHEEL: [u8; 8] = [1, 7, 11, 13, 17, 19, 23, 29];
+
+// The bits of the residues modulo 30 in the bytes of the sieve, 8 for the residues not coprime with 30
+const WHEEL_BITS: [u8; 30] = {
+    let mut bits = [8; 30];
+    let mut i = 0;
+    while i < WHEEL.len() {
+        bits[WHEEL[i] as usize] = i as u8;
+        i += 1;
+    }
+    bits
+};
+
+// The primes below 2^16, which sieve the integers below 2^32
+pub(crate) static SMALL_PRIMES: [u16; 6542] = {
+    let mut is_composite = [false; 1 << 16];
+    let mut primes = [0; 6542];
+    let (mut n, mut len) = (2, 0);
+    while n < 1 << 16 {
+        if !is_composite[n] {
+            primes[len] = n as u16;
+            len += 1;
+            let mut multiple = n * n;
+            while multiple < 1 << 16 {
+                is_composite[multiple] = true;
+                multiple += n;
+            }
+        }
+        n += 1;
+    }
+    primes
+};
+
+// The primorials P_k of the first k ≤ 6 primes, the periods of φ(x, k) in x
+const PRIMORIALS: [usize; 7] = [1, 2, 6, 30, 210, 2310, 30030];
+
+// φ(y, k) for y in [0, P_k) and k in [1, 6], the tables for the consecutive k one after another
+static PHI_TABLE: [u16; 32_588] = {
+    let mut table = [0; 32_588];
+    let (mut k, mut offset) = (1, 0);
+    while k <= 6 {
+        let (primorial, mut count, mut y) = (PRIMORIALS[k], 0, 1);
+        while y < primorial {
+            let mut i = 0;
+            while i < k && y % SMALL_PRIMES[i] as usize != 0 {
+                i += 1;
+            }
+            count += (i == k) as u16;
+            table[offset + y] = count;
+            y += 1;
+        }
+        offset += primorial;
+        k += 1;
+    }
+    table
+};
+
+// φ(x, k) for k ≤ 6 in the closed form φ(x, k) = ⌊x / P_k⌋ φ(P_k, k) + φ(x mod P_k, k)
+fn phi_small(x: u64, k: usize) -> u64 {
+    if k == 0 {
+        return x;
+    }
+    let offset: usize = PRIMORIALS[1..k].iter().sum();
+    let primorial = PRIMORIALS[k] as u64;
+    // φ(P_k, k) = φ(P_k - 1, k)
+    let period = u64::from(PHI_TABLE[offset + PRIMORIALS[k] - 1]);
+    x / primorial * period + u64::from(PHI_TABLE[offset + (x % primorial) as usize])
+}
+
+// Sets the bits of the integers in [base, base + 30 * buf.len()) for the multiple of 30 base that are coprime
+// with 30 and are not the multiples of the increasing base primes, which are greater than 5, other than themselves.
+fn sieve_segment(buf: &mut [u8], base: u64, base_primes: impl Iterator<Item = u64>) {
+    buf.fill(u8::MAX);
+    if base == 0 {
+        // 1 is not prime
+        buf[0] &= !1;
+    }
+    let (base, end) = (u128::from(base), u128::from(base) + 30 * buf.len() as u128);
+    for p in base_primes {
+        let p = u128::from(p);
+        if p * p >= end {
+            break;
+        }
+        // the multiples pm ≥ max(p², base) with m ≡ r (mod 30) for each r in the wheel are pm + 30pk,
+        // which share the bit and are p bytes apart
+        let least_m = p.max(base.div_ceil(p));
+        for r in WHEEL {
+            let m = least_m + (u128::from(r) + 30 - least_m % 30) % 30;
+            let multiple = p * m;
+            if multiple >= end {
+                continue;
+            }
+            let mask = !(1 << WHEEL_BITS[(multiple % 30) as usize]);
+            let first = ((multiple - base) / 30) as usize;
+            for byte in buf[first..].iter_mut().step_by(p as usize) {
+                *byte &= mask;
+            }
+        }
+    }
+}
+
+// The iterator over the integers in [lower, upper] with the set bits after sieving the segments in the buffer
+#[derive(Clone, Debug)]
+struct WheelSieve<B> {
+    buf: B,
+    lower: u64,
+    upper: u64,
+    // the multiple of 30 that starts the current segment, the current byte and whether the segment is sieved
+    base: u64,
+    byte: usize,
+    is_sieved: bool,
+    is_done: bool,
+}
+
+impl<B: AsMut<[u8]>> WheelSieve<B> {
+    fn new(buf: B, lower: u64, upper: u64) -> Self {
+        Self {
+            buf,
+            lower,
+            upper,
+            base: lower - lower % 30,
+            byte: 0,
+            is_sieved: false,
+            is_done: lower > upper,
+        }
+    }
+
+    fn next_with(&mut self, mut sieve: impl FnMut(&mut [u8], u64, u64)) -> Option<u64> {
+        while !self.is_done {
+            let buf = self.buf.as_mut();
+            let end = u128::from(self.base) + 30 * buf.len() as u128;
+            if !self.is_sieved {
+                // the greatest integer of the segment to be yielded
+                let last = (end - 1).min(u128::from(self.upper)) as u64;
+                sieve(buf, self.base, last);
+                self.is_sieved = true;
+                self.byte = 0;
+            }
+            while let Some(bits) = buf.get_mut(self.byte) {
+                if *bits == 0 {
+                    self.byte += 1;
+                    continue;
+                }
+                let residue = WHEEL[bits.trailing_zeros() as usize];
+                *bits &= *bits - 1;
+                let n = u128::from(self.base) + 30 * self.byte as u128 + u128::from(residue);
+                if n > u128::from(self.upper) {
+                    self.is_done = true;
+                    return None;
+                }
+                if n >= u128::from(self.lower) {
+                    return Some(n as u64);
+                }
+            }
+            match u64::try_from(end) {
+                Ok(base) if base <= self.upper => {
+                    self.base = base;
+                    self.is_sieved = false;
+                }
+                _ => self.is_done = true,
+            }
+        }
+        None
+    }
+}
+
+// The primes from 7 up to the limit, which is at most 2^32, sieved with the small primes
+#[derive(Clone, Debug)]
+struct BasePrimes(WheelSieve<[u8; 2048]>);
+
+impl BasePrimes {
+    fn new(limit: u64) -> Self {
+        Self(WheelSieve::new([0; 2048], 7, limit))
+    }
+}
+
+impl Iterator for BasePrimes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.0.next_with(|buf, base, _| {
+            sieve_segment(buf, base, SMALL_PRIMES[3..].iter().map(|&p| u64::from(p)))
+        })
+    }
+}
+
+/// The increasing iterator over the primes in a range, sieved in the segments of the caller-provided buffer.
+#[derive(Debug)]
+pub struct PrimesInRange<'a> {
+    // the number of the primes 2, 3 and 5, which the wheel skips, already considered
+    small: usize,
+    sieve: WheelSieve<&'a mut [u8]>,
+}
+
+impl<'a> PrimesInRange<'a> {
+    /// Returns the iterator over the primes in `[lower, upper]` or [None] if the buffer is empty.
+    pub fn new(lower: u64, upper: u64, buf: &'a mut [u8]) -> Option<Self> {
+        if buf.is_empty() {
+            return None;
+        }
+        Some(Self {
+            small: 0,
+            sieve: WheelSieve::new(buf, lower, upper),
+        })
+    }
+
+    /// Returns the iterator over the primes in the interval or [None] if the buffer is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "adt_const_params")))]
+    #[cfg(any(doc, test, doctest, feature = "adt_const_params"))]
+    #[inline]
+    pub fn in_interval(interval: &ClosedIntvl<u64>, buf: &'a mut [u8]) -> Option<Self> {
+        let bounds = interval.bounds();
+        Self::new(bounds.lower_bound, bounds.upper_bound, buf)
+    }
+}
+
+impl Iterator for PrimesInRange<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let (lower, upper) = (self.sieve.lower, self.sieve.upper);
+        while let Some(&p) = [2, 3, 5].get(self.small) {
+            self.small += 1;
+            if (lower..=upper).contains(&p) {
+                return Some(p);
+            }
+        }
+        self.sieve
+            .next_with(|buf, base, last| sieve_segment(buf, base, BasePrimes::new(last.isqrt())))
+    }
+}
+
+impl core::iter::FusedIterator for PrimesInRange<'_> {}
+
+/// Returns the lazy iterator over all primes, which ends only after the greatest prime below 2^64,
+/// or [None] if the buffer is empty.
+#[inline]
+pub fn primes(buf: &mut [u8]) -> Option<PrimesInRange<'_>> {
+    PrimesInRange::new(0, u64::MAX, buf)
+}
+
+/// Returns the number of the primes up to `n` or [None] if the buffer cannot hold the primes up to `√n`,
+/// which are stored in it. The caller needs `π(√n)` slots, e.g. 1229 for `n` below 10^8, 9592 below 10^10
+/// and at most `π(2^32) = 203_280_221` for any `n`.
+pub fn prime_pi(n: u64, buf: &mut [u32]) -> Option<u64> {
+    let root = n.isqrt();
+    let mut len = 0;
+    for p in [2, 3, 5]
+        .into_iter()
+        .take_while(|&p| p <= root)
+        .chain(BasePrimes::new(root))
+    {
+        *buf.get_mut(len)? = p as u32;
+        len += 1;
+    }
+    let primes = &buf[..len];
+    Some(
+        Lehmer {
+            primes,
+            limit: root,
+        }
+        .pi(n),
+    )
+}
+
+// The primes up to the limit, which count the primes up to the square of the limit
+#[derive(Clone, Copy)]
+struct Lehmer<'a> {
+    primes: &'a [u32],
+    limit: u64,
+}
+
+impl Lehmer<'_> {
+    // π(x) for x at most the square of the limit
+    fn pi(self, x: u64) -> u64 {
+        if x <= self.limit {
+            return self.primes.partition_point(|&p| u64::from(p) <= x) as u64;
+        }
+        // Meissel's π(x) = φ(x, a) + a - 1 - Σ_{a < i ≤ b} (π(x / p_i) - i + 1) with a = π(x^(1/3)) and
+        // b = π(x^(1/2)), as the integers up to x have at most two prime factors above x^(1/3)
+        let a = self.pi(icbrt(x)) as usize;
+        let b = self.pi(x.isqrt()) as usize;
+        let mut sum = self.phi(x, a) + a as u64 - 1;
+        if a == b {
+            return sum;
+        }
+        // x / p_i increases as i decreases, so the primes above the limit up to x / p_{a+1} < x^(2/3)
+        // are counted by the sieve on the way
+        let p = |i: usize| u64::from(self.primes[i - 1]);
+        let mut buf = [0; 4096];
+        let mut primes = PrimesInRange::new(self.limit + 1, x / p(a + 1), &mut buf)
+            .unwrap()
+            .peekable();
+        let mut count = self.primes.len() as u64;
+        for i in (a + 1..=b).rev() {
+            let w = x / p(i);
+            let pi_w = if w <= self.limit {
+                self.pi(w)
+            } else {
+                while primes.next_if(|&q| q <= w).is_some() {
+                    count += 1;
+                }
+                count
+            };
+            sum -= pi_w - (i as u64 - 1);
+        }
+        sum
+    }
+
+    // φ(x, a), the number of the integers in [1, x] not divisible by any of the first a primes
+    fn phi(self, x: u64, a: usize) -> u64 {
+        if a <= 6 {
+            return phi_small(x, a);
+        }
+        let p = u64::from(self.primes[a - 1]);
+        if p >= x {
+            // only 1 remains
+            return 1;
+        }
+        if p * p >= x && x <= self.limit {
+            // only 1 and the primes in (p_a, x] remain
+            return 1 + self.pi(x) - a as u64;
+        }
+        // φ(x, a) = φ(x, 6) - Σ_{6 < i ≤ a} φ(x / p_i, i - 1) unrolls the recursion in a,
+        // where only 1 remains in the terms with p_i² > x
+        let mut sum = phi_small(x, 6);
+        for i in 7..=a {
+            let p = u64::from(self.primes[i - 1]);
+            if p * p > x {
+                return sum - (a - i + 1) as u64;
+            }
+            sum -= self.phi(x / p, i - 1);
+        }
+        sum
+    }
+}
+
+// ⌊∛x⌋ digit by digit in binary
+fn icbrt(x: u64) -> u64 {
+    let mut r: u64 = 0;
+    for shift in (0..22).rev() {
+        let candidate = r | 1 << shift;
+        if candidate.checked_pow(3).is_some_and(|cube| cube <= x) {
+            r = candidate;
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number_theory::{
+        primality::IsPrime,
+        primes::{icbrt, phi_small, prime_pi, primes, PrimesInRange, SMALL_PRIMES},
+    };
+
+    #[test]
+    fn segmented_sieves_find_the_primes_in_ranges() {
+        assert_eq!(SMALL_PRIMES.last(), Some(&65_521));
+        let is_prime: Vec<bool> = (0..20_000u64).map(|n| n.is_prime()).collect();
+        for len in [1, 2, 7, 64] {
+            let mut buf = vec![0; len];
+            for (lower, upper) in [
+                (0, 19_999),
+                (2, 2),
+                (3, 5),
+                (29, 31),
+                (1000, 1030),
+                (7919, 19_999),
+                (24, 28),
+            ] {
+                let primes: Vec<u64> = PrimesInRange::new(lower, upper, &mut buf)
+                    .unwrap()
+                    .collect();
+                let expected: Vec<u64> =
+                    (lower..=upper).filter(|&n| is_prime[n as usize]).collect();
+                assert_eq!(primes, expected);
+            }
+        }
+        assert_eq!(PrimesInRange::new(10, 9, &mut [0; 8]).unwrap().count(), 0);
+        assert!(PrimesInRange::new(0, 10, &mut []).is_none());
+
+        let mut buf = [0; 100];
+        for lower in [1 << 40, 999_999_999_989, (1 << 50) - 3000] {
+            let primes: Vec<u64> = PrimesInRange::new(lower, lower + 10_000, &mut buf)
+                .unwrap()
+                .collect();
+            let expected: Vec<u64> = (lower..=lower + 10_000).filter(|n| n.is_prime()).collect();
+            assert_eq!(primes, expected);
+        }
+    }
+
+    #[test]
+    fn primes_are_unbounded() {
+        let mut buf = [0; 16];
+        let mut primes = primes(&mut buf).unwrap();
+        let mut previous = 1;
+        for _ in 0..10_000 {
+            let p = primes.next().unwrap();
+            assert!(p > previous && p.is_prime());
+            assert!((previous + 1..p).all(|n| !n.is_prime()));
+            previous = p;
+        }
+        assert_eq!(previous, 104_729);
+    }
+
+    #[test]
+    fn small_phi_has_the_closed_form() {
+        for k in 0..=6 {
+            let mut count = 0;
+            for x in 1..70_000u64 {
+                count += u64::from(SMALL_PRIMES[..k].iter().all(|&p| x % u64::from(p) != 0));
+                assert_eq!(phi_small(x, k), count);
+            }
+        }
+        assert_eq!(phi_small(0, 6), 0);
+        // u64::MAX = 15 mod 30030
+        assert_eq!(phi_small(u64::MAX, 6), u64::MAX / 30030 * 5760 + 1);
+    }
+
+    #[test]
+    fn prime_pi_counts_the_primes() {
+        let mut buf = [0; 10_000];
+        let mut count = 0;
+        for n in 0..5000u64 {
+            count += u64::from(n.is_prime());
+            assert_eq!(prime_pi(n, &mut buf), Some(count));
+        }
+        let pi = [
+            4,
+            25,
+            168,
+            1229,
+            9592,
+            78_498,
+            664_579,
+            5_761_455,
+            50_847_534,
+            455_052_511,
+        ];
+        for (k, expected) in (1..).zip(pi) {
+            assert_eq!(prime_pi(10u64.pow(k), &mut buf), Some(expected));
+        }
+        assert_eq!(prime_pi(1 << 32, &mut buf), Some(203_280_221));
+        // π(10^6) = 78_498
+        let mut buf = vec![0; 78_498];
+        assert_eq!(prime_pi(10u64.pow(11), &mut buf), Some(4_118_054_813));
+        assert_eq!(prime_pi(10u64.pow(12), &mut buf), Some(37_607_912_018));
+        assert_eq!(prime_pi(10u64.pow(8), &mut [0; 1228]), None);
+        assert_eq!(prime_pi(10u64.pow(8), &mut [0; 1229]), Some(5_761_455));
+
+        for x in (0..1000).chain([u64::MAX, 1 << 63, 999_999_999_999]) {
+            let r = icbrt(x);
+            assert!(r.pow(3) <= x && (r + 1).checked_pow(3).is_none_or(|cube| cube > x));
+        }
+    }
+}
@@ -26,6 +26,21 @@ pub struct BoundsKinds {
 #[cfg(any(doc, test, doctest, feature = "adt_const_params"))]
 pub struct Range<T, const BOUNDS_KINDS: BoundsKinds>(Bounds<T>);
 
+#[cfg(any(doc, test, doctest, feature = "adt_const_params"))]
+impl<T, const BOUNDS_KINDS: BoundsKinds> Range<T, BOUNDS_KINDS> {
+    /// Returns the range with the bounds.
+    #[inline(always)]
+    pub const fn new(bounds: Bounds<T>) -> Self {
+        Self(bounds)
+    }
+
+    /// Returns the bounds of the range.
+    #[inline(always)]
+    pub fn bounds(&self) -> &Bounds<T> {
+        &self.0
+    }
+}
+
 #[cfg(any(doc, test, doctest, feature = "adt_const_params"))]
 const CLOSED_INTVL_BOUNDS_KINDS: BoundsKinds = BoundsKinds {
     lower_bound_kind: BoundKind::Closed,
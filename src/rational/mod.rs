@@ -0,0 +1,602 @@
+//! Rational numbers.
+//!
+//! [Ratio] keeps the fractions in the canonical form, i.e. in lowest terms with the positive denominator,
+//! so that the equal rational numbers have equal representations and the derived [PartialEq], [Eq] and [Hash]
+//! are consistent with their values.
+//!
+//! ```rust
+//! use dd_maths_traits::rational::Ratio;
+//!
+//! let r = Ratio::new(6i32, -8).unwrap();
+//! assert_eq!((*r.numer(), *r.denom()), (-3, 4));
+//! assert_eq!(r.checked_add(&Ratio::new(1, 4).unwrap()), Ratio::new(-1, 2));
+//! assert!(r < Ratio::from_integer(0));
+//! assert_eq!(r.to_string(), "-3/4");
+//!
+//! assert_eq!(Ratio::<u8>::from_float_exact(0.375f32), Ratio::new(3, 8));
+//! assert_eq!(Ratio::<u32>::approximate_float(core::f64::consts::PI, &1000), Ratio::new(355, 113));
+//! ```
+
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use crate::num_sys::base_10::ToCanonicalRepresentationBase10AsString;
+use crate::{
+    continued_fraction::best_approximation_float,
+    int::IntSubset,
+    num_sys::{
+        base_10::ToCanonicalRepresentationBase10Into,
+        rational_expansion::{expand_fraction, RationalExpansion},
+        sign_magnitude::SignMagnitude,
+    },
+};
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+use alloc::string::String;
+use core::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+use num_traits::{
+    float::FloatCore, CheckedAdd, CheckedMul, CheckedSub, FromPrimitive, Num, ToPrimitive,
+};
+
+/// The integers that [Ratio] is defined over, i.e. the primitive and big integers.
+pub trait RatioInt: IntSubset + Clone + Ord + Num + CheckedAdd + CheckedSub + CheckedMul {}
+
+impl<T: IntSubset + Clone + Ord + Num + CheckedAdd + CheckedSub + CheckedMul> RatioInt for T {}
+
+/// A rational number in lowest terms with the positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T> Ratio<T>
+where
+    T: RatioInt,
+{
+    /// Returns the fraction `numer / denom` in lowest terms or [None] if `denom` is 0
+    /// or the canonical form is not representable, e.g. for `1 / i8::MIN`.
+    pub fn new(numer: T, denom: T) -> Option<Self> {
+        if denom.is_zero() {
+            return None;
+        }
+        let divisor = gcd(numer.clone(), denom.clone());
+        Self::with_sign_normalized(numer / divisor.clone(), denom / divisor)
+    }
+
+    #[inline]
+    pub fn from_integer(n: T) -> Self {
+        Self {
+            numer: n,
+            denom: T::one(),
+        }
+    }
+
+    #[inline]
+    pub fn zero() -> Self {
+        Self::from_integer(T::zero())
+    }
+
+    #[inline]
+    pub fn one() -> Self {
+        Self::from_integer(T::one())
+    }
+
+    #[inline(always)]
+    pub fn numer(&self) -> &T {
+        &self.numer
+    }
+
+    #[inline(always)]
+    pub fn denom(&self) -> &T {
+        &self.denom
+    }
+
+    #[inline(always)]
+    pub fn into_parts(self) -> (T, T) {
+        (self.numer, self.denom)
+    }
+
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        self.denom.is_one()
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    // The numerator and the denominator must be coprime and the denominator must be positive
+    #[inline(always)]
+    pub(crate) fn from_canonical_parts(numer: T, denom: T) -> Self {
+        Self { numer, denom }
+    }
+
+    // The numerator and the denominator must be coprime
+    fn with_sign_normalized(numer: T, denom: T) -> Option<Self> {
+        if denom < T::zero() {
+            return Some(Self {
+                numer: T::zero().checked_sub(&numer)?,
+                denom: T::zero().checked_sub(&denom)?,
+            });
+        }
+        Some(Self { numer, denom })
+    }
+
+    /// Returns the sum or [None] if it is not representable. The sum can also fail to be computed if
+    /// the numerators scaled to the least common denominator are not representable.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.checked_add_or_sub(other, false)
+    }
+
+    /// Returns the difference with the same caveat as [Ratio::checked_add].
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add_or_sub(other, true)
+    }
+
+    fn checked_add_or_sub(&self, other: &Self, is_sub: bool) -> Option<Self> {
+        // a/b ± c/d = (a * d/g ± c * b/g) / (b/g * d) with g = gcd(b, d),
+        // where only the common divisors of the numerator and g are left to cancel
+        let g = gcd(self.denom.clone(), other.denom.clone());
+        let (b, d) = (
+            self.denom.clone() / g.clone(),
+            other.denom.clone() / g.clone(),
+        );
+        let lhs = self.numer.checked_mul(&d)?;
+        let rhs = other.numer.checked_mul(&b)?;
+        let numer = if is_sub {
+            lhs.checked_sub(&rhs)?
+        } else {
+            lhs.checked_add(&rhs)?
+        };
+        let g = gcd(numer.clone(), g);
+        let denom = b.checked_mul(&(other.denom.clone() / g.clone()))?;
+        Self::with_sign_normalized(numer / g, denom)
+    }
+
+    /// Returns the product or [None] if it is not representable.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        // the cross-cancellation keeps the intermediate values as small as the result
+        let g1 = gcd(self.numer.clone(), other.denom.clone());
+        let g2 = gcd(other.numer.clone(), self.denom.clone());
+        let numer =
+            (self.numer.clone() / g1.clone()).checked_mul(&(other.numer.clone() / g2.clone()))?;
+        let denom = (self.denom.clone() / g2).checked_mul(&(other.denom.clone() / g1))?;
+        Self::with_sign_normalized(numer, denom)
+    }
+
+    /// Returns [None] if `other` is 0 or the quotient is not representable.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        self.checked_mul(&other.checked_recip()?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            numer: T::zero().checked_sub(&self.numer)?,
+            denom: self.denom.clone(),
+        })
+    }
+
+    /// Returns the reciprocal or [None] if `self` is 0 or the reciprocal is not representable.
+    pub fn checked_recip(&self) -> Option<Self> {
+        if self.numer.is_zero() {
+            return None;
+        }
+        Self::with_sign_normalized(self.denom.clone(), self.numer.clone())
+    }
+
+    /// Returns the greatest integer not greater than `self`.
+    pub fn floor(&self) -> T {
+        div_floor(self.numer.clone(), &self.denom).0
+    }
+
+    /// Returns the least integer not less than `self` or [None] if it is not representable.
+    pub fn ceil(&self) -> Option<T> {
+        let (q, r) = div_floor(self.numer.clone(), &self.denom);
+        if r.is_zero() {
+            Some(q)
+        } else {
+            q.checked_add(&T::one())
+        }
+    }
+
+    /// Returns the integer part, rounded toward zero.
+    pub fn trunc(&self) -> T {
+        self.numer.clone() / self.denom.clone()
+    }
+
+    /// Returns the exact value of the finite float or [None] if it is not finite or not representable.
+    pub fn from_float_exact<F: FloatCore>(x: F) -> Option<Self>
+    where
+        T: FromPrimitive,
+    {
+        if !x.is_finite() {
+            return None;
+        }
+        let (mantissa, exponent, sign) = x.integer_decode();
+        if mantissa == 0 {
+            return Some(Self::zero());
+        }
+        let shift = mantissa.trailing_zeros();
+        let (mantissa, exponent) = (mantissa >> shift, i32::from(exponent) + shift as i32);
+        let mut numer = T::from_u64(mantissa)?;
+        let mut denom = T::one();
+        let two = T::one() + T::one();
+        for _ in 0..exponent.unsigned_abs() {
+            if exponent > 0 {
+                numer = numer.checked_mul(&two)?;
+            } else {
+                denom = denom.checked_mul(&two)?;
+            }
+        }
+        if sign < 0 {
+            numer = T::zero().checked_sub(&numer)?;
+        }
+        // the odd mantissa is coprime with the power of 2
+        Some(Self { numer, denom })
+    }
+
+    /// Returns the closest fraction to the finite float with the denominator at most `max_denom`
+    /// or [None] if there is no representable one.
+    ///
+    /// It is the same as [best_approximation_float].
+    pub fn approximate_float<F: FloatCore>(x: F, max_denom: &T) -> Option<Self>
+    where
+        T: FromPrimitive + ToPrimitive,
+    {
+        best_approximation_float(x, max_denom)
+    }
+
+    /// Returns the expansion of the fraction in base `radix` or [None] if the period is longer than [u64::MAX]
+    /// digits, which is possible only for the denominators above [u64::MAX].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is less than 2.
+    #[inline]
+    pub fn expansion(&self, radix: u32) -> Option<RationalExpansion<T::Magnitude>>
+    where
+        T: SignMagnitude,
+    {
+        // the denominator is positive
+        expand_fraction(&self.numer, &self.denom, radix)
+    }
+}
+
+fn gcd<T: Clone + Ord + Num + CheckedSub>(mut a: T, mut b: T) -> T {
+    while !b.is_zero() {
+        // the remainder of the division of the minimum of the type by -1 overflows
+        let rem = if T::zero().checked_sub(&b).is_some_and(|b| b.is_one()) {
+            T::zero()
+        } else {
+            a.clone() % b.clone()
+        };
+        a = core::mem::replace(&mut b, rem);
+    }
+    // the remainders of the signed integers take the signs of the dividends, so the result
+    // is made non-negative unless it is the minimum of the type, which does not overflow the divisions
+    if a < T::zero() {
+        T::zero().checked_sub(&a).unwrap_or(a)
+    } else {
+        a
+    }
+}
+
+// `divisor` must be positive
+pub(crate) fn div_floor<T: Clone + Ord + Num>(n: T, divisor: &T) -> (T, T) {
+    let (q, r) = (n.clone() / divisor.clone(), n % divisor.clone());
+    if r < T::zero() {
+        (q - T::one(), r + divisor.clone())
+    } else {
+        (q, r)
+    }
+}
+
+impl<T> PartialOrd for Ratio<T>
+where
+    T: RatioInt,
+{
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Ratio<T>
+where
+    T: RatioInt,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // the fractions are compared term by term of their continued fractions,
+        // which avoids the cross-multiplication that can overflow
+        let (mut a, mut b) = (self.numer.clone(), self.denom.clone());
+        let (mut c, mut d) = (other.numer.clone(), other.denom.clone());
+        let mut is_reversed = false;
+        loop {
+            let (q1, r1) = div_floor(a, &b);
+            let (q2, r2) = div_floor(c, &d);
+            let ordering = match (q1.cmp(&q2), r1.is_zero(), r2.is_zero()) {
+                (Ordering::Equal, false, false) => {
+                    // a/b - q = r1/b and c/d - q = r2/d are compared through their reciprocals
+                    (a, b, c, d) = (b, r1, d, r2);
+                    is_reversed = !is_reversed;
+                    continue;
+                }
+                (Ordering::Equal, true, true) => Ordering::Equal,
+                (Ordering::Equal, true, false) => Ordering::Less,
+                (Ordering::Equal, false, true) => Ordering::Greater,
+                (ordering, _, _) => ordering,
+            };
+            return if is_reversed {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+    }
+}
+
+impl<T> From<T> for Ratio<T>
+where
+    T: RatioInt,
+{
+    #[inline(always)]
+    fn from(n: T) -> Self {
+        Self::from_integer(n)
+    }
+}
+
+macro_rules! impl_op {
+    ($op:ident::$f:ident via $checked:ident, $msg:literal) => {
+        impl<T> $op for Ratio<T>
+        where
+            T: RatioInt,
+        {
+            type Output = Self;
+
+            /// # Panics
+            ///
+            /// Panics if the result is not representable.
+            #[inline]
+            fn $f(self, other: Self) -> Self {
+                self.$checked(&other).expect($msg)
+            }
+        }
+    };
+}
+
+impl_op!(Add::add via checked_add, "attempt to add with overflow");
+impl_op!(Sub::sub via checked_sub, "attempt to subtract with overflow");
+impl_op!(Mul::mul via checked_mul, "attempt to multiply with overflow");
+impl_op!(Div::div via checked_div, "attempt to divide by zero or with overflow");
+
+impl<T> Neg for Ratio<T>
+where
+    T: RatioInt,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if the result is not representable.
+    #[inline]
+    fn neg(self) -> Self {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+/// Displays the integers without the denominator and the other fractions as `numer/denom`.
+impl<T: Display + Num> Display for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom.is_one() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(any(doc, test, doctest, feature = "alloc"))]
+impl<T> ToCanonicalRepresentationBase10AsString for Ratio<T>
+where
+    T: ToCanonicalRepresentationBase10AsString + Num,
+{
+    fn to_canonical_representation_base_10_as_string(&self) -> String {
+        let mut s = self.numer.to_canonical_representation_base_10_as_string();
+        if !self.denom.is_one() {
+            s.push('/');
+            s.push_str(&self.denom.to_canonical_representation_base_10_as_string());
+        }
+        s
+    }
+}
+
+impl<T> ToCanonicalRepresentationBase10Into for Ratio<T>
+where
+    T: ToCanonicalRepresentationBase10Into + Num,
+{
+    fn to_canonical_representation_base_10_into(&self, buf: &mut [u8]) -> Option<usize> {
+        let len = self.numer.to_canonical_representation_base_10_into(buf)?;
+        if self.denom.is_one() {
+            return Some(len);
+        }
+        *buf.get_mut(len)? = b'/';
+        let denom_len = self
+            .denom
+            .to_canonical_representation_base_10_into(&mut buf[len + 1..])?;
+        Some(len + 1 + denom_len)
+    }
+
+    fn extend_with_canonical_representation_base_10<E: Extend<u8>>(&self, sink: &mut E) {
+        self.numer
+            .extend_with_canonical_representation_base_10(sink);
+        if !self.denom.is_one() {
+            sink.extend(*b"/");
+            self.denom
+                .extend_with_canonical_representation_base_10(sink);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::Ratio;
+
+    #[test]
+    fn ratios_are_canonical() {
+        assert_eq!(Ratio::new(-4i8, -6).unwrap().into_parts(), (2, 3));
+        assert_eq!(Ratio::new(0i8, -6).unwrap().into_parts(), (0, 1));
+        assert_eq!(Ratio::new(i8::MIN, i8::MIN).unwrap().into_parts(), (1, 1));
+        assert_eq!(Ratio::new(i8::MIN, 2).unwrap().into_parts(), (-64, 1));
+        assert_eq!(Ratio::new(1i8, i8::MIN), None);
+        assert_eq!(Ratio::new(i8::MIN, -1), None);
+        assert_eq!(Ratio::new(-1i8, i8::MIN), None);
+        assert_eq!(Ratio::new(i64::MIN, -1), None);
+        assert_eq!(Ratio::new(i64::MIN, 1).unwrap().into_parts(), (i64::MIN, 1));
+        assert_eq!(Ratio::new(1u8, 0), None);
+    }
+
+    #[test]
+    fn checked_arithmetic_agrees_with_wider_integers() {
+        // the i8 fractions against the same computations with i64
+        let values: Vec<Ratio<i8>> = (-20i8..=20)
+            .flat_map(|n| (1i8..=12).filter_map(move |d| Ratio::new(n * 6, d * 9)))
+            .collect();
+        let widen =
+            |r: &Ratio<i8>| Ratio::new(i64::from(*r.numer()), i64::from(*r.denom())).unwrap();
+        let narrow = |r: Ratio<i64>| {
+            Some(
+                Ratio::new(
+                    i8::try_from(*r.numer()).ok()?,
+                    i8::try_from(*r.denom()).ok()?,
+                )
+                .unwrap(),
+            )
+        };
+        for x in &values {
+            for y in &values {
+                let (wx, wy) = (widen(x), widen(y));
+                // the sums may fail to be computed only if the numerators overflow on the way
+                let (b, d) = (i64::from(*x.denom()), i64::from(*y.denom()));
+                let g = (1..=b).rev().find(|g| b % g == 0 && d % g == 0).unwrap();
+                let (lhs, rhs) = (wx.numer() * (d / g), wy.numer() * (b / g));
+                for (sum, wide, numer) in [
+                    (x.checked_add(y), wx + wy, lhs + rhs),
+                    (x.checked_sub(y), wx - wy, lhs - rhs),
+                ] {
+                    if sum.is_some() || narrow(wide).is_some() {
+                        let overflows = [lhs, rhs, numer].iter().any(|n| i8::try_from(*n).is_err());
+                        assert_eq!(sum.is_none(), overflows);
+                        assert!(sum.is_none() || sum == narrow(wide));
+                    }
+                }
+                assert_eq!(x.checked_mul(y), narrow(wx * wy));
+                assert_eq!(x.checked_div(y), wx.checked_div(&wy).and_then(narrow));
+                assert_eq!(
+                    x.cmp(y),
+                    (wx.numer() * wy.denom()).cmp(&(wy.numer() * wx.denom()))
+                );
+            }
+            assert_eq!(
+                x.floor(),
+                (*x.numer() as f64 / *x.denom() as f64).floor() as i8
+            );
+            assert_eq!(
+                x.ceil(),
+                Some((*x.numer() as f64 / *x.denom() as f64).ceil() as i8)
+            );
+        }
+        let max = Ratio::from_integer(u8::MAX);
+        assert_eq!(max.checked_add(&Ratio::one()), None);
+        assert_eq!(
+            Ratio::new(255u8, 2)
+                .unwrap()
+                .checked_mul(&Ratio::new(2, 255).unwrap()),
+            Some(Ratio::one())
+        );
+        assert_eq!(Ratio::new(1u8, 2).unwrap().checked_neg(), None);
+        assert!(
+            Ratio::new(u64::MAX - 2, u64::MAX - 1).unwrap()
+                < Ratio::new(u64::MAX - 1, u64::MAX).unwrap()
+        );
+    }
+
+    #[test]
+    fn floats_are_converted() {
+        assert_eq!(Ratio::<i32>::from_float_exact(-2.5f64), Ratio::new(-5, 2));
+        assert_eq!(
+            Ratio::<i64>::from_float_exact(1e10f64),
+            Ratio::new(10_000_000_000, 1)
+        );
+        assert_eq!(Ratio::<u32>::from_float_exact(0.1f64), None);
+        assert_eq!(Ratio::<u8>::from_float_exact(-0.5f32), None);
+        assert_eq!(Ratio::<i32>::from_float_exact(f64::NAN), None);
+        assert_eq!(Ratio::<u8>::from_float_exact(0.0f64), Some(Ratio::zero()));
+
+        use num_bigint::BigInt;
+
+        let r = Ratio::<BigInt>::from_float_exact(0.1f64).unwrap();
+        assert_eq!(*r.denom(), BigInt::from(1u64 << 55));
+        assert_eq!(*r.numer(), BigInt::from(3_602_879_701_896_397u64));
+
+        let pi = core::f64::consts::PI;
+        let cases = [
+            (1, (3, 1)),
+            (7, (22, 7)),
+            (56, (22, 7)),
+            (57, (179, 57)),
+            (113, (355, 113)),
+        ];
+        for (max_denom, (n, d)) in cases {
+            assert_eq!(
+                Ratio::<i32>::approximate_float(pi, &max_denom),
+                Ratio::new(n, d)
+            );
+        }
+        assert_eq!(
+            Ratio::<i32>::approximate_float(-0.75f32, &100),
+            Ratio::new(-3, 4)
+        );
+        assert_eq!(Ratio::<u8>::approximate_float(1000.0f64, &10), None);
+        assert_eq!(
+            Ratio::<u8>::approximate_float(0.001f64, &10),
+            Some(Ratio::zero())
+        );
+    }
+
+    #[test]
+    fn ratios_are_formatted() {
+        use crate::num_sys::base_10::{
+            ToCanonicalRepresentationBase10AsString, ToCanonicalRepresentationBase10Into,
+        };
+
+        let r = Ratio::new(-10i16, 4).unwrap();
+        assert_eq!(r.to_canonical_representation_base_10_as_string(), "-5/2");
+        let mut buf = [0u8; 4];
+        assert_eq!(
+            r.to_canonical_representation_base_10_into(&mut buf),
+            Some(4)
+        );
+        assert_eq!(&buf, b"-5/2");
+        assert_eq!(
+            r.to_canonical_representation_base_10_into(&mut buf[..3]),
+            None
+        );
+        let mut v = Vec::new();
+        Ratio::from_integer(7u8).extend_with_canonical_representation_base_10(&mut v);
+        assert_eq!(v, b"7");
+        assert_eq!(
+            Ratio::new(1i32, 6)
+                .unwrap()
+                .expansion(10)
+                .unwrap()
+                .to_string(),
+            "0.1(6)"
+        );
+        // the period of 1 / (2^127 - 1) has more than u64::MAX digits
+        let r = Ratio::<u128>::new(1, i128::MAX.unsigned_abs()).unwrap();
+        assert!(r.expansion(10).is_none());
+    }
+}